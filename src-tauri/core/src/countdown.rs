@@ -0,0 +1,266 @@
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// Whole days, hours, minutes and seconds in a span of milliseconds
+pub fn time_components(ms: i64) -> (i64, i64, i64, i64) {
+    let total_seconds = ms / 1000;
+    let days = total_seconds / (24 * 60 * 60);
+    let hours = (total_seconds % (24 * 60 * 60)) / (60 * 60);
+    let minutes = (total_seconds % (60 * 60)) / 60;
+    let seconds = total_seconds % 60;
+    (days, hours, minutes, seconds)
+}
+
+// What happens to the part of a unit that's left over when time left is shown in whole units
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Rounding {
+    // 1 minute 59 seconds shows as 1 minute
+    #[default]
+    Truncate,
+    // 1 minute 1 second shows as 2 minutes, so the time shown is never less than what's left
+    Up,
+}
+
+// `ms` as a whole number of `unit_ms`, still in milliseconds
+pub fn round_to_unit(ms: i64, unit_ms: i64, rounding: Rounding) -> i64 {
+    let ms = ms.max(0);
+    let whole = ms - ms % unit_ms;
+    match rounding {
+        Rounding::Up if whole < ms => whole + unit_ms,
+        _ => whole,
+    }
+}
+
+// Share of the countdown that has already elapsed, as a whole percentage
+pub fn percent_elapsed(start: DateTime<Utc>, end: DateTime<Utc>, now: DateTime<Utc>) -> Option<i64> {
+    let total = (end - start).num_milliseconds();
+    if total <= 0 {
+        return None;
+    }
+    let elapsed = (now - start).num_milliseconds().clamp(0, total);
+    Some(elapsed * 100 / total)
+}
+
+// Share of the countdown already behind us, from 0 to 1
+pub fn progress(start: DateTime<Utc>, end: DateTime<Utc>, now: DateTime<Utc>) -> Option<f64> {
+    let total = (end - start).num_milliseconds();
+    (total > 0).then(|| ((now - start).num_milliseconds() as f64 / total as f64).clamp(0.0, 1.0))
+}
+
+// A span in calendar units, the way a person would count it on a calendar
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct CalendarSpan {
+    pub years: i64,
+    pub months: i64,
+    pub days: i64,
+    pub hours: i64,
+    pub minutes: i64,
+    pub seconds: i64,
+}
+
+// Whole months are counted by stepping along the calendar, so their varying lengths and leap
+// years come out right; what's left is split into days and smaller units. Both ends are wall
+// clock times in the same zone. Empty once `to` has passed.
+pub fn calendar_span(from: NaiveDateTime, to: NaiveDateTime) -> CalendarSpan {
+    if to <= from {
+        return CalendarSpan::default();
+    }
+    let shifted = |months: i64| from.checked_add_months(Months::new(u32::try_from(months).ok()?));
+    let mut months = i64::from(to.year() - from.year()) * 12 + i64::from(to.month()) - i64::from(from.month());
+    // Adding months clamps to the end of a shorter month, e.g. Jan 31 plus one month is the
+    // last day of February, so at most one step back is needed
+    while months > 0 && shifted(months).is_none_or(|at| at > to) {
+        months -= 1;
+    }
+    let anchor = shifted(months).unwrap_or(from);
+    let (days, hours, minutes, seconds) = time_components((to - anchor).num_milliseconds());
+    CalendarSpan {
+        years: months / 12,
+        months: months % 12,
+        days,
+        hours,
+        minutes,
+        seconds,
+    }
+}
+
+// Progress against the clock, extrapolated from the latest check-in as if work kept going at the
+// same average rate it has had since the start
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+pub struct Pace {
+    // Percentage points of work per day
+    pub actual_per_day: f64,
+    // What the rest of the work needs to finish on time; None once the deadline has passed
+    pub required_per_day: Option<f64>,
+    // None while no progress has been made, as there's no rate to go by
+    pub projected_finish: Option<DateTime<Utc>>,
+    // How far the projected finish lands past the deadline; negative when it's early
+    pub late_by_seconds: Option<i64>,
+}
+
+const DAY_SECONDS: f64 = 24.0 * 60.0 * 60.0;
+
+pub fn pace(start: DateTime<Utc>, end: DateTime<Utc>, checked_at: DateTime<Utc>, percent_done: f64) -> Option<Pace> {
+    let worked_days = (checked_at - start).num_seconds() as f64 / DAY_SECONDS;
+    if worked_days <= 0.0 {
+        return None;
+    }
+    let remaining = (100.0 - percent_done).max(0.0);
+    let actual_per_day = percent_done / worked_days;
+    let days_left = (end - checked_at).num_seconds() as f64 / DAY_SECONDS;
+    let required_per_day = (days_left > 0.0).then(|| remaining / days_left);
+    let projected_finish = if remaining == 0.0 {
+        Some(checked_at)
+    } else if actual_per_day > 0.0 {
+        checked_at.checked_add_signed(chrono::Duration::seconds((remaining / actual_per_day * DAY_SECONDS) as i64))
+    } else {
+        None
+    };
+    Some(Pace {
+        actual_per_day,
+        required_per_day,
+        projected_finish,
+        late_by_seconds: projected_finish.map(|finish| (finish - end).num_seconds()),
+    })
+}
+
+// A business cycle a countdown can follow to its end
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum CalendarPeriod {
+    Month,
+    Quarter,
+    Year,
+    FiscalQuarter,
+    FiscalYear,
+}
+
+impl CalendarPeriod {
+    fn months(self) -> u32 {
+        match self {
+            CalendarPeriod::Month => 1,
+            CalendarPeriod::Quarter | CalendarPeriod::FiscalQuarter => 3,
+            CalendarPeriod::Year | CalendarPeriod::FiscalYear => 12,
+        }
+    }
+
+    pub fn is_fiscal(self) -> bool {
+        matches!(self, CalendarPeriod::FiscalQuarter | CalendarPeriod::FiscalYear)
+    }
+}
+
+// The period containing `date`, as its first day and the first day of the next period. Fiscal
+// periods count from `fiscal_start_month` (1 for January) instead of January.
+pub fn period_bounds(period: CalendarPeriod, date: NaiveDate, fiscal_start_month: u32) -> (NaiveDate, NaiveDate) {
+    let months = period.months();
+    let year_start = if period.is_fiscal() { fiscal_start_month.clamp(1, 12) } else { 1 };
+    let into_year = (date.month() + 12 - year_start) % 12;
+    let month_start = date.with_day(1).unwrap_or(date);
+    let start = month_start - Months::new(into_year % months);
+    (start, start + Months::new(months))
+}
+
+// Fiscal years are named after the calendar year they end in, so with an October start the
+// year from October 2026 is FY2027
+pub fn fiscal_year(date: NaiveDate, fiscal_start_month: u32) -> i32 {
+    let start = fiscal_start_month.clamp(1, 12);
+    if start == 1 || date.month() < start {
+        date.year()
+    } else {
+        date.year() + 1
+    }
+}
+
+// The ISO year and week `date` falls in; the days around New Year can belong to the
+// neighbouring year's week
+pub fn iso_week(date: NaiveDate) -> (i32, u32) {
+    let week = date.iso_week();
+    (week.year(), week.week())
+}
+
+// Week boundaries crossed between the two dates, counting ISO weeks from Monday: from Friday to
+// next Tuesday is one calendar week even though it's four days
+pub fn calendar_weeks_between(from: NaiveDate, to: NaiveDate) -> i64 {
+    let monday = |date: NaiveDate| date - Days::new(date.weekday().num_days_from_monday().into());
+    ((monday(to) - monday(from)).num_days() / 7).max(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    fn span(years: i64, months: i64, days: i64, hours: i64) -> CalendarSpan {
+        CalendarSpan { years, months, days, hours, ..CalendarSpan::default() }
+    }
+
+    #[test]
+    fn calendar_span_across_leap_day() {
+        assert_eq!(calendar_span(at(2024, 2, 28, 0), at(2024, 3, 1, 0)), span(0, 0, 2, 0));
+        assert_eq!(calendar_span(at(2024, 2, 29, 0), at(2025, 2, 28, 0)), span(1, 0, 0, 0));
+        assert_eq!(calendar_span(at(2024, 2, 29, 0), at(2025, 3, 1, 0)), span(1, 0, 1, 0));
+        assert_eq!(calendar_span(at(2023, 2, 28, 0), at(2024, 2, 29, 0)), span(1, 0, 1, 0));
+        assert_eq!(calendar_span(at(2024, 1, 29, 0), at(2028, 2, 29, 12)), span(4, 1, 0, 12));
+    }
+
+    #[test]
+    fn calendar_span_across_month_end() {
+        assert_eq!(calendar_span(at(2026, 1, 31, 0), at(2026, 2, 28, 0)), span(0, 1, 0, 0));
+        assert_eq!(calendar_span(at(2026, 1, 31, 0), at(2026, 3, 1, 0)), span(0, 1, 1, 0));
+        assert_eq!(calendar_span(at(2026, 3, 31, 0), at(2026, 4, 30, 0)), span(0, 1, 0, 0));
+        assert_eq!(calendar_span(at(2026, 10, 31, 18), at(2026, 11, 30, 6)), span(0, 0, 29, 12));
+        assert_eq!(calendar_span(at(2026, 12, 31, 0), at(2027, 12, 31, 0)), span(1, 0, 0, 0));
+    }
+
+    #[test]
+    fn calendar_span_is_empty_once_passed() {
+        assert_eq!(calendar_span(at(2026, 5, 2, 0), at(2026, 5, 1, 0)), CalendarSpan::default());
+        assert_eq!(calendar_span(at(2026, 5, 1, 0), at(2026, 5, 1, 0)), CalendarSpan::default());
+    }
+
+    #[test]
+    fn iso_week_at_year_boundaries() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        // Monday Dec 29 2025 starts week 1 of 2026
+        assert_eq!(iso_week(date(2025, 12, 28)), (2025, 52));
+        assert_eq!(iso_week(date(2025, 12, 29)), (2026, 1));
+        assert_eq!(iso_week(date(2026, 1, 1)), (2026, 1));
+        // Friday Jan 1 2027 still belongs to the last week of 2026, which has 53
+        assert_eq!(iso_week(date(2026, 12, 31)), (2026, 53));
+        assert_eq!(iso_week(date(2027, 1, 3)), (2026, 53));
+        assert_eq!(iso_week(date(2027, 1, 4)), (2027, 1));
+    }
+
+    #[test]
+    fn calendar_weeks_across_new_year() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        // Friday to the following Tuesday
+        assert_eq!(calendar_weeks_between(date(2026, 12, 25), date(2026, 12, 29)), 1);
+        assert_eq!(calendar_weeks_between(date(2026, 12, 28), date(2027, 1, 3)), 0);
+        assert_eq!(calendar_weeks_between(date(2026, 12, 31), date(2027, 1, 4)), 1);
+        assert_eq!(calendar_weeks_between(date(2027, 1, 4), date(2026, 12, 31)), 0);
+    }
+
+    #[test]
+    fn rounding_truncates_or_rounds_up() {
+        const MINUTE: i64 = 60_000;
+        assert_eq!(round_to_unit(119_000, MINUTE, Rounding::Truncate), MINUTE);
+        assert_eq!(round_to_unit(61_000, MINUTE, Rounding::Up), 2 * MINUTE);
+        assert_eq!(round_to_unit(1, MINUTE, Rounding::Up), MINUTE);
+        assert_eq!(round_to_unit(1, MINUTE, Rounding::Truncate), 0);
+        // Already whole, so both agree
+        assert_eq!(round_to_unit(2 * MINUTE, MINUTE, Rounding::Truncate), 2 * MINUTE);
+        assert_eq!(round_to_unit(2 * MINUTE, MINUTE, Rounding::Up), 2 * MINUTE);
+        // Past the deadline counts as nothing left
+        assert_eq!(round_to_unit(-30_000, MINUTE, Rounding::Up), 0);
+    }
+
+    #[test]
+    fn time_components_split_a_span() {
+        assert_eq!(time_components(((2 * 24 + 3) * 60 + 4) * 60_000 + 5_999), (2, 3, 4, 5));
+    }
+}
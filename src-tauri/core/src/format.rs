@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockFormat {
+    // Follow the conventions of the locale's region
+    #[default]
+    Auto,
+    H12,
+    H24,
+}
+
+// Regions that write the month before the day
+const MONTH_FIRST_REGIONS: [&str; 4] = ["US", "PH", "FM", "MH"];
+// Regions where the 12-hour clock is the everyday default
+const TWELVE_HOUR_REGIONS: [&str; 10] = ["US", "CA", "AU", "NZ", "IN", "PH", "PK", "BD", "EG", "SA"];
+
+pub fn month_first(region: &str) -> bool {
+    MONTH_FIRST_REGIONS.contains(&region)
+}
+
+pub fn uses_12_hour_clock(format: ClockFormat, region: &str) -> bool {
+    match format {
+        ClockFormat::H12 => true,
+        ClockFormat::H24 => false,
+        ClockFormat::Auto => TWELVE_HOUR_REGIONS.contains(&region),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_clock_follows_region() {
+        assert!(uses_12_hour_clock(ClockFormat::Auto, "US"));
+        assert!(!uses_12_hour_clock(ClockFormat::Auto, "BR"));
+        assert!(uses_12_hour_clock(ClockFormat::H12, "BR"));
+        assert!(!uses_12_hour_clock(ClockFormat::H24, "US"));
+    }
+
+    #[test]
+    fn month_first_regions() {
+        assert!(month_first("US"));
+        assert!(!month_first("GB"));
+    }
+}
@@ -0,0 +1,7 @@
+// Countdown logic with no ties to the desktop app: time arithmetic, reminder schedules, the
+// event queue, region conventions and JSON storage
+pub mod countdown;
+pub mod format;
+pub mod persistence;
+pub mod queue;
+pub mod schedule;
@@ -0,0 +1,46 @@
+use std::fs;
+use std::path::Path;
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// Missing files are normal on first launch; corrupt ones are reported and replaced by defaults
+// rather than preventing the app from starting. The corrupt file is moved aside first so the
+// next save can't overwrite what's left in it.
+pub fn load_json<T: DeserializeOwned + Default>(path: &Path) -> T {
+    match fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+            eprintln!("Failed to parse {}: {}", path.display(), e);
+            set_aside(path);
+            T::default()
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => T::default(),
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", path.display(), e);
+            T::default()
+        }
+    }
+}
+
+// Renames a file that couldn't be read to <name>.corrupt-<timestamp> next to it
+fn set_aside(path: &Path) {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".corrupt-{}", Utc::now().format("%Y%m%d%H%M%S")));
+    let aside = path.with_file_name(name);
+    match fs::rename(path, &aside) {
+        Ok(()) => eprintln!("Kept the unreadable file as {}", aside.display()),
+        Err(e) => eprintln!("Failed to set aside {}: {}", path.display(), e),
+    }
+}
+
+// Writes to a temporary file first so a crash mid-write never leaves a truncated file behind
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    let contents = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", path.display(), e))?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, contents).map_err(|e| format!("Failed to write {}: {}", tmp_path.display(), e))?;
+    fs::rename(&tmp_path, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+}
@@ -0,0 +1,64 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use chrono::{DateTime, Utc};
+
+// Whatever owns a slot in the queue: the primary countdown or one stored timer. Each source
+// has at most one pending entry, its earliest upcoming event.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum EventSource {
+    Primary,
+    Timer(u64),
+}
+
+// Min-heap of upcoming events. Replanning a source doesn't search the heap; the old entry
+// is left in place and skipped once it surfaces, because `planned` no longer agrees with it.
+#[derive(Default)]
+pub struct EventQueue {
+    heap: BinaryHeap<Reverse<(DateTime<Utc>, EventSource)>>,
+    planned: HashMap<EventSource, DateTime<Utc>>,
+}
+
+impl EventQueue {
+    pub fn plan(&mut self, source: EventSource, at: Option<DateTime<Utc>>) {
+        match at {
+            Some(at) => {
+                if self.planned.insert(source, at) != Some(at) {
+                    self.heap.push(Reverse((at, source)));
+                }
+            }
+            None => {
+                self.planned.remove(&source);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.planned.clear();
+    }
+
+    pub fn next_at(&mut self) -> Option<DateTime<Utc>> {
+        while let Some(Reverse((at, source))) = self.heap.peek() {
+            if self.planned.get(source) == Some(at) {
+                return Some(*at);
+            }
+            self.heap.pop();
+        }
+        None
+    }
+
+    // Removes and returns every source whose event is due; callers replan them after handling
+    pub fn pop_due(&mut self, now: DateTime<Utc>) -> Vec<EventSource> {
+        let mut due = Vec::new();
+        while let Some(at) = self.next_at() {
+            if at > now {
+                break;
+            }
+            if let Some(Reverse((_, source))) = self.heap.pop() {
+                self.planned.remove(&source);
+                due.push(source);
+            }
+        }
+        due
+    }
+}
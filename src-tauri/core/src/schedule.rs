@@ -0,0 +1,261 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Timelike, Utc};
+
+// Reminders without a custom schedule keep the original cadence of one every 6 hours
+pub const DEFAULT_REMINDER_INTERVAL_SECS: i64 = 6 * 60 * 60;
+
+// How far ahead a cron expression is searched before it is considered to never fire
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 5;
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const DAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+// Standard 5-field cron expression (minute hour day-of-month month day-of-week),
+// evaluated in the local timezone since "09:00" means the user's 09:00
+#[derive(Clone)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "Invalid cron expression '{}': expected 5 fields (minute hour day-of-month month day-of-week)",
+                expression
+            ));
+        }
+
+        let mut days_of_week = parse_field(fields[4], 0, 7, &DAY_NAMES, "day-of-week")?;
+        // Both 0 and 7 mean Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            minutes: parse_field(fields[0], 0, 59, &[], "minute")?,
+            hours: parse_field(fields[1], 0, 23, &[], "hour")?,
+            days_of_month: parse_field(fields[2], 1, 31, &[], "day-of-month")?,
+            months: parse_field(fields[3], 1, 12, &MONTH_NAMES, "month")?,
+            days_of_week,
+            // Like classic cron, a field starting with "*" (including steps such as "*/2") still
+            // filters by its values but doesn't switch the day fields to either-may-match
+            day_of_month_restricted: !fields[2].starts_with('*'),
+            day_of_week_restricted: !fields[4].starts_with('*'),
+        })
+    }
+
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+        let dom = self.days_of_month & (1 << date.day()) != 0;
+        let dow = self.days_of_week & (1 << date.weekday().num_days_from_sunday()) != 0;
+        // Like classic cron, restricting both day fields means either one may match
+        if self.day_of_month_restricted && self.day_of_week_restricted {
+            dom || dow
+        } else {
+            dom && dow
+        }
+    }
+
+    // First matching minute strictly after `after`
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let local = after.with_timezone(&Local).naive_local();
+        let start = local.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+
+        let mut date = start.date();
+        for _ in 0..MAX_LOOKAHEAD_DAYS {
+            if self.matches_date(date) {
+                for hour in (0..24).filter(|h| self.hours & (1 << h) != 0) {
+                    for minute in (0..60).filter(|m| self.minutes & (1 << m) != 0) {
+                        let candidate = date.and_hms_opt(hour, minute, 0)?;
+                        if candidate < start {
+                            continue;
+                        }
+                        // Skips times that don't exist because of a DST jump
+                        if let Some(resolved) = Local.from_local_datetime(&candidate).earliest() {
+                            return Some(resolved.with_timezone(&Utc));
+                        }
+                    }
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+}
+
+fn parse_value(value: &str, names: &[&str], min: u32) -> Option<u32> {
+    if let Ok(number) = value.parse::<u32>() {
+        return Some(number);
+    }
+    let value = value.to_lowercase();
+    names.iter().position(|n| *n == value).map(|i| i as u32 + min)
+}
+
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str], name: &str) -> Result<u64, String> {
+    let invalid = || format!("Invalid cron {} field '{}'", name, field);
+    let mut mask = 0u64;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| invalid())?;
+                if step == 0 {
+                    return Err(invalid());
+                }
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+
+        let (low, high) = if range == "*" {
+            (min, max)
+        } else if let Some((low, high)) = range.split_once('-') {
+            (
+                parse_value(low, names, min).ok_or_else(invalid)?,
+                parse_value(high, names, min).ok_or_else(invalid)?,
+            )
+        } else {
+            let value = parse_value(range, names, min).ok_or_else(invalid)?;
+            // "5/15" means "from 5 to the end of the range every 15"
+            (value, if step.is_some() { max } else { value })
+        };
+
+        if low < min || high > max || low > high {
+            return Err(format!(
+                "Invalid cron {} field '{}': values must be between {} and {}",
+                name, field, min, max
+            ));
+        }
+
+        let mut value = low;
+        while value <= high {
+            mask |= 1 << value;
+            value += step.unwrap_or(1);
+        }
+    }
+
+    Ok(mask)
+}
+
+#[derive(Clone)]
+pub enum ReminderSchedule {
+    Interval(Duration),
+    Cron(CronSchedule),
+    // Several at once; whichever comes first
+    All(Vec<ReminderSchedule>),
+}
+
+impl Default for ReminderSchedule {
+    fn default() -> Self {
+        ReminderSchedule::Interval(Duration::seconds(DEFAULT_REMINDER_INTERVAL_SECS))
+    }
+}
+
+impl ReminderSchedule {
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            ReminderSchedule::Interval(every) => Some(after + *every),
+            ReminderSchedule::Cron(cron) => cron.next_after(after),
+            ReminderSchedule::All(schedules) => schedules.iter().filter_map(|schedule| schedule.next_after(after)).min(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn parses_lists_ranges_steps_and_names() {
+        let cron = CronSchedule::parse("0,30 9-17/4 * jan-mar MON-fri").unwrap();
+        assert_eq!(cron.minutes, 1 | 1 << 30);
+        assert_eq!(cron.hours, 1 << 9 | 1 << 13 | 1 << 17);
+        assert_eq!(cron.months, 1 << 1 | 1 << 2 | 1 << 3);
+        assert_eq!(cron.days_of_week, 0b11_1110);
+
+        let from_five = CronSchedule::parse("5/20 * * * *").unwrap();
+        assert_eq!(from_five.minutes, 1 << 5 | 1 << 25 | 1 << 45);
+    }
+
+    #[test]
+    fn seven_is_sunday() {
+        let cron = CronSchedule::parse("0 9 * * 7").unwrap();
+        assert_eq!(cron.days_of_week, 1);
+        assert!(cron.matches_date(date(2026, 10, 18)));
+        assert!(!cron.matches_date(date(2026, 10, 19)));
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for expression in ["", "* * * *", "* * * * * *", "60 * * * *", "* 24 * * *", "* * 0 * *", "*/0 * * * *", "5-1 * * * *", "* * * foo *"] {
+            assert!(CronSchedule::parse(expression).is_err(), "{}", expression);
+        }
+    }
+
+    #[test]
+    fn stepped_wildcard_filters_without_or_ing_the_day_fields() {
+        let cron = CronSchedule::parse("0 9 */2 * *").unwrap();
+        assert!(cron.matches_date(date(2026, 10, 1)));
+        assert!(!cron.matches_date(date(2026, 10, 2)));
+        assert!(cron.matches_date(date(2026, 10, 3)));
+
+        // Odd-dated Mondays only, not every Monday or every odd day
+        let cron = CronSchedule::parse("0 9 */2 * mon").unwrap();
+        assert!(!cron.day_of_month_restricted);
+        assert!(cron.day_of_week_restricted);
+        assert!(cron.matches_date(date(2026, 10, 19)));
+        assert!(!cron.matches_date(date(2026, 10, 26)));
+        assert!(!cron.matches_date(date(2026, 10, 21)));
+
+        // The 1st, only when it falls on a Sunday, Tuesday, Thursday or Saturday
+        let cron = CronSchedule::parse("0 9 1 * */2").unwrap();
+        assert!(cron.day_of_month_restricted);
+        assert!(!cron.day_of_week_restricted);
+        assert!(cron.matches_date(date(2026, 11, 1)));
+        assert!(!cron.matches_date(date(2026, 11, 3)));
+        assert!(!cron.matches_date(date(2027, 1, 1)));
+
+        let cron = CronSchedule::parse("0 9 * * */2").unwrap();
+        assert!(cron.matches_date(date(2026, 10, 18)));
+        assert!(!cron.matches_date(date(2026, 10, 19)));
+    }
+
+    #[test]
+    fn both_restricted_day_fields_match_either() {
+        let cron = CronSchedule::parse("0 9 13 * fri").unwrap();
+        assert!(cron.matches_date(date(2026, 10, 13)));
+        assert!(cron.matches_date(date(2026, 10, 16)));
+        assert!(!cron.matches_date(date(2026, 10, 14)));
+    }
+
+    #[test]
+    fn next_after_is_strictly_later_and_on_the_schedule() {
+        let cron = CronSchedule::parse("*/15 * * * *").unwrap();
+        let after = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        let next = cron.next_after(after).unwrap();
+        assert!(next > after);
+        assert!(next - after <= Duration::minutes(15));
+        assert_eq!(next.with_timezone(&Local).minute() % 15, 0);
+    }
+
+    #[test]
+    fn impossible_date_never_fires() {
+        let cron = CronSchedule::parse("0 0 31 feb *").unwrap();
+        assert_eq!(cron.next_after(Utc::now()), None);
+    }
+}
@@ -0,0 +1,239 @@
+## Notifications
+
+reminder-title = Hourglass Reminder
+reminder-no-dates = ⏳ Time keeps flowing... Set your dates to see time remaining!
+reminder-expired = ⏰ Time's up! Your hourglass has run out of sand.
+reminder-final-hour = 🔥 Final hour! Only { $duration } left — make them count.
+reminder-final-day = ⚡ Final stretch: { $duration } left. Focus on what matters most.
+reminder-casual = { $duration } left
+milestone-due = Milestone due: { $name }
+milestone-reminder = { $name } is due in { $duration }
+checkin-behind-pace = { $done }% done with { $elapsed }% of the time gone. You're behind pace.
+projection-late = At this pace you'll finish { $duration } late
+projection-on-time = At this pace you'll finish on time
+goal-met = Goal met: { $done } of { $target } minutes today
+goal-missed = Goal missed: { $done } of { $target } minutes today. It starts over now.
+remote-deadline-moved = The deadline moved from { $from } to { $to }
+reminder-remaining = ⏳ Time remaining: { $duration }
+reminder-ends = Ends { $date }.
+pace-used = You've used { $percent }% of the time. { $outlook }
+pace-almost-done = Almost all of the sand has fallen.
+pace-wrap-up = Most of the sand has fallen — time to wrap things up.
+pace-halfway = You're past the halfway mark.
+pace-room-to-plan = A good part of the sand has fallen, but there's still room to plan.
+pace-plenty-left = Plenty of sand left, but it keeps falling.
+catch-up-title = While you were away
+catch-up-line = { $title }: { $duration } left
+catch-up-line-expired = { $title }: time's up
+catch-up-downtime-title = Missed while Hourglass was away
+catch-up-gap = Hourglass was closed or asleep for { $duration }.
+catch-up-line-milestone = { $title }: reached { $milestone }
+streak-milestone-days = { $count } days in a row. Keep it going!
+streak-milestone-weeks = { $count } weeks in a row. Keep it going!
+primary-countdown-label = Countdown
+test-notification-title = Test Notification
+test-notification-body = This is a test notification from Hourglass!
+share-sentence = { $duration } until { $title } — tracked with Hourglass
+share-sentence-untitled = { $duration } left — tracked with Hourglass
+share-sentence-ended = { $title } is here — tracked with Hourglass
+share-sentence-ended-untitled = Time's up — tracked with Hourglass
+
+## Accessibility
+
+announcement = { $duration } left, { $percent }% of the time used. Ends { $date }.
+announcement-without-progress = { $duration } left. Ends { $date }.
+announcement-expired = The countdown has finished.
+announcement-no-dates = No countdown is set.
+
+## Power
+
+keep-awake-reason = A countdown is about to end
+keep-display-on-reason = The countdown is on screen and about to end
+
+## Expiry actions
+
+action-lock-screen = lock the screen
+action-suspend = go to sleep
+action-shut-down = shut down
+action-warning = Time's up. The computer will { $action } in { $seconds } seconds — open Hourglass to cancel.
+
+## Focus
+
+break-window-title = Time for a break
+alert-window-title = Hourglass reminder
+blocked-app-warning = { $app } is on your blocklist. Close it to stay focused.
+blocked-app-closed = { $app } was closed because it's on your blocklist.
+
+## Dates and durations
+
+duration-years = { $count ->
+        [one] 1 year
+       *[other] { $count } years
+    }
+duration-months = { $count ->
+        [one] 1 month
+       *[other] { $count } months
+    }
+duration-days = { $count ->
+        [one] 1 day
+       *[other] { $count } days
+    }
+duration-hours = { $count ->
+        [one] 1 hour
+       *[other] { $count } hours
+    }
+duration-minutes = { $count ->
+        [one] 1 minute
+       *[other] { $count } minutes
+    }
+casual-under-minute = less than a minute
+casual-few-minutes = a few minutes
+casual-minutes = about { $count } minutes
+casual-hours = { $count ->
+        [one] about an hour
+       *[other] about { $count } hours
+    }
+casual-about-day = about a day
+casual-over-day = just over a day
+casual-days = about { $count } days
+casual-weeks = { $count ->
+        [one] about a week
+       *[other] about { $count } weeks
+    }
+casual-months = { $count ->
+        [one] about a month
+       *[other] about { $count } months
+    }
+casual-years = { $count ->
+        [one] about a year
+       *[other] about { $count } years
+    }
+date-day-first = { $weekday }, { $day } { $month }
+date-day-first-with-year = { $weekday }, { $day } { $month } { $year }
+date-month-first = { $weekday }, { $month } { $day }
+date-month-first-with-year = { $weekday }, { $month } { $day }, { $year }
+date-time = { $date }, { $time }
+iso-week = W{ $week }
+zone-time-other-day = { $time } ({ $sign }{ $count ->
+        [one] 1 day
+       *[other] { $count } days
+    })
+time-am = { $hour }:{ $minute } AM
+time-pm = { $hour }:{ $minute } PM
+month-1 = Jan
+month-2 = Feb
+month-3 = Mar
+month-4 = Apr
+month-5 = May
+month-6 = Jun
+month-7 = Jul
+month-8 = Aug
+month-9 = Sep
+month-10 = Oct
+month-11 = Nov
+month-12 = Dec
+weekday-1 = Mon
+weekday-2 = Tue
+weekday-3 = Wed
+weekday-4 = Thu
+weekday-5 = Fri
+weekday-6 = Sat
+weekday-7 = Sun
+
+## Tray
+
+tray-title = Hourglass
+tray-tooltip = Hourglass - Time Tracker
+tray-tooltip-ends = Hourglass - ends { $date }
+tray-timer-from-clipboard = New timer from clipboard
+tray-quit = Quit
+clock-drift-title = Your clock is off
+clock-drift-body = The system clock is { $seconds } seconds away from the correct time, so countdowns and reminders are off too. Check your date and time settings.
+
+## Timers
+
+previous-countdown-label = Previous countdown
+preset-end-of-month = End of month
+preset-end-of-quarter = End of quarter
+preset-end-of-year = End of year
+preset-end-of-fiscal-quarter = End of fiscal quarter
+preset-end-of-fiscal-year = End of fiscal year
+timeline-start = Start
+fiscal-year-label = FY{ $year }
+
+## Errors
+
+error-timer-not-found = Timer { $id } not found
+error-invalid-color = Invalid color "{ $color }": expected a hex color like #f59e0b
+error-invalid-icon = Invalid icon "{ $icon }": expected a single emoji or symbol
+error-timer-already-trashed = Timer { $id } is already in the trash
+error-timer-not-active = Only active timers can be archived (timer { $id })
+error-timer-not-archived = Timer { $id } is not archived
+error-timer-not-trashed = Timer { $id } is not in the trash
+error-empty-tag = Tag must not be empty
+error-tag-no-reminder-days = Select at least one reminder day, or turn notifications off for this tag instead
+error-note-too-long = Note is too long (maximum { $max } characters)
+error-negative-expiring-within = "Expiring within" can't be a negative number of days
+error-reminder-in-past = Reminder must be in the future
+error-reminder-exists = A reminder already exists at that time
+error-reminder-not-found = No reminder exists at that time
+error-relative-reminder-not-positive = Relative reminders must be a positive number of seconds before the end date
+error-schedule-interval-too-short = Reminder intervals must be at least { $min } seconds
+error-timer-dates-not-set = Timer dates not set
+error-display-not-found = There's no display number { $index }
+error-window-opacity = Opacity must be between { $min }% and 100%
+error-passcode-too-short = The passcode needs at least { $min } characters
+error-wrong-passcode = That passcode isn't right
+error-passcode-required = This needs the passcode
+error-invalid-viewer-source = { $source } isn't an http:// or https:// address
+error-invalid-webhook-url = { $url } isn't an http:// or https:// address
+error-webhook-route-without-url = a route sends to the webhook channel, but webhook_url isn't set
+error-invalid-smtp-host = { $host } isn't an SMTP server name like smtp.example.com
+error-smtp-port = The SMTP port can't be 0
+error-invalid-email-address = { $address } isn't an email address
+error-email-route-without-smtp = a route sends to the email channel, but smtp_host, email_from and email_to aren't all set
+error-period-dates = Couldn't work out the dates of that period in the local time zone
+error-fiscal-year-start = Pick the month the fiscal year starts in, from 1 (January) to 12 (December)
+error-milestone-name = Give the milestone a name
+error-milestone-after-end = A milestone can't be due after the timer ends
+error-milestone-not-found = Milestone { $id } not found
+error-checkin-percent = Progress has to be between 0 and 100 percent
+error-goal-target = A daily goal needs between 1 minute and 24 hours
+error-goal-reset-time = Give the reset time as HH:MM, e.g. 18:00
+error-timer-not-goal = Timer isn't a daily goal
+error-unknown-timezone = { $zone } isn't a known time zone, e.g. Europe/Berlin
+error-invalid-remote-url = { $url } isn't an http:// or https:// address
+error-remote-no-end-date = The remote deadline has no end date
+error-timer-not-subscribed = Timer doesn't follow a remote deadline
+error-viewer-read-only = This window is showing another computer's countdown and can't change it
+error-kiosk-locked = Hourglass is in presentation mode; enter the passcode to make changes
+error-timer-not-recurring = This timer doesn't repeat
+error-clipboard-empty = The clipboard doesn't contain any text
+error-no-date-in-clipboard = Couldn't find a date in the copied text
+error-unreadable-file = Couldn't read the file: { $reason }
+error-newer-file-version = This file was made by a newer version of Hourglass
+error-no-events-in-file = The file doesn't contain any events
+error-invalid-date = Invalid { $field } format: { $reason }
+error-end-before-start = End date must be after start date
+error-no-reminder-days = Select at least one reminder day, or turn notifications off instead
+error-unsupported-locale = Unsupported locale '{ $locale }': use one of { $available }
+error-announcement-interval = Announcement interval must be between 1 and { $max } minutes
+error-idle-threshold = Idle threshold must be between 1 and { $max } minutes
+error-keep-awake-minutes = Keep-awake window must be between 1 and { $max } minutes
+error-keep-display-on-minutes = Keep-display-on window must be between 1 and { $max } minutes
+error-break-minutes = Break length must be between 1 and { $max } minutes
+error-ntp-server = Enter an NTP server to check the clock against
+error-clock-drift-threshold = The clock drift threshold must be at least 1 second
+error-empty-blocklist-entry = Blocklist entries can't be empty
+error-invalid-blocked-site = { $site } isn't a website address
+error-local-api-port = The local API port must be { $min } or higher
+error-invalid-bind-address = { $address } isn't an IP address
+error-invalid-cors-origin = { $origin } isn't an origin like https://example.com
+error-api-client-name-required = API clients need a name
+error-invalid-setting = { $field }: { $message } (for example { $example })
+error-unreadable-settings = The settings file isn't valid JSON: { $reason }
+cli-no-countdown = No countdown to show. Open Hourglass with the local API turned on, or add a timer.
+field-start-date = start date
+field-end-date = end date
+field-reminder-date = reminder date
+field-milestone-date = Milestone date
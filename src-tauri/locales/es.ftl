@@ -0,0 +1,242 @@
+## Notifications
+
+reminder-title = Recordatorio de Hourglass
+reminder-no-dates = ⏳ El tiempo sigue corriendo... ¡Define tus fechas para ver el tiempo restante!
+reminder-expired = ⏰ ¡Se acabó el tiempo! Tu reloj de arena se ha quedado sin arena.
+reminder-final-hour = 🔥 ¡Última hora! Solo { $minutes ->
+        [one] queda
+       *[other] quedan
+    } { $duration }: haz que cuenten.
+reminder-final-day = ⚡ Recta final: { $duration } restantes. Concéntrate en lo más importante.
+reminder-casual = Quedan { $duration }
+milestone-due = Vence el hito: { $name }
+milestone-reminder = { $name } vence en { $duration }
+checkin-behind-pace = { $done }% hecho con el { $elapsed }% del tiempo consumido. Vas por detrás del ritmo.
+projection-late = A este ritmo terminarás con { $duration } de retraso
+projection-on-time = A este ritmo terminarás a tiempo
+goal-met = Objetivo cumplido: { $done } de { $target } minutos hoy
+goal-missed = Objetivo no cumplido: { $done } de { $target } minutos hoy. Empieza de nuevo ahora.
+remote-deadline-moved = La fecha límite pasó de { $from } a { $to }
+reminder-remaining = ⏳ Tiempo restante: { $duration }
+reminder-ends = Termina el { $date }.
+pace-used = Has usado el { $percent }% del tiempo. { $outlook }
+pace-almost-done = Casi toda la arena ha caído.
+pace-wrap-up = La mayor parte de la arena ha caído: es hora de ir cerrando.
+pace-halfway = Ya pasaste la mitad.
+pace-room-to-plan = Buena parte de la arena ha caído, pero aún hay margen para planificar.
+pace-plenty-left = Queda mucha arena, pero sigue cayendo.
+catch-up-title = Mientras no estabas
+catch-up-line = { $title }: quedan { $duration }
+catch-up-line-expired = { $title }: se acabó el tiempo
+catch-up-downtime-title = Lo que pasó mientras Hourglass no estaba
+catch-up-gap = Hourglass estuvo cerrado o en reposo durante { $duration }.
+catch-up-line-milestone = { $title }: se alcanzó { $milestone }
+streak-milestone-days = ¡{ $count } días seguidos! Sigue así.
+streak-milestone-weeks = ¡{ $count } semanas seguidas! Sigue así.
+primary-countdown-label = Cuenta atrás
+test-notification-title = Notificación de prueba
+test-notification-body = ¡Esta es una notificación de prueba de Hourglass!
+share-sentence = { $duration } para { $title } — con Hourglass
+share-sentence-untitled = Quedan { $duration } — con Hourglass
+share-sentence-ended = ¡{ $title } ya llegó! — con Hourglass
+share-sentence-ended-untitled = Se acabó el tiempo — con Hourglass
+
+## Accessibility
+
+announcement = Quedan { $duration }, { $percent }% del tiempo usado. Termina el { $date }.
+announcement-without-progress = Quedan { $duration }. Termina el { $date }.
+announcement-expired = La cuenta atrás ha terminado.
+announcement-no-dates = No hay ninguna cuenta atrás definida.
+
+## Power
+
+keep-awake-reason = Una cuenta atrás está a punto de terminar
+keep-display-on-reason = La cuenta atrás está en pantalla y a punto de terminar
+
+## Expiry actions
+
+action-lock-screen = bloqueará la pantalla
+action-suspend = entrará en suspensión
+action-shut-down = se apagará
+action-warning = Se acabó el tiempo. El equipo { $action } en { $seconds } segundos: abre Hourglass para cancelar.
+
+## Focus
+
+break-window-title = Hora de un descanso
+alert-window-title = Recordatorio de Hourglass
+blocked-app-warning = { $app } está en tu lista de bloqueo. Ciérrala para mantener la concentración.
+blocked-app-closed = Se cerró { $app } porque está en tu lista de bloqueo.
+
+## Dates and durations
+
+duration-years = { $count ->
+        [one] 1 año
+       *[other] { $count } años
+    }
+duration-months = { $count ->
+        [one] 1 mes
+       *[other] { $count } meses
+    }
+duration-days = { $count ->
+        [one] 1 día
+       *[other] { $count } días
+    }
+duration-hours = { $count ->
+        [one] 1 hora
+       *[other] { $count } horas
+    }
+duration-minutes = { $count ->
+        [one] 1 minuto
+       *[other] { $count } minutos
+    }
+casual-under-minute = menos de un minuto
+casual-few-minutes = unos pocos minutos
+casual-minutes = unos { $count } minutos
+casual-hours = { $count ->
+        [one] una hora más o menos
+       *[other] unas { $count } horas
+    }
+casual-about-day = un día más o menos
+casual-over-day = poco más de un día
+casual-days = unos { $count } días
+casual-weeks = { $count ->
+        [one] una semana más o menos
+       *[other] unas { $count } semanas
+    }
+casual-months = { $count ->
+        [one] un mes más o menos
+       *[other] unos { $count } meses
+    }
+casual-years = { $count ->
+        [one] un año más o menos
+       *[other] unos { $count } años
+    }
+date-day-first = { $weekday }, { $day } de { $month }
+date-day-first-with-year = { $weekday }, { $day } de { $month } de { $year }
+date-month-first = { $weekday }, { $month } { $day }
+date-month-first-with-year = { $weekday }, { $month } { $day } de { $year }
+date-time = { $date }, { $time }
+iso-week = S{ $week }
+zone-time-other-day = { $time } ({ $sign }{ $count ->
+        [one] 1 día
+       *[other] { $count } días
+    })
+time-am = { $hour }:{ $minute } a. m.
+time-pm = { $hour }:{ $minute } p. m.
+month-1 = ene
+month-2 = feb
+month-3 = mar
+month-4 = abr
+month-5 = may
+month-6 = jun
+month-7 = jul
+month-8 = ago
+month-9 = sept
+month-10 = oct
+month-11 = nov
+month-12 = dic
+weekday-1 = lun
+weekday-2 = mar
+weekday-3 = mié
+weekday-4 = jue
+weekday-5 = vie
+weekday-6 = sáb
+weekday-7 = dom
+
+## Tray
+
+tray-title = Hourglass
+tray-tooltip = Hourglass - Control del tiempo
+tray-tooltip-ends = Hourglass - termina el { $date }
+tray-timer-from-clipboard = Nuevo temporizador desde el portapapeles
+tray-quit = Salir
+clock-drift-title = Tu reloj no está en hora
+clock-drift-body = El reloj del sistema está desfasado { $seconds } segundos respecto a la hora correcta, así que las cuentas atrás y los recordatorios también. Revisa la configuración de fecha y hora.
+
+## Timers
+
+previous-countdown-label = Cuenta atrás anterior
+preset-end-of-month = Fin de mes
+preset-end-of-quarter = Fin de trimestre
+preset-end-of-year = Fin de año
+preset-end-of-fiscal-quarter = Fin del trimestre fiscal
+preset-end-of-fiscal-year = Fin del año fiscal
+timeline-start = Inicio
+fiscal-year-label = AF{ $year }
+
+## Errors
+
+error-timer-not-found = No se encontró el temporizador { $id }
+error-invalid-color = Color no válido "{ $color }": se esperaba un color hexadecimal como #f59e0b
+error-invalid-icon = Icono no válido "{ $icon }": se esperaba un solo emoji o símbolo
+error-timer-already-trashed = El temporizador { $id } ya está en la papelera
+error-timer-not-active = Solo se pueden archivar temporizadores activos (temporizador { $id })
+error-timer-not-archived = El temporizador { $id } no está archivado
+error-timer-not-trashed = El temporizador { $id } no está en la papelera
+error-empty-tag = La etiqueta no puede estar vacía
+error-tag-no-reminder-days = Elige al menos un día de recordatorio o desactiva las notificaciones de esta etiqueta
+error-note-too-long = La nota es demasiado larga (máximo { $max } caracteres)
+error-negative-expiring-within = "Vence en" no puede ser un número negativo de días
+error-reminder-in-past = El recordatorio debe estar en el futuro
+error-reminder-exists = Ya hay un recordatorio a esa hora
+error-reminder-not-found = No hay ningún recordatorio a esa hora
+error-relative-reminder-not-positive = Los recordatorios relativos deben ser un número positivo de segundos antes de la fecha de fin
+error-schedule-interval-too-short = Los intervalos de recordatorio deben ser de al menos { $min } segundos
+error-timer-dates-not-set = Las fechas del temporizador no están definidas
+error-display-not-found = No existe la pantalla número { $index }
+error-window-opacity = La opacidad debe estar entre { $min } % y 100 %
+error-passcode-too-short = El código necesita al menos { $min } caracteres
+error-wrong-passcode = Ese código no es correcto
+error-passcode-required = Esto requiere el código
+error-invalid-viewer-source = { $source } no es una dirección http:// o https://
+error-invalid-webhook-url = { $url } no es una dirección http:// o https://
+error-webhook-route-without-url = una ruta envía al canal webhook, pero webhook_url no está configurado
+error-invalid-smtp-host = { $host } no es un nombre de servidor SMTP como smtp.example.com
+error-smtp-port = El puerto SMTP no puede ser 0
+error-invalid-email-address = { $address } no es una dirección de correo electrónico
+error-email-route-without-smtp = una ruta envía al canal de correo, pero smtp_host, email_from y email_to no están todos configurados
+error-period-dates = No se pudieron calcular las fechas de ese periodo en la zona horaria local
+error-fiscal-year-start = Elige el mes en que empieza el año fiscal, de 1 (enero) a 12 (diciembre)
+error-milestone-name = Ponle un nombre al hito
+error-milestone-after-end = Un hito no puede vencer después de que termine el temporizador
+error-milestone-not-found = No se encontró el hito { $id }
+error-checkin-percent = El progreso tiene que estar entre 0 y 100 por ciento
+error-goal-target = Un objetivo diario necesita entre 1 minuto y 24 horas
+error-goal-reset-time = Indica la hora de reinicio como HH:MM, p. ej. 18:00
+error-timer-not-goal = El temporizador no es un objetivo diario
+error-unknown-timezone = { $zone } no es una zona horaria conocida, p. ej. Europe/Madrid
+error-invalid-remote-url = { $url } no es una dirección http:// o https://
+error-remote-no-end-date = La fecha límite remota no tiene fecha de fin
+error-timer-not-subscribed = El temporizador no sigue una fecha límite remota
+error-viewer-read-only = Esta ventana muestra la cuenta regresiva de otro equipo y no puede cambiarla
+error-kiosk-locked = Hourglass está en modo presentación; introduce el código para hacer cambios
+error-timer-not-recurring = Este temporizador no se repite
+error-clipboard-empty = El portapapeles no contiene texto
+error-no-date-in-clipboard = No se encontró ninguna fecha en el texto copiado
+error-unreadable-file = No se pudo leer el archivo: { $reason }
+error-newer-file-version = Este archivo se creó con una versión más reciente de Hourglass
+error-no-events-in-file = El archivo no contiene ningún evento
+error-invalid-date = Formato de { $field } no válido: { $reason }
+error-end-before-start = La fecha de fin debe ser posterior a la fecha de inicio
+error-no-reminder-days = Selecciona al menos un día de recordatorio, o desactiva las notificaciones
+error-unsupported-locale = Idioma no admitido '{ $locale }': usa uno de { $available }
+error-announcement-interval = El intervalo de anuncios debe estar entre 1 y { $max } minutos
+error-idle-threshold = El umbral de inactividad debe estar entre 1 y { $max } minutos
+error-keep-awake-minutes = La ventana para mantener activo el equipo debe estar entre 1 y { $max } minutos
+error-keep-display-on-minutes = La ventana para mantener la pantalla encendida debe estar entre 1 y { $max } minutos
+error-break-minutes = La duración del descanso debe estar entre 1 y { $max } minutos
+error-ntp-server = Indica un servidor NTP con el que comprobar el reloj
+error-clock-drift-threshold = El umbral de desfase del reloj debe ser de al menos 1 segundo
+error-empty-blocklist-entry = Las entradas de la lista de bloqueo no pueden estar vacías
+error-invalid-blocked-site = { $site } no es una dirección de sitio web
+error-local-api-port = El puerto de la API local debe ser { $min } o superior
+error-invalid-bind-address = { $address } no es una dirección IP
+error-invalid-cors-origin = { $origin } no es un origen como https://example.com
+error-api-client-name-required = Los clientes de la API necesitan un nombre
+error-invalid-setting = { $field }: { $message } (por ejemplo { $example })
+error-unreadable-settings = El archivo de configuración no es JSON válido: { $reason }
+cli-no-countdown = No hay ninguna cuenta regresiva. Abre Hourglass con la API local activada o añade un temporizador.
+field-start-date = fecha de inicio
+field-end-date = fecha de fin
+field-reminder-date = fecha de recordatorio
+field-milestone-date = Fecha del hito
@@ -0,0 +1,242 @@
+## Notifications
+
+reminder-title = Lembrete do Hourglass
+reminder-no-dates = ⏳ O tempo continua passando... Defina suas datas para ver o tempo restante!
+reminder-expired = ⏰ Acabou o tempo! Sua ampulheta ficou sem areia.
+reminder-final-hour = 🔥 Última hora! { $minutes ->
+        [one] Resta
+       *[other] Restam
+    } apenas { $duration } — faça valer a pena.
+reminder-final-day = ⚡ Reta final: { $duration } restantes. Foque no que mais importa.
+reminder-casual = Faltam { $duration }
+milestone-due = Marco vencendo: { $name }
+milestone-reminder = { $name } vence em { $duration }
+checkin-behind-pace = { $done }% feito com { $elapsed }% do tempo já gasto. Você está atrás do ritmo.
+projection-late = Neste ritmo você vai terminar com { $duration } de atraso
+projection-on-time = Neste ritmo você vai terminar a tempo
+goal-met = Meta cumprida: { $done } de { $target } minutos hoje
+goal-missed = Meta não cumprida: { $done } de { $target } minutos hoje. Ela recomeça agora.
+remote-deadline-moved = O prazo mudou de { $from } para { $to }
+reminder-remaining = ⏳ Tempo restante: { $duration }
+reminder-ends = Termina em { $date }.
+pace-used = Você já usou { $percent }% do tempo. { $outlook }
+pace-almost-done = Quase toda a areia já caiu.
+pace-wrap-up = A maior parte da areia já caiu — hora de finalizar as coisas.
+pace-halfway = Você já passou da metade.
+pace-room-to-plan = Boa parte da areia já caiu, mas ainda há espaço para planejar.
+pace-plenty-left = Ainda há bastante areia, mas ela continua caindo.
+catch-up-title = Enquanto você estava fora
+catch-up-line = { $title }: restam { $duration }
+catch-up-line-expired = { $title }: acabou o tempo
+catch-up-downtime-title = O que aconteceu enquanto o Hourglass estava fora
+catch-up-gap = O Hourglass ficou fechado ou em repouso por { $duration }.
+catch-up-line-milestone = { $title }: { $milestone } alcançado
+streak-milestone-days = { $count } dias seguidos! Continue assim.
+streak-milestone-weeks = { $count } semanas seguidas! Continue assim.
+primary-countdown-label = Contagem
+test-notification-title = Notificação de teste
+test-notification-body = Esta é uma notificação de teste do Hourglass!
+share-sentence = { $duration } até { $title } — com Hourglass
+share-sentence-untitled = Faltam { $duration } — com Hourglass
+share-sentence-ended = { $title } chegou! — com Hourglass
+share-sentence-ended-untitled = Acabou o tempo — com Hourglass
+
+## Accessibility
+
+announcement = Restam { $duration }, { $percent }% do tempo usado. Termina em { $date }.
+announcement-without-progress = Restam { $duration }. Termina em { $date }.
+announcement-expired = A contagem terminou.
+announcement-no-dates = Nenhuma contagem definida.
+
+## Power
+
+keep-awake-reason = Uma contagem está prestes a terminar
+keep-display-on-reason = A contagem está na tela e prestes a terminar
+
+## Expiry actions
+
+action-lock-screen = bloquear a tela
+action-suspend = entrar em suspensão
+action-shut-down = desligar
+action-warning = Acabou o tempo. O computador vai { $action } em { $seconds } segundos — abra o Hourglass para cancelar.
+
+## Focus
+
+break-window-title = Hora de uma pausa
+alert-window-title = Lembrete do Hourglass
+blocked-app-warning = { $app } está na sua lista de bloqueio. Feche-o para manter o foco.
+blocked-app-closed = { $app } foi fechado porque está na sua lista de bloqueio.
+
+## Dates and durations
+
+duration-years = { $count ->
+        [one] 1 ano
+       *[other] { $count } anos
+    }
+duration-months = { $count ->
+        [one] 1 mês
+       *[other] { $count } meses
+    }
+duration-days = { $count ->
+        [one] 1 dia
+       *[other] { $count } dias
+    }
+duration-hours = { $count ->
+        [one] 1 hora
+       *[other] { $count } horas
+    }
+duration-minutes = { $count ->
+        [one] 1 minuto
+       *[other] { $count } minutos
+    }
+casual-under-minute = menos de um minuto
+casual-few-minutes = poucos minutos
+casual-minutes = cerca de { $count } minutos
+casual-hours = { $count ->
+        [one] cerca de uma hora
+       *[other] cerca de { $count } horas
+    }
+casual-about-day = cerca de um dia
+casual-over-day = pouco mais de um dia
+casual-days = cerca de { $count } dias
+casual-weeks = { $count ->
+        [one] cerca de uma semana
+       *[other] cerca de { $count } semanas
+    }
+casual-months = { $count ->
+        [one] cerca de um mês
+       *[other] cerca de { $count } meses
+    }
+casual-years = { $count ->
+        [one] cerca de um ano
+       *[other] cerca de { $count } anos
+    }
+date-day-first = { $weekday }, { $day } de { $month }
+date-day-first-with-year = { $weekday }, { $day } de { $month } de { $year }
+date-month-first = { $weekday }, { $month } { $day }
+date-month-first-with-year = { $weekday }, { $month } { $day } de { $year }
+date-time = { $date }, { $time }
+iso-week = S{ $week }
+zone-time-other-day = { $time } ({ $sign }{ $count ->
+        [one] 1 dia
+       *[other] { $count } dias
+    })
+time-am = { $hour }:{ $minute } AM
+time-pm = { $hour }:{ $minute } PM
+month-1 = jan
+month-2 = fev
+month-3 = mar
+month-4 = abr
+month-5 = mai
+month-6 = jun
+month-7 = jul
+month-8 = ago
+month-9 = set
+month-10 = out
+month-11 = nov
+month-12 = dez
+weekday-1 = seg
+weekday-2 = ter
+weekday-3 = qua
+weekday-4 = qui
+weekday-5 = sex
+weekday-6 = sáb
+weekday-7 = dom
+
+## Tray
+
+tray-title = Hourglass
+tray-tooltip = Hourglass - Controle de tempo
+tray-tooltip-ends = Hourglass - termina em { $date }
+tray-timer-from-clipboard = Novo timer da área de transferência
+tray-quit = Sair
+clock-drift-title = Seu relógio está errado
+clock-drift-body = O relógio do sistema está { $seconds } segundos fora da hora correta, então as contagens regressivas e os lembretes também estão. Verifique as configurações de data e hora.
+
+## Timers
+
+previous-countdown-label = Contagem anterior
+preset-end-of-month = Fim do mês
+preset-end-of-quarter = Fim do trimestre
+preset-end-of-year = Fim do ano
+preset-end-of-fiscal-quarter = Fim do trimestre fiscal
+preset-end-of-fiscal-year = Fim do ano fiscal
+timeline-start = Início
+fiscal-year-label = AF{ $year }
+
+## Errors
+
+error-timer-not-found = Timer { $id } não encontrado
+error-invalid-color = Cor inválida "{ $color }": era esperada uma cor hexadecimal como #f59e0b
+error-invalid-icon = Ícone inválido "{ $icon }": era esperado um único emoji ou símbolo
+error-timer-already-trashed = O timer { $id } já está na lixeira
+error-timer-not-active = Só timers ativos podem ser arquivados (timer { $id })
+error-timer-not-archived = O timer { $id } não está arquivado
+error-timer-not-trashed = O timer { $id } não está na lixeira
+error-empty-tag = A tag não pode ficar vazia
+error-tag-no-reminder-days = Escolha pelo menos um dia de lembrete ou desative as notificações desta tag
+error-note-too-long = A nota é longa demais (máximo de { $max } caracteres)
+error-negative-expiring-within = "Vence em" não pode ser um número negativo de dias
+error-reminder-in-past = O lembrete precisa estar no futuro
+error-reminder-exists = Já existe um lembrete nesse horário
+error-reminder-not-found = Não existe lembrete nesse horário
+error-relative-reminder-not-positive = Lembretes relativos precisam ser um número positivo de segundos antes da data de término
+error-schedule-interval-too-short = Os intervalos de lembrete precisam ter pelo menos { $min } segundos
+error-timer-dates-not-set = As datas do timer não foram definidas
+error-display-not-found = Não existe a tela número { $index }
+error-window-opacity = A opacidade deve estar entre { $min }% e 100%
+error-passcode-too-short = O código precisa de pelo menos { $min } caracteres
+error-wrong-passcode = Esse código não está certo
+error-passcode-required = Isso precisa do código
+error-invalid-viewer-source = { $source } não é um endereço http:// ou https://
+error-invalid-webhook-url = { $url } não é um endereço http:// ou https://
+error-webhook-route-without-url = uma rota envia para o canal webhook, mas webhook_url não está definido
+error-invalid-smtp-host = { $host } não é um nome de servidor SMTP como smtp.example.com
+error-smtp-port = A porta SMTP não pode ser 0
+error-invalid-email-address = { $address } não é um endereço de e-mail
+error-email-route-without-smtp = uma rota envia para o canal de e-mail, mas smtp_host, email_from e email_to não estão todos definidos
+error-period-dates = Não foi possível calcular as datas desse período no fuso horário local
+error-fiscal-year-start = Escolha o mês em que o ano fiscal começa, de 1 (janeiro) a 12 (dezembro)
+error-milestone-name = Dê um nome ao marco
+error-milestone-after-end = Um marco não pode vencer depois do fim do timer
+error-milestone-not-found = Marco { $id } não encontrado
+error-checkin-percent = O progresso precisa estar entre 0 e 100 por cento
+error-goal-target = Uma meta diária precisa ter entre 1 minuto e 24 horas
+error-goal-reset-time = Informe o horário de reinício como HH:MM, ex. 18:00
+error-timer-not-goal = O timer não é uma meta diária
+error-unknown-timezone = { $zone } não é um fuso horário conhecido, ex. America/Sao_Paulo
+error-invalid-remote-url = { $url } não é um endereço http:// ou https://
+error-remote-no-end-date = O prazo remoto não tem data de término
+error-timer-not-subscribed = O timer não segue um prazo remoto
+error-viewer-read-only = Esta janela mostra a contagem regressiva de outro computador e não pode alterá-la
+error-kiosk-locked = O Hourglass está no modo de apresentação; digite o código para fazer alterações
+error-timer-not-recurring = Este timer não se repete
+error-clipboard-empty = A área de transferência não contém texto
+error-no-date-in-clipboard = Não foi possível encontrar uma data no texto copiado
+error-unreadable-file = Não foi possível ler o arquivo: { $reason }
+error-newer-file-version = Este arquivo foi criado por uma versão mais recente do Hourglass
+error-no-events-in-file = O arquivo não contém nenhum evento
+error-invalid-date = Formato de { $field } inválido: { $reason }
+error-end-before-start = A data de término deve ser posterior à data de início
+error-no-reminder-days = Selecione pelo menos um dia de lembrete, ou desative as notificações
+error-unsupported-locale = Idioma não suportado '{ $locale }': use um de { $available }
+error-announcement-interval = O intervalo de anúncios deve estar entre 1 e { $max } minutos
+error-idle-threshold = O limite de inatividade deve estar entre 1 e { $max } minutos
+error-keep-awake-minutes = A janela para manter o computador ativo deve estar entre 1 e { $max } minutos
+error-keep-display-on-minutes = A janela para manter a tela ligada deve estar entre 1 e { $max } minutos
+error-break-minutes = A duração da pausa deve estar entre 1 e { $max } minutos
+error-ntp-server = Informe um servidor NTP para comparar o relógio
+error-clock-drift-threshold = O limite de desvio do relógio deve ser de pelo menos 1 segundo
+error-empty-blocklist-entry = As entradas da lista de bloqueio não podem estar vazias
+error-invalid-blocked-site = { $site } não é um endereço de site
+error-local-api-port = A porta da API local deve ser { $min } ou maior
+error-invalid-bind-address = { $address } não é um endereço IP
+error-invalid-cors-origin = { $origin } não é uma origem como https://example.com
+error-api-client-name-required = Clientes da API precisam de um nome
+error-invalid-setting = { $field }: { $message } (por exemplo { $example })
+error-unreadable-settings = O arquivo de configurações não é um JSON válido: { $reason }
+cli-no-countdown = Nenhuma contagem regressiva para mostrar. Abra o Hourglass com a API local ativada ou adicione um temporizador.
+field-start-date = data de início
+field-end-date = data de término
+field-reminder-date = data do lembrete
+field-milestone-date = Data do marco
@@ -0,0 +1,125 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::time::{interval, Duration};
+
+use crate::clock;
+use crate::i18n::{t, t_args};
+use crate::settings::SettingsState;
+use crate::{format, message, primary_end_date, primary_start_date, NotificationState};
+
+#[derive(Serialize, Clone)]
+pub struct Announcement {
+    pub text: String,
+    pub at: DateTime<Utc>,
+}
+
+// Short, emoji-free and in a fixed order so it reads well through a screen reader
+pub fn compose_announcement(start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>, now: DateTime<Utc>) -> String {
+    let Some(end) = end else {
+        return t("announcement-no-dates");
+    };
+    let remaining = (end - now).num_milliseconds();
+    if remaining <= 0 {
+        return t("announcement-expired");
+    }
+    let duration = format::format_duration(remaining);
+    let date = format::format_date_time(end);
+    match start.and_then(|start| message::percent_elapsed(start, end, now)) {
+        Some(percent) => t_args("announcement", &[
+            ("duration", duration.into()),
+            ("percent", percent.into()),
+            ("date", date.into()),
+        ]),
+        None => t_args("announcement-without-progress", &[("duration", duration.into()), ("date", date.into())]),
+    }
+}
+
+fn primary_announcement(app: &AppHandle) -> String {
+    let state = app.state::<NotificationState>();
+    compose_announcement(primary_start_date(&state), primary_end_date(&state), clock::now())
+}
+
+// Hands the text to whatever is voicing the desktop: VoiceOver on macOS, speech-dispatcher
+// (which Orca also speaks through) on Linux. Windows has no command-line route to Narrator,
+// so there the frontend's live region is the only channel.
+#[cfg(target_os = "macos")]
+fn speak(text: &str) -> Result<(), String> {
+    let script = format!(
+        "if application \"VoiceOver\" is running then tell application \"VoiceOver\" to output \"{}\"",
+        text.replace('\\', "\\\\").replace('"', "\\\"")
+    );
+    std::process::Command::new("osascript")
+        .args(["-e", &script])
+        .status()
+        .map_err(|e| format!("Failed to run osascript: {}", e))?;
+    Ok(())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn speak(text: &str) -> Result<(), String> {
+    std::process::Command::new("spd-say")
+        .args(["--", text])
+        .status()
+        .map_err(|e| format!("Failed to run spd-say: {}", e))?;
+    Ok(())
+}
+
+#[cfg(windows)]
+fn speak(_text: &str) -> Result<(), String> {
+    Ok(())
+}
+
+pub fn announce(app: &AppHandle, speak_aloud: bool) {
+    let announcement = Announcement { text: primary_announcement(app), at: clock::now() };
+    if speak_aloud {
+        let text = announcement.text.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(e) = speak(&text) {
+                eprintln!("Failed to send accessibility announcement: {}", e);
+            }
+        });
+    }
+    if let Err(e) = app.emit("countdown-announcement", announcement) {
+        eprintln!("Failed to emit countdown announcement: {}", e);
+    }
+}
+
+// Runs independently of notifications so screen-reader users keep their summaries even with
+// reminders turned off; the interval is re-read every pass so settings apply right away
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut tick = interval(Duration::from_secs(30));
+        let mut last_announcement: Option<DateTime<Utc>> = None;
+        loop {
+            tick.tick().await;
+            let (enabled, interval_minutes, speak_aloud) = match app.state::<SettingsState>().lock() {
+                Ok(settings) => (
+                    settings.announcements_enabled,
+                    settings.announcement_interval_minutes,
+                    settings.speak_announcements,
+                ),
+                Err(e) => {
+                    eprintln!("Failed to lock settings in announcement task: {}", e);
+                    continue;
+                }
+            };
+            if !enabled {
+                last_announcement = None;
+                continue;
+            }
+            let now = clock::now();
+            let due = last_announcement
+                .is_none_or(|last| now - last >= chrono::Duration::minutes(interval_minutes.into()));
+            if due {
+                last_announcement = Some(now);
+                announce(&app, speak_aloud);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_countdown_announcement(app: AppHandle) -> Result<String, String> {
+    Ok(primary_announcement(&app))
+}
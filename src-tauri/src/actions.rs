@@ -0,0 +1,176 @@
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::i18n::{t, t_args};
+use crate::notifier::EventKind;
+use crate::send_notification;
+use crate::timers::{Timer, TimerState};
+
+// How long the user gets to call it off after the warning
+const WARNING_SECONDS: i64 = 60;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SystemAction {
+    LockScreen,
+    Suspend,
+    ShutDown,
+}
+
+impl SystemAction {
+    fn label(self) -> String {
+        t(match self {
+            SystemAction::LockScreen => "action-lock-screen",
+            SystemAction::Suspend => "action-suspend",
+            SystemAction::ShutDown => "action-shut-down",
+        })
+    }
+
+    fn command(self) -> (&'static str, &'static [&'static str]) {
+        #[cfg(windows)]
+        let command: (&str, &[&str]) = match self {
+            SystemAction::LockScreen => ("rundll32.exe", &["user32.dll,LockWorkStation"]),
+            SystemAction::Suspend => ("rundll32.exe", &["powrprof.dll,SetSuspendState", "0,1,0"]),
+            SystemAction::ShutDown => ("shutdown", &["/s", "/t", "0"]),
+        };
+        #[cfg(target_os = "macos")]
+        let command: (&str, &[&str]) = match self {
+            // Sleeping the display locks the session when a password is required after sleep
+            SystemAction::LockScreen => ("pmset", &["displaysleepnow"]),
+            SystemAction::Suspend => ("pmset", &["sleepnow"]),
+            SystemAction::ShutDown => ("osascript", &["-e", "tell application \"System Events\" to shut down"]),
+        };
+        #[cfg(all(unix, not(target_os = "macos")))]
+        let command: (&str, &[&str]) = match self {
+            SystemAction::LockScreen => ("loginctl", &["lock-session"]),
+            SystemAction::Suspend => ("systemctl", &["suspend"]),
+            SystemAction::ShutDown => ("systemctl", &["poweroff"]),
+        };
+        command
+    }
+
+    pub fn perform(self) -> Result<(), String> {
+        let (program, args) = self.command();
+        let status = Command::new(program)
+            .args(args)
+            .status()
+            .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+        if !status.success() {
+            return Err(format!("{} exited with {}", program, status));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct PendingAction {
+    pub action: SystemAction,
+    pub title: String,
+    pub due_at: DateTime<Utc>,
+    #[serde(skip)]
+    cancelled: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+pub struct ActionState {
+    pending: Arc<Mutex<Option<PendingAction>>>,
+}
+
+impl ActionState {
+    // Warns, waits out the grace period and then runs the action unless it was cancelled.
+    // Only one action can be pending; later expiries don't stack more on top.
+    pub fn schedule(&self, app: &AppHandle, action: SystemAction, title: &str) -> Result<(), String> {
+        let mut pending = self.pending.lock().map_err(|e| format!("Failed to lock pending action: {}", e))?;
+        if pending.is_some() {
+            return Ok(());
+        }
+        let entry = PendingAction {
+            action,
+            title: title.to_string(),
+            due_at: Utc::now() + Duration::seconds(WARNING_SECONDS),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        };
+        *pending = Some(entry.clone());
+        drop(pending);
+
+        send_notification(
+            app,
+            EventKind::System,
+            title,
+            &t_args("action-warning", &[("action", action.label().into()), ("seconds", WARNING_SECONDS.into())]),
+        );
+        if let Err(e) = app.emit("expiry-action-pending", &entry) {
+            eprintln!("Failed to emit pending expiry action: {}", e);
+        }
+
+        let app = app.clone();
+        let slot = self.pending.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_secs(WARNING_SECONDS as u64)).await;
+            // A cancelled entry may already have been replaced by a newer one; leave that alone
+            if let Ok(mut pending) = slot.lock() {
+                if pending.as_ref().is_some_and(|p| Arc::ptr_eq(&p.cancelled, &entry.cancelled)) {
+                    *pending = None;
+                }
+            }
+            if entry.cancelled.load(Ordering::SeqCst) {
+                return;
+            }
+            let result = tauri::async_runtime::spawn_blocking(move || action.perform())
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|result| result);
+            if let Err(e) = result {
+                eprintln!("Failed to run expiry action: {}", e);
+            }
+            if let Err(e) = app.emit("expiry-action-finished", action) {
+                eprintln!("Failed to emit expiry action result: {}", e);
+            }
+        });
+        Ok(())
+    }
+
+    pub fn cancel(&self) -> Result<bool, String> {
+        let mut pending = self.pending.lock().map_err(|e| format!("Failed to lock pending action: {}", e))?;
+        Ok(match pending.take() {
+            Some(entry) => {
+                entry.cancelled.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false,
+        })
+    }
+}
+
+#[tauri::command]
+pub async fn get_pending_expiry_action(state: State<'_, ActionState>) -> Result<Option<PendingAction>, String> {
+    Ok(state.pending.lock().map_err(|e| format!("Failed to lock pending action: {}", e))?.clone())
+}
+
+#[tauri::command]
+pub async fn cancel_expiry_action(app: AppHandle, state: State<'_, ActionState>) -> Result<bool, String> {
+    let cancelled = state.cancel()?;
+    if cancelled {
+        if let Err(e) = app.emit("expiry-action-cancelled", ()) {
+            eprintln!("Failed to emit expiry action cancellation: {}", e);
+        }
+    }
+    Ok(cancelled)
+}
+
+#[tauri::command]
+pub async fn set_timer_expiry_action(
+    state: State<'_, TimerState>,
+    id: u64,
+    action: Option<SystemAction>,
+) -> Result<Timer, String> {
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        timer.expiry_action = action;
+        Ok(())
+    })
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::settings::SettingsState;
+use crate::timers::TimerState;
+use crate::{idle, persistence, primary_end_date, NotificationState};
+
+const SAMPLE_SECONDS: u64 = 10;
+// Samples are kept in memory and written out every so often rather than on every tick
+const SAVE_EVERY_SAMPLES: u32 = 6;
+
+// Seconds spent in each foreground application, keyed by the app's name
+type AppSeconds = HashMap<String, u64>;
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ActivityLog {
+    pub primary: AppSeconds,
+    pub timers: HashMap<u64, AppSeconds>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct AppUsage {
+    pub app: String,
+    pub seconds: u64,
+}
+
+#[derive(Default)]
+pub struct ActivityState {
+    pub log: Arc<Mutex<ActivityLog>>,
+    pub data_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl ActivityState {
+    pub fn lock(&self) -> Result<MutexGuard<'_, ActivityLog>, String> {
+        self.log.lock().map_err(|e| format!("Failed to lock activity log: {}", e))
+    }
+
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let loaded: ActivityLog = persistence::load_json(&path);
+        *self.lock()? = loaded;
+        *self.data_path.lock().map_err(|e| format!("Failed to lock activity path: {}", e))? = Some(path);
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = self.data_path.lock().map_err(|e| format!("Failed to lock activity path: {}", e))?.clone();
+        match path {
+            Some(path) => persistence::save_json(&path, &*self.lock()?),
+            None => Ok(()),
+        }
+    }
+}
+
+fn foreground_app() -> Option<String> {
+    let window = active_win_pos_rs::get_active_window().ok()?;
+    let name = window.app_name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+// Samples the foreground application while any countdown runs and credits the interval to
+// each running one. Time the user spends away from the keyboard isn't counted.
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut tick = tokio::time::interval(std::time::Duration::from_secs(SAMPLE_SECONDS));
+        let mut unsaved = 0;
+        loop {
+            tick.tick().await;
+            let (enabled, idle_threshold) = match app.state::<SettingsState>().lock() {
+                Ok(settings) => (settings.track_active_app, settings.idle_threshold_minutes),
+                Err(e) => {
+                    eprintln!("Failed to lock settings in activity tracker: {}", e);
+                    continue;
+                }
+            };
+            if !enabled || idle::is_away(std::time::Duration::from_secs(u64::from(idle_threshold) * 60)) {
+                continue;
+            }
+
+            let now = Utc::now();
+            let primary_running = primary_end_date(&app.state::<NotificationState>()).is_some_and(|end| end > now);
+            let running_timers: Vec<u64> = match app.state::<TimerState>().lock() {
+                Ok(store) => store
+                    .timers
+                    .iter()
+                    .filter(|timer| timer.is_active() && timer.start_date <= now && now < timer.end_date)
+                    .map(|timer| timer.id)
+                    .collect(),
+                Err(e) => {
+                    eprintln!("Failed to lock timers in activity tracker: {}", e);
+                    Vec::new()
+                }
+            };
+            if !primary_running && running_timers.is_empty() {
+                continue;
+            }
+            let Ok(Some(app_name)) = tauri::async_runtime::spawn_blocking(foreground_app).await else {
+                continue;
+            };
+
+            let state = app.state::<ActivityState>();
+            match state.lock() {
+                Ok(mut log) => {
+                    if primary_running {
+                        *log.primary.entry(app_name.clone()).or_default() += SAMPLE_SECONDS;
+                    }
+                    for id in running_timers {
+                        *log.timers.entry(id).or_default().entry(app_name.clone()).or_default() += SAMPLE_SECONDS;
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    continue;
+                }
+            }
+            unsaved += 1;
+            if unsaved >= SAVE_EVERY_SAMPLES {
+                unsaved = 0;
+                if let Err(e) = state.save() {
+                    eprintln!("Failed to save activity log: {}", e);
+                }
+            }
+        }
+    });
+}
+
+// Most-used first. With no id, reports the main countdown.
+#[tauri::command]
+pub async fn get_app_usage(state: State<'_, ActivityState>, timer_id: Option<u64>) -> Result<Vec<AppUsage>, String> {
+    let log = state.lock()?;
+    let seconds = match timer_id {
+        Some(id) => log.timers.get(&id),
+        None => Some(&log.primary),
+    };
+    let mut usage: Vec<AppUsage> = seconds
+        .into_iter()
+        .flatten()
+        .map(|(app, seconds)| AppUsage { app: app.clone(), seconds: *seconds })
+        .collect();
+    usage.sort_by(|a, b| b.seconds.cmp(&a.seconds).then_with(|| a.app.cmp(&b.app)));
+    Ok(usage)
+}
+
+#[tauri::command]
+pub async fn clear_app_usage(state: State<'_, ActivityState>, timer_id: Option<u64>) -> Result<(), String> {
+    {
+        let mut log = state.lock()?;
+        match timer_id {
+            Some(id) => {
+                log.timers.remove(&id);
+            }
+            None => log.primary.clear(),
+        }
+    }
+    state.save()
+}
@@ -0,0 +1,103 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::i18n::t;
+use crate::passcode;
+use crate::persistence;
+
+// A program or person allowed to use the local API. Only a hash of the token is kept, so the
+// token itself is shown once, when it's created.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ApiClient {
+    pub name: String,
+    token_hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+pub struct ApiClientInfo {
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+pub struct NewApiToken {
+    pub name: String,
+    pub token: String,
+}
+
+#[derive(Default)]
+pub struct ApiTokenState {
+    pub clients: Arc<Mutex<Vec<ApiClient>>>,
+    pub data_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl ApiTokenState {
+    pub fn lock(&self) -> Result<MutexGuard<'_, Vec<ApiClient>>, String> {
+        self.clients.lock().map_err(|e| format!("Failed to lock API tokens: {}", e))
+    }
+
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let loaded: Vec<ApiClient> = persistence::load_json(&path);
+        *self.lock()? = loaded;
+        *self.data_path.lock().map_err(|e| format!("Failed to lock API tokens path: {}", e))? = Some(path);
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = self.data_path.lock().map_err(|e| format!("Failed to lock API tokens path: {}", e))?.clone();
+        match path {
+            Some(path) => persistence::save_json(&path, &*self.lock()?),
+            None => Ok(()),
+        }
+    }
+
+    // The name of the client a bearer token belongs to
+    pub fn authenticate(&self, token: &str) -> Option<String> {
+        let hash = passcode::token_hash(token);
+        let clients = self.lock().ok()?;
+        clients.iter().find(|client| passcode::secrets_match(&client.token_hash, &hash)).map(|client| client.name.clone())
+    }
+}
+
+#[tauri::command]
+pub async fn list_api_tokens(state: State<'_, ApiTokenState>) -> Result<Vec<ApiClientInfo>, String> {
+    Ok(state
+        .lock()?
+        .iter()
+        .map(|client| ApiClientInfo {
+            name: client.name.clone(),
+            created_at: client.created_at,
+        })
+        .collect())
+}
+
+// Issues a token for a named client, replacing any it had before
+#[tauri::command]
+pub async fn create_api_token(state: State<'_, ApiTokenState>, name: String) -> Result<NewApiToken, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(t("error-api-client-name-required"));
+    }
+    let token = passcode::random_token()?;
+    {
+        let mut clients = state.lock()?;
+        clients.retain(|client| client.name != name);
+        clients.push(ApiClient {
+            name: name.clone(),
+            token_hash: passcode::token_hash(&token),
+            created_at: Utc::now(),
+        });
+    }
+    state.save()?;
+    Ok(NewApiToken { name, token })
+}
+
+#[tauri::command]
+pub async fn revoke_api_token(state: State<'_, ApiTokenState>, name: String) -> Result<(), String> {
+    state.lock()?.retain(|client| client.name != name);
+    state.save()
+}
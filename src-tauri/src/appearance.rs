@@ -0,0 +1,119 @@
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State, Theme};
+
+use crate::main_window;
+
+#[derive(Serialize, Clone, PartialEq)]
+pub struct SystemAppearance {
+    pub theme: String,
+    pub accent_color: Option<String>,
+}
+
+#[derive(Default)]
+pub struct AppearanceState {
+    last: Arc<Mutex<Option<SystemAppearance>>>,
+}
+
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let mut command = Command::new(program);
+    command.args(args);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+    let output = command.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// DWM stores the accent as 0xAABBGGRR
+#[cfg(target_os = "windows")]
+fn accent_color() -> Option<String> {
+    let output = run("reg", &["query", r"HKCU\Software\Microsoft\Windows\DWM", "/v", "AccentColor"])?;
+    let value = output.split_whitespace().last()?.trim_start_matches("0x");
+    let abgr = u32::from_str_radix(value, 16).ok()?;
+    Some(format!("#{:02x}{:02x}{:02x}", abgr & 0xff, (abgr >> 8) & 0xff, (abgr >> 16) & 0xff))
+}
+
+// macOS only stores an index into its fixed palette; no value means the default blue
+#[cfg(target_os = "macos")]
+fn accent_color() -> Option<String> {
+    let color = match run("defaults", &["read", "-g", "AppleAccentColor"]).as_deref() {
+        Some("-1") => "#8c8c8c",
+        Some("0") => "#ff5257",
+        Some("1") => "#f7821b",
+        Some("2") => "#ffc600",
+        Some("3") => "#62ba46",
+        Some("5") => "#a550a7",
+        Some("6") => "#f74f9e",
+        _ => "#007aff",
+    };
+    Some(color.to_string())
+}
+
+// GNOME 47+ exposes a named accent; other desktops don't have a portable equivalent
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn accent_color() -> Option<String> {
+    let output = run("gsettings", &["get", "org.gnome.desktop.interface", "accent-color"])?;
+    let color = match output.trim_matches('\'') {
+        "blue" => "#3584e4",
+        "teal" => "#2190a4",
+        "green" => "#3a944a",
+        "yellow" => "#c88800",
+        "orange" => "#ed5b00",
+        "red" => "#e62d42",
+        "pink" => "#d56199",
+        "purple" => "#9141ac",
+        "slate" => "#6f8396",
+        _ => return None,
+    };
+    Some(color.to_string())
+}
+
+pub fn current_appearance(app: &AppHandle) -> SystemAppearance {
+    let theme = main_window(app)
+        .and_then(|window| window.theme().ok())
+        .unwrap_or(Theme::Light);
+    SystemAppearance {
+        theme: if theme == Theme::Dark { "dark" } else { "light" }.to_string(),
+        accent_color: accent_color(),
+    }
+}
+
+// Called on theme changes and when the window regains focus (the accent color has no
+// change notification of its own); only emits when something actually changed
+pub fn refresh(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let appearance = current_appearance(&app);
+        let state = app.state::<AppearanceState>();
+        let mut last = match state.last.lock() {
+            Ok(last) => last,
+            Err(e) => {
+                eprintln!("Failed to lock appearance state: {}", e);
+                return;
+            }
+        };
+        if last.as_ref() != Some(&appearance) {
+            *last = Some(appearance.clone());
+            if let Err(e) = app.emit("system-appearance-changed", appearance) {
+                eprintln!("Failed to emit appearance change: {}", e);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_system_appearance(
+    app: AppHandle,
+    state: State<'_, AppearanceState>,
+) -> Result<SystemAppearance, String> {
+    let appearance = current_appearance(&app);
+    *state.last.lock().map_err(|e| format!("Failed to lock appearance state: {}", e))? = Some(appearance.clone());
+    Ok(appearance)
+}
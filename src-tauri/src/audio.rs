@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use rodio::source::{SineWave, Source, Zero};
+use rodio::{Decoder, OutputStreamBuilder, Sink};
+use tauri::State;
+
+use crate::timers::{Timer, TimerState};
+
+// The alarm starts barely audible and reaches full volume after RAMP_DURATION
+const START_VOLUME: f32 = 0.05;
+const RAMP_DURATION: Duration = Duration::from_secs(30);
+// Stop eventually if nobody is around to dismiss it
+const MAX_ALARM_DURATION: Duration = Duration::from_secs(10 * 60);
+
+const SUPPORTED_SOUND_EXTENSIONS: [&str; 4] = ["wav", "mp3", "ogg", "flac"];
+const MAX_SOUND_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+type SoundData = Arc<[u8]>;
+
+#[derive(Default)]
+pub struct AudioState {
+    alarm_stop: Arc<Mutex<Option<Arc<AtomicBool>>>>,
+    sound_cache: Arc<Mutex<HashMap<PathBuf, SoundData>>>,
+}
+
+impl AudioState {
+    // Reads and decodes a sound file once, then serves it from memory
+    pub fn load_sound(&self, path: &Path) -> Result<SoundData, String> {
+        let mut cache = self.sound_cache.lock().map_err(|e| format!("Failed to lock sound cache: {}", e))?;
+        if let Some(data) = cache.get(path) {
+            return Ok(data.clone());
+        }
+
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .unwrap_or_default();
+        if !SUPPORTED_SOUND_EXTENSIONS.contains(&extension.as_str()) {
+            return Err(format!(
+                "Unsupported sound format '{}': use one of {}",
+                extension,
+                SUPPORTED_SOUND_EXTENSIONS.join(", ")
+            ));
+        }
+        let metadata = fs::metadata(path).map_err(|e| format!("Failed to read sound file {}: {}", path.display(), e))?;
+        if !metadata.is_file() {
+            return Err(format!("{} is not a file", path.display()));
+        }
+        if metadata.len() > MAX_SOUND_FILE_BYTES {
+            return Err(format!("Sound file is too large (maximum {} MB)", MAX_SOUND_FILE_BYTES / 1024 / 1024));
+        }
+
+        let data: SoundData = fs::read(path)
+            .map_err(|e| format!("Failed to read sound file {}: {}", path.display(), e))?
+            .into();
+        Decoder::new(Cursor::new(data.clone()))
+            .map_err(|e| format!("Failed to decode sound file {}: {}", path.display(), e))?;
+        cache.insert(path.to_path_buf(), data.clone());
+        Ok(data)
+    }
+
+    // Plays a timer's own sound once alongside its reminder; failures only cost the sound
+    pub fn play_sound(&self, path: &str) {
+        let data = match self.load_sound(Path::new(path)) {
+            Ok(data) => data,
+            Err(e) => {
+                eprintln!("Failed to play reminder sound: {}", e);
+                return;
+            }
+        };
+        thread::spawn(move || {
+            if let Err(e) = play_once(data) {
+                eprintln!("Failed to play reminder sound: {}", e);
+            }
+        });
+    }
+
+    pub fn start_alarm(&self, sound: Option<&str>) -> Result<(), String> {
+        let sound = match sound {
+            Some(path) => Some(self.load_sound(Path::new(path))?),
+            None => None,
+        };
+
+        let mut current = self.alarm_stop.lock().map_err(|e| format!("Failed to lock alarm state: {}", e))?;
+        if current.as_ref().is_some_and(|stop| !stop.load(Ordering::SeqCst)) {
+            return Ok(()); // Already ringing
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        *current = Some(stop.clone());
+        thread::spawn(move || {
+            if let Err(e) = play_alarm(&stop, sound) {
+                eprintln!("Failed to play expiry alarm: {}", e);
+            }
+            stop.store(true, Ordering::SeqCst);
+        });
+        Ok(())
+    }
+
+    pub fn is_ringing(&self) -> bool {
+        self.alarm_stop
+            .lock()
+            .map(|current| current.as_ref().is_some_and(|stop| !stop.load(Ordering::SeqCst)))
+            .unwrap_or(false)
+    }
+
+    pub fn stop_alarm(&self) -> Result<bool, String> {
+        let mut current = self.alarm_stop.lock().map_err(|e| format!("Failed to lock alarm state: {}", e))?;
+        Ok(match current.take() {
+            Some(stop) => !stop.swap(true, Ordering::SeqCst),
+            None => false,
+        })
+    }
+}
+
+fn volume_at(elapsed: Duration) -> f32 {
+    let progress = (elapsed.as_secs_f32() / RAMP_DURATION.as_secs_f32()).min(1.0);
+    START_VOLUME + (1.0 - START_VOLUME) * progress
+}
+
+fn queue_beep(sink: &Sink) {
+    sink.append(SineWave::new(880.0).take_duration(Duration::from_millis(400)).amplify(0.6));
+    sink.append(Zero::new(1, 48_000).take_duration(Duration::from_millis(600)));
+}
+
+fn play_once(data: SoundData) -> Result<(), String> {
+    let mut stream = OutputStreamBuilder::open_default_stream()
+        .map_err(|e| format!("Failed to open audio output: {}", e))?;
+    stream.log_on_drop(false);
+    let sink = Sink::connect_new(stream.mixer());
+    let source = Decoder::new(Cursor::new(data)).map_err(|e| format!("Failed to decode sound: {}", e))?;
+    sink.append(source);
+    sink.sleep_until_end();
+    Ok(())
+}
+
+// Runs on its own thread because the output stream can't be moved between threads
+fn play_alarm(stop: &AtomicBool, sound: Option<SoundData>) -> Result<(), String> {
+    let mut stream = OutputStreamBuilder::open_default_stream()
+        .map_err(|e| format!("Failed to open audio output: {}", e))?;
+    stream.log_on_drop(false);
+    let sink = Sink::connect_new(stream.mixer());
+    sink.set_volume(START_VOLUME);
+
+    // A custom sound loops for as long as the alarm rings
+    if let Some(data) = sound {
+        let source = Decoder::new_looped(Cursor::new(data)).map_err(|e| format!("Failed to decode sound: {}", e))?;
+        sink.append(source);
+    }
+
+    let started = Instant::now();
+    while !stop.load(Ordering::SeqCst) && started.elapsed() < MAX_ALARM_DURATION {
+        // Keep the queue short so volume changes are heard right away
+        if sink.empty() {
+            queue_beep(&sink);
+        }
+        sink.set_volume(volume_at(started.elapsed()));
+        thread::sleep(Duration::from_millis(200));
+    }
+    sink.stop();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn dismiss_alarm(state: State<'_, AudioState>) -> Result<bool, String> {
+    state.stop_alarm()
+}
+
+#[tauri::command]
+pub async fn set_timer_sound(
+    state: State<'_, AudioState>,
+    timer_state: State<'_, TimerState>,
+    id: u64,
+    path: Option<String>,
+) -> Result<Timer, String> {
+    let path = path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    if let Some(path) = &path {
+        state.load_sound(Path::new(path))?;
+    }
+
+    let mut store = timer_state.lock()?;
+    store.update(id, |timer| {
+        timer.sound_path = path;
+        Ok(())
+    })
+}
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::persistence;
+
+// Oldest entries are dropped past this
+const MAX_ENTRIES: usize = 1000;
+
+// One change made through the local API
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AuditEntry {
+    pub at: DateTime<Utc>,
+    // The API token's name; None for requests without one
+    pub client: Option<String>,
+    pub address: String,
+    pub action: String,
+    // Why it didn't go through, if it didn't
+    pub error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct AuditState {
+    pub entries: Arc<Mutex<Vec<AuditEntry>>>,
+    pub data_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl AuditState {
+    pub fn lock(&self) -> Result<MutexGuard<'_, Vec<AuditEntry>>, String> {
+        self.entries.lock().map_err(|e| format!("Failed to lock audit log: {}", e))
+    }
+
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let loaded: Vec<AuditEntry> = persistence::load_json(&path);
+        *self.lock()? = loaded;
+        *self.data_path.lock().map_err(|e| format!("Failed to lock audit log path: {}", e))? = Some(path);
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = self.data_path.lock().map_err(|e| format!("Failed to lock audit log path: {}", e))?.clone();
+        match path {
+            Some(path) => persistence::save_json(&path, &*self.lock()?),
+            None => Ok(()),
+        }
+    }
+
+    pub fn record(&self, entry: AuditEntry) {
+        match self.lock() {
+            Ok(mut entries) => {
+                entries.push(entry);
+                let overflow = entries.len().saturating_sub(MAX_ENTRIES);
+                entries.drain(..overflow);
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                return;
+            }
+        }
+        if let Err(e) = self.save() {
+            eprintln!("Failed to save audit log: {}", e);
+        }
+    }
+}
+
+// Newest first, optionally only the latest `limit` entries
+#[tauri::command]
+pub async fn get_api_audit_log(state: State<'_, AuditState>, limit: Option<usize>) -> Result<Vec<AuditEntry>, String> {
+    let entries = state.lock()?;
+    Ok(entries.iter().rev().take(limit.unwrap_or(usize::MAX)).cloned().collect())
+}
+
+#[tauri::command]
+pub async fn clear_api_audit_log(state: State<'_, AuditState>) -> Result<(), String> {
+    state.lock()?.clear();
+    state.save()
+}
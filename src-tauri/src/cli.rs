@@ -0,0 +1,172 @@
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use chrono::Utc;
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use serde_json::json;
+
+use crate::i18n::{self, t, t_args};
+use crate::settings::Settings;
+use crate::share::{Countdown, CountdownSummary};
+use crate::timers::TimerStore;
+use crate::{format, persistence};
+
+// Matches "identifier" in tauri.conf.json, which names the app's data directory
+pub const APP_IDENTIFIER: &str = "com.hourglass.desktop";
+const INSTANCE_TIMEOUT: Duration = Duration::from_millis(500);
+
+#[derive(Parser)]
+#[command(name = "hourglass", version, about = "Countdowns that keep you aware of the time left")]
+struct Cli {
+    #[arg(long, global = true, help = "Print results and errors as JSON objects")]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(about = "Print the time left and exit, for launchers, status bars and shell prompts")]
+    Query {
+        #[arg(long, value_name = "ID", help = "Report this timer instead of the main countdown")]
+        timer: Option<u64>,
+    },
+    #[command(about = "Print a shell completion script")]
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+// Runs a subcommand when the first argument names one and returns the exit code. Anything else,
+// such as a file or link the OS opened us with, is left for the app.
+pub fn run() -> Option<i32> {
+    let first = std::env::args_os().nth(1)?;
+    let first = first.to_str()?;
+    let is_command = matches!(first, "-h" | "--help" | "-V" | "--version") || Cli::command().find_subcommand(first).is_some();
+    if !is_command {
+        return None;
+    }
+    attach_console();
+
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Query { timer } => query(cli.json, timer),
+        Command::Completions { shell } => completions(cli.json, shell),
+    };
+    match result {
+        Ok(()) => Some(0),
+        Err(e) if cli.json => {
+            eprintln!("{}", json!({ "error": e }));
+            Some(1)
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            Some(1)
+        }
+    }
+}
+
+// Release builds on Windows have no console of their own; borrow the one we were started from
+#[cfg(windows)]
+fn attach_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    // SAFETY: no arguments besides a constant; failure just leaves output unseen
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_console() {}
+
+fn data_dir() -> Result<PathBuf, String> {
+    dirs::data_dir()
+        .map(|dir| dir.join(APP_IDENTIFIER))
+        .ok_or_else(|| "Failed to resolve the data directory".to_string())
+}
+
+fn query(json: bool, timer: Option<u64>) -> Result<(), String> {
+    let data_dir = data_dir()?;
+    let settings: Settings = persistence::load_json(&data_dir.join("settings.json"));
+    format::set_clock_format(settings.clock_format);
+    format::set_rounding(settings.remaining_rounding);
+    if i18n::apply(settings.locale.as_deref()).is_err() {
+        let _ = i18n::apply(None);
+    }
+
+    let summary = match ask_running_instance(&settings, timer) {
+        Some(reply) => reply?,
+        None => read_saved_timers(&data_dir, timer)?,
+    };
+    if json {
+        let json = serde_json::to_string(&summary).map_err(|e| format!("Failed to serialize countdown: {}", e))?;
+        println!("{}", json);
+    } else {
+        match &summary.title {
+            Some(title) => println!("{}: {}", title, summary.text),
+            None => println!("{}", summary.text),
+        }
+    }
+    Ok(())
+}
+
+fn completions(json: bool, shell: Shell) -> Result<(), String> {
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut Cli::command(), "hourglass", &mut script);
+    if json {
+        let script = String::from_utf8(script).map_err(|e| format!("Failed to generate completions: {}", e))?;
+        println!("{}", json!({ "shell": shell.to_string(), "script": script }));
+    } else {
+        std::io::stdout().write_all(&script).map_err(|e| format!("Failed to write completions: {}", e))?;
+    }
+    Ok(())
+}
+
+// The running app knows the main countdown, which is never written to disk. It's only reachable
+// through the local API, so None means "not running or not listening".
+fn ask_running_instance(settings: &Settings, timer: Option<u64>) -> Option<Result<CountdownSummary, String>> {
+    if !settings.local_api_enabled {
+        return None;
+    }
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, settings.local_api_port));
+    let mut stream = TcpStream::connect_timeout(&address, INSTANCE_TIMEOUT).ok()?;
+    stream.set_read_timeout(Some(INSTANCE_TIMEOUT)).ok()?;
+    let path = match timer {
+        Some(id) => format!("/api/countdown?timer={}", id),
+        None => "/api/countdown".to_string(),
+    };
+    write!(stream, "GET {} HTTP/1.0\r\nHost: localhost\r\n\r\n", path).ok()?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).ok()?;
+
+    let (head, body) = response.split_once("\r\n\r\n")?;
+    let status = head.split_whitespace().nth(1)?;
+    // The API wants a token; what's on disk will have to do
+    if status == "401" {
+        return None;
+    }
+    if status != "200" {
+        return Some(Err(body.trim().to_string()));
+    }
+    Some(serde_json::from_str(body).map_err(|e| format!("Failed to parse the app's reply: {}", e)))
+}
+
+// Without the app, a timer can still be read from disk: the one asked for, or else the next to end
+fn read_saved_timers(data_dir: &Path, timer: Option<u64>) -> Result<CountdownSummary, String> {
+    let store: TimerStore = persistence::load_json(&data_dir.join("timers.json"));
+    let now = Utc::now();
+    let found = match timer {
+        Some(id) => Some(store.get(id).ok_or_else(|| t_args("error-timer-not-found", &[("id", id.into())]))?),
+        None => store
+            .timers
+            .iter()
+            .filter(|timer| timer.is_active() && timer.end_date > now)
+            .min_by_key(|timer| timer.end_date),
+    };
+    let found = found.ok_or_else(|| t("cli-no-countdown"))?;
+    Ok(Countdown::from_timer(found).summary(now))
+}
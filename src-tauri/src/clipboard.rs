@@ -0,0 +1,82 @@
+use chrono::Utc;
+use serde::Deserialize;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::i18n::{t, t_args};
+use crate::notifier::EventKind;
+use crate::phrase::{self, ParsedDeadline};
+use crate::share::Countdown;
+use crate::{format, main_window, send_notification};
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CountdownTextFormat {
+    // "12 days, 3 hours, 5 minutes"
+    #[default]
+    Remaining,
+    // "12 days until Launch — tracked with Hourglass"
+    Sentence,
+}
+
+// With no id, describes the main countdown
+fn countdown_text(app: &AppHandle, format: CountdownTextFormat, timer_id: Option<u64>) -> Result<String, String> {
+    let countdown = Countdown::load(app, timer_id)?;
+    let remaining_ms = countdown.remaining_ms(Utc::now());
+    Ok(match format {
+        CountdownTextFormat::Remaining => format::format_duration(remaining_ms),
+        CountdownTextFormat::Sentence => {
+            let duration = format::format_duration_short(remaining_ms);
+            match (countdown.title, remaining_ms > 0) {
+                (Some(title), true) => t_args("share-sentence", &[("duration", duration.into()), ("title", title.into())]),
+                (None, true) => t_args("share-sentence-untitled", &[("duration", duration.into())]),
+                (Some(title), false) => t_args("share-sentence-ended", &[("title", title.into())]),
+                (None, false) => t("share-sentence-ended-untitled"),
+            }
+        }
+    })
+}
+
+// Returns the text that was copied so the UI can confirm it
+#[tauri::command]
+pub async fn copy_countdown_text(
+    app: AppHandle,
+    format: Option<CountdownTextFormat>,
+    timer_id: Option<u64>,
+) -> Result<String, String> {
+    let text = countdown_text(&app, format.unwrap_or_default(), timer_id)?;
+    app.clipboard()
+        .write_text(text.clone())
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+    Ok(text)
+}
+
+// Reads a deadline out of whatever text is on the clipboard, e.g. a line copied from an email.
+// Nothing is created; the UI shows the proposal for the user to confirm or adjust.
+#[tauri::command]
+pub async fn propose_timer_from_clipboard(app: AppHandle) -> Result<ParsedDeadline, String> {
+    let text = app.clipboard().read_text().unwrap_or_default();
+    if text.trim().is_empty() {
+        return Err(t("error-clipboard-empty"));
+    }
+    phrase::parse_deadline(&text, Utc::now()).ok_or_else(|| t("error-no-date-in-clipboard"))
+}
+
+// Tray entry point: brings the window up with the proposal, or explains why there isn't one
+pub fn propose_from_tray(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match propose_timer_from_clipboard(app.clone()).await {
+            Ok(proposal) => {
+                if let Some(window) = main_window(&app) {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+                if let Err(e) = app.emit("timer-proposed", proposal) {
+                    eprintln!("Failed to emit timer proposal: {}", e);
+                }
+            }
+            Err(message) => send_notification(&app, EventKind::System, &t("tray-title"), &message),
+        }
+    });
+}
@@ -0,0 +1,45 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+use chrono::{DateTime, Duration, Utc};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::scheduler::{SchedulerMessage, SchedulerState};
+use crate::tamper::{self, TamperKind};
+
+// Debug shift applied to "now" for countdowns and reminders. Never saved, so a restart always
+// goes back to the real time.
+static OFFSET_SECONDS: AtomicI64 = AtomicI64::new(0);
+
+pub fn offset_seconds() -> i64 {
+    OFFSET_SECONDS.load(Ordering::Relaxed)
+}
+
+// The time countdowns and the scheduler go by; timestamps recorded for the user's own history
+// keep using the real clock
+pub fn now() -> DateTime<Utc> {
+    Utc::now() + Duration::seconds(offset_seconds())
+}
+
+// Moves the app's clock by `seconds` from the real time, e.g. to run a countdown out without
+// touching its dates; 0 turns it off. The UI is told through "time-offset-changed" so it can
+// show that the time is simulated.
+#[tauri::command]
+pub async fn set_time_offset(app: AppHandle, seconds: i64) -> Result<(), String> {
+    // Keeps the shifted time within what chrono can represent
+    if seconds.unsigned_abs() > 100 * 365 * 24 * 60 * 60 {
+        return Err(format!("Time offset out of range: {} seconds", seconds));
+    }
+    OFFSET_SECONDS.store(seconds, Ordering::Relaxed);
+    if seconds != 0 {
+        tamper::record(&app, TamperKind::TimeOffset { seconds });
+    }
+    app.state::<SchedulerState>().sender.send(SchedulerMessage::ReplanAll);
+    if let Err(e) = app.emit("time-offset-changed", seconds) {
+        eprintln!("Failed to emit time offset change: {}", e);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_time_offset() -> Result<i64, String> {
+    Ok(offset_seconds())
+}
@@ -0,0 +1,29 @@
+use std::process::Command;
+
+// Calls a method on the session bus through whichever command-line client is installed, with
+// string arguments only. None when neither client is; otherwise the reply text or why it failed.
+pub fn call_session(destination: &str, path: &str, method: &str, args: &[&str]) -> Option<Result<String, String>> {
+    let mut gdbus = Command::new("gdbus");
+    gdbus
+        .args(["call", "--session", "--dest", destination, "--object-path", path, "--method", method])
+        .args(args);
+    let mut dbus_send = Command::new("dbus-send");
+    dbus_send
+        .args(["--session", "--print-reply", &format!("--dest={}", destination), path, method])
+        .args(args.iter().map(|arg| format!("string:{}", arg)));
+
+    for mut command in [gdbus, dbus_send] {
+        match command.output() {
+            Ok(output) if output.status.success() => return Some(Ok(String::from_utf8_lossy(&output.stdout).into_owned())),
+            Ok(output) => return Some(Err(String::from_utf8_lossy(&output.stderr).trim().to_string())),
+            // Client not installed, try the next one
+            Err(_) => continue,
+        }
+    }
+    None
+}
+
+pub fn name_has_owner(name: &str) -> Option<bool> {
+    let reply = call_session("org.freedesktop.DBus", "/org/freedesktop/DBus", "org.freedesktop.DBus.NameHasOwner", &[name])?;
+    Some(reply.is_ok_and(|reply| reply.contains("true")))
+}
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::integrations::{self, ExternalDeadline, IntegrationState, SyncReport};
+
+const PROVIDER: &str = "file";
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+// Keys a TOML table may keep its date and name under
+const DUE_KEYS: [&str; 5] = ["due", "deadline", "end", "end_date", "date"];
+const TITLE_KEYS: [&str; 3] = ["title", "name", "label"];
+// Org keywords for a closed headline
+const ORG_DONE: [&str; 3] = ["DONE", "CANCELED", "CANCELLED"];
+
+// A YYYY-MM-DD date at the start of `text`, with an optional HH:MM after a space or T. Returns
+// the moment and how many bytes it took up. A bare day is due when it ends, like elsewhere.
+fn date_at(text: &str) -> Option<(DateTime<Utc>, usize)> {
+    let day = NaiveDate::parse_from_str(text.get(..10)?, "%Y-%m-%d").ok()?;
+    let time = text
+        .get(10..16)
+        .filter(|rest| rest.starts_with([' ', 'T']))
+        .and_then(|rest| NaiveTime::parse_from_str(&rest[1..], "%H:%M").ok());
+    let (time, len) = match time {
+        Some(time) => (time, 16),
+        None => (NaiveTime::from_hms_opt(23, 59, 59)?, 10),
+    };
+    let due = Local.from_local_datetime(&day.and_time(time)).earliest()?.with_timezone(&Utc);
+    Some((due, len))
+}
+
+// The first date in a line and the line with it taken out
+fn find_date(line: &str) -> Option<(DateTime<Utc>, String)> {
+    line.char_indices().find_map(|(index, c)| {
+        if !c.is_ascii_digit() || line[..index].ends_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        let (due, len) = date_at(&line[index..])?;
+        Some((due, format!("{}{}", &line[..index], &line[index + len..])))
+    })
+}
+
+// Timers are matched by title so moving a line or changing its date keeps the same timer; a
+// repeated title gets a counter so both still show up
+fn push_unique(deadlines: &mut Vec<ExternalDeadline>, seen: &mut HashMap<String, usize>, title: String, due: DateTime<Utc>) {
+    let title = title.trim().to_string();
+    if title.is_empty() {
+        return;
+    }
+    let count = seen.entry(title.clone()).or_default();
+    *count += 1;
+    let id = if *count == 1 { title.clone() } else { format!("{} #{}", title, count) };
+    deadlines.push(ExternalDeadline { id, title, due });
+}
+
+// Any line with a date counts, e.g. "- [ ] Ship the beta 2026-11-01" or "Tax return: due
+// 2026-04-15 17:00". Ticked checkboxes are done and left out.
+fn parse_markdown(contents: &str) -> Vec<ExternalDeadline> {
+    let mut deadlines = Vec::new();
+    let mut seen = HashMap::new();
+    for line in contents.lines() {
+        let text = line.trim_start().trim_start_matches(['#', '-', '*', '+', '>']).trim_start();
+        if text.starts_with("[x]") || text.starts_with("[X]") {
+            continue;
+        }
+        let text = text.trim_start_matches("[ ]");
+        let Some((due, title)) = find_date(text) else {
+            continue;
+        };
+        let title = title
+            .replace("due:", "")
+            .replace("@due()", "")
+            .replace("📅", "")
+            .trim_matches(|c: char| c.is_whitespace() || c == ':' || c == '-' || c == '(' || c == ')' || c == '*' || c == '_')
+            .to_string();
+        push_unique(&mut deadlines, &mut seen, title, due);
+    }
+    deadlines
+}
+
+// Headlines with a DEADLINE, on the headline itself or the planning line under it. DONE items
+// are left out.
+fn parse_org(contents: &str) -> Vec<ExternalDeadline> {
+    let mut deadlines = Vec::new();
+    let mut seen = HashMap::new();
+    let mut headline: Option<String> = None;
+    for line in contents.lines() {
+        if line.starts_with('*') && line.trim_start_matches('*').starts_with(' ') {
+            let mut text = line.trim_start_matches('*').trim();
+            let keyword = text.split_whitespace().next().unwrap_or_default();
+            if ORG_DONE.contains(&keyword) {
+                headline = None;
+                continue;
+            }
+            if keyword == "TODO" {
+                text = text[keyword.len()..].trim_start();
+            }
+            if text.starts_with("[#") {
+                text = text.split_once(']').map_or(text, |(_, rest)| rest.trim_start());
+            }
+            // Trailing :tags:
+            if let Some((title, tags)) = text.rsplit_once(' ') {
+                if tags.len() > 1 && tags.starts_with(':') && tags.ends_with(':') {
+                    text = title.trim_end();
+                }
+            }
+            headline = Some(text.to_string());
+        }
+        let Some(start) = line.find("DEADLINE: <") else {
+            continue;
+        };
+        let Some(title) = headline.take() else {
+            continue;
+        };
+        let title = title.split("DEADLINE:").next().unwrap_or_default().to_string();
+        if let Some((due, _)) = find_date(&line[start..]) {
+            push_unique(&mut deadlines, &mut seen, title, due);
+        }
+    }
+    deadlines
+}
+
+fn toml_due(value: &toml::Value) -> Option<DateTime<Utc>> {
+    match value {
+        toml::Value::Datetime(moment) => integrations::parse_due(&moment.to_string()),
+        toml::Value::String(text) => integrations::parse_due(text).or_else(|| find_date(text).map(|(due, _)| due)),
+        _ => None,
+    }
+}
+
+// A table with a date under one of DUE_KEYS is a deadline named by its title, or by its key
+// when it has none; any other date value is a deadline named by its key. Arrays of tables
+// ([[deadline]]) work the same way.
+fn collect_toml(name: &str, value: &toml::Value, deadlines: &mut Vec<ExternalDeadline>, seen: &mut HashMap<String, usize>) {
+    match value {
+        toml::Value::Table(table) => {
+            if let Some(due) = DUE_KEYS.iter().find_map(|key| table.get(*key).and_then(toml_due)) {
+                if table.get("done").and_then(toml::Value::as_bool) == Some(true) {
+                    return;
+                }
+                let title = TITLE_KEYS
+                    .iter()
+                    .find_map(|key| table.get(*key).and_then(toml::Value::as_str))
+                    .unwrap_or(name);
+                push_unique(deadlines, seen, title.to_string(), due);
+                return;
+            }
+            for (key, value) in table {
+                collect_toml(key, value, deadlines, seen);
+            }
+        }
+        toml::Value::Array(values) => {
+            for value in values {
+                collect_toml(name, value, deadlines, seen);
+            }
+        }
+        toml::Value::Datetime(_) => {
+            if let Some(due) = toml_due(value) {
+                push_unique(deadlines, seen, name.to_string(), due);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn parse_toml(contents: &str) -> Result<Vec<ExternalDeadline>, String> {
+    let value: toml::Value = toml::from_str(contents).map_err(|e| format!("Failed to parse TOML: {}", e))?;
+    let mut deadlines = Vec::new();
+    collect_toml("", &value, &mut deadlines, &mut HashMap::new());
+    Ok(deadlines)
+}
+
+fn parse(path: &Path, contents: &str) -> Result<Vec<ExternalDeadline>, String> {
+    match path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase).as_deref() {
+        Some("toml") => parse_toml(contents),
+        Some("org") => Ok(parse_org(contents)),
+        _ => Ok(parse_markdown(contents)),
+    }
+}
+
+fn sync(app: &AppHandle, path: &Path) -> Result<SyncReport, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let deadlines = parse(path, &contents)?;
+    // The file is the whole list, so a line that's gone (or ticked off) archives its timer
+    let report = integrations::sync_deadlines(app, PROVIDER, &deadlines, true)?;
+    if let Err(e) = app.emit("timers-synced", PROVIDER) {
+        eprintln!("Failed to emit file sync: {}", e);
+    }
+    Ok(report)
+}
+
+fn watched(app: &AppHandle) -> Option<PathBuf> {
+    app.state::<IntegrationState>().lock().ok().and_then(|config| config.deadline_file.clone())
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+// Syncs again whenever the watched file's modification time changes
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last_seen: Option<(PathBuf, SystemTime)> = None;
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let Some(path) = watched(&app) else {
+                last_seen = None;
+                continue;
+            };
+            let Some(modified) = modified(&path) else {
+                continue;
+            };
+            if last_seen.as_ref().is_some_and(|(seen, at)| *seen == path && *at == modified) {
+                continue;
+            }
+            last_seen = Some((path.clone(), modified));
+            if let Err(e) = sync(&app, &path) {
+                eprintln!("Failed to sync deadlines from {}: {}", path.display(), e);
+            }
+        }
+    });
+}
+
+// Reads the file once before saving it, so a file that can't be read or parsed is refused
+#[tauri::command]
+pub async fn watch_deadline_file(app: AppHandle, state: State<'_, IntegrationState>, path: PathBuf) -> Result<SyncReport, String> {
+    let report = sync(&app, &path)?;
+    state.lock()?.deadline_file = Some(path);
+    state.save()?;
+    Ok(report)
+}
+
+// Timers already created stay; they just stop following the file
+#[tauri::command]
+pub async fn unwatch_deadline_file(state: State<'_, IntegrationState>) -> Result<(), String> {
+    state.lock()?.deadline_file = None;
+    state.save()
+}
+
+#[tauri::command]
+pub async fn sync_deadline_file(app: AppHandle) -> Result<SyncReport, String> {
+    let path = watched(&app).ok_or_else(|| "No deadline file is being watched".to_string())?;
+    sync(&app, &path)
+}
@@ -0,0 +1,112 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::i18n::t;
+use crate::passcode::PasscodeHash;
+use crate::{persistence, NotificationState};
+
+// A passcode guarding the main countdown's dates and deleting timers, so a commitment can't be
+// reset on a whim
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct EditLock {
+    pub passcode: Option<PasscodeHash>,
+    // The main countdown isn't saved by the backend and the UI sets it again on every launch;
+    // setting it back to these dates never needs the passcode
+    pub pinned_dates: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    // When the passcode was first set; tampering from then on counts against this lock
+    pub locked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Default)]
+pub struct EditLockState {
+    pub lock: Arc<Mutex<EditLock>>,
+    pub data_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl EditLockState {
+    pub fn lock(&self) -> Result<MutexGuard<'_, EditLock>, String> {
+        self.lock.lock().map_err(|e| format!("Failed to lock edit lock: {}", e))
+    }
+
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let loaded: EditLock = persistence::load_json(&path);
+        *self.lock()? = loaded;
+        *self.data_path.lock().map_err(|e| format!("Failed to lock edit lock path: {}", e))? = Some(path);
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = self.data_path.lock().map_err(|e| format!("Failed to lock edit lock path: {}", e))?.clone();
+        match path {
+            Some(path) => persistence::save_json(&path, &*self.lock()?),
+            None => Ok(()),
+        }
+    }
+
+    // Passes when there's no passcode or the right one was given
+    pub fn check(&self, passcode: Option<&str>) -> Result<(), String> {
+        match &self.lock()?.passcode {
+            None => Ok(()),
+            Some(hash) if passcode.is_some_and(|passcode| hash.verify(passcode)) => Ok(()),
+            Some(_) if passcode.is_none() => Err(t("error-passcode-required")),
+            Some(_) => Err(t("error-wrong-passcode")),
+        }
+    }
+
+    pub fn check_dates(&self, start_date: DateTime<Utc>, end_date: DateTime<Utc>, passcode: Option<&str>) -> Result<(), String> {
+        let pinned = self.lock()?.pinned_dates == Some((start_date, end_date));
+        if pinned {
+            return Ok(());
+        }
+        self.check(passcode)
+    }
+
+    // Remembers the dates a locked countdown may always be set back to
+    pub fn pin_dates(&self, start_date: DateTime<Utc>, end_date: DateTime<Utc>) -> Result<(), String> {
+        {
+            let mut lock = self.lock()?;
+            if lock.passcode.is_none() {
+                return Ok(());
+            }
+            lock.pinned_dates = Some((start_date, end_date));
+        }
+        self.save()
+    }
+}
+
+#[tauri::command]
+pub async fn get_edit_lock_status(state: State<'_, EditLockState>) -> Result<bool, String> {
+    Ok(state.lock()?.passcode.is_some())
+}
+
+// Sets or changes the passcode; changing it needs the current one
+#[tauri::command]
+pub async fn set_edit_passcode(
+    state: State<'_, EditLockState>,
+    notifications: State<'_, NotificationState>,
+    passcode: String,
+    current: Option<String>,
+) -> Result<(), String> {
+    state.check(current.as_deref())?;
+    let hash = PasscodeHash::new(&passcode)?;
+    let start = *notifications.start_date.lock().map_err(|e| format!("Failed to lock start date: {}", e))?;
+    let end = *notifications.end_date.lock().map_err(|e| format!("Failed to lock end date: {}", e))?;
+    {
+        let mut lock = state.lock()?;
+        lock.passcode = Some(hash);
+        lock.pinned_dates = start.zip(end);
+        lock.locked_at.get_or_insert_with(Utc::now);
+    }
+    state.save()
+}
+
+#[tauri::command]
+pub async fn clear_edit_passcode(state: State<'_, EditLockState>, passcode: String) -> Result<(), String> {
+    state.check(Some(&passcode))?;
+    *state.lock()? = EditLock::default();
+    state.save()
+}
@@ -0,0 +1,184 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::i18n::t_args;
+use crate::passcode;
+use crate::persistence;
+use crate::settings::SettingsState;
+use crate::timers::TimerState;
+
+pub const EMBED_PREFIX: &str = "/embed/";
+
+// A live countdown page polling its own token's countdown, for OBS browser sources and iframes
+pub const EMBED_PAGE: &str = r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<style>
+  html, body { margin: 0; height: 100%; background: transparent; color: #fff; font-family: system-ui, sans-serif; }
+  body { display: flex; flex-direction: column; align-items: center; justify-content: center; text-shadow: 0 1px 4px #000; }
+  #title { font-size: 6vw; opacity: 0.8; }
+  #text { font-size: 14vw; font-variant-numeric: tabular-nums; }
+</style>
+</head>
+<body>
+<div id="title"></div>
+<div id="text"></div>
+<script>
+  async function refresh() {
+    try {
+      const response = await fetch(location.pathname.replace(/\/$/, "") + "/countdown");
+      if (!response.ok) return;
+      const countdown = await response.json();
+      document.getElementById("title").textContent = countdown.title || "";
+      document.getElementById("text").textContent = countdown.text;
+    } catch (e) {}
+  }
+  refresh();
+  setInterval(refresh, 1000);
+</script>
+</body>
+</html>
+"#;
+
+// Read-only access to one countdown: a timer, or the main countdown when there's none. Like API
+// tokens, only a hash is kept, so the link itself is shown once, when it's created.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EmbedToken {
+    #[serde(default)]
+    token_hash: String,
+    // Links saved before tokens were hashed; hashed as soon as they're loaded
+    #[serde(default, skip_serializing)]
+    token: Option<String>,
+    pub timer: Option<u64>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl EmbedToken {
+    // Tells links apart for listing and revoking without giving the token away
+    fn id(&self) -> String {
+        self.token_hash.chars().take(12).collect()
+    }
+}
+
+#[derive(Serialize)]
+pub struct EmbedLinkInfo {
+    pub id: String,
+    pub timer: Option<u64>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+pub struct EmbedLink {
+    pub id: String,
+    pub timer: Option<u64>,
+    // Relative to wherever the server is reachable from
+    pub path: String,
+    pub url: String,
+}
+
+#[derive(Default)]
+pub struct EmbedState {
+    pub tokens: Arc<Mutex<Vec<EmbedToken>>>,
+    pub data_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl EmbedState {
+    pub fn lock(&self) -> Result<MutexGuard<'_, Vec<EmbedToken>>, String> {
+        self.tokens.lock().map_err(|e| format!("Failed to lock embed tokens: {}", e))
+    }
+
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let mut loaded: Vec<EmbedToken> = persistence::load_json(&path);
+        let mut migrated = false;
+        for entry in &mut loaded {
+            if let Some(token) = entry.token.take() {
+                entry.token_hash = passcode::token_hash(&token);
+                migrated = true;
+            }
+        }
+        *self.lock()? = loaded;
+        *self.data_path.lock().map_err(|e| format!("Failed to lock embed tokens path: {}", e))? = Some(path);
+        if migrated {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = self.data_path.lock().map_err(|e| format!("Failed to lock embed tokens path: {}", e))?.clone();
+        match path {
+            Some(path) => persistence::save_json(&path, &*self.lock()?),
+            None => Ok(()),
+        }
+    }
+
+    // The countdown a token unlocks; Err for tokens that don't exist
+    pub fn resolve(&self, token: &str) -> Result<Option<u64>, ()> {
+        let hash = passcode::token_hash(token);
+        let tokens = self.lock().map_err(|_| ())?;
+        tokens
+            .iter()
+            .find(|entry| passcode::secrets_match(&entry.token_hash, &hash))
+            .map(|entry| entry.timer)
+            .ok_or(())
+    }
+}
+
+fn link(app: &AppHandle, entry: &EmbedToken, token: &str) -> EmbedLink {
+    let port = app.state::<SettingsState>().lock().map(|settings| settings.local_api_port).unwrap_or_default();
+    let path = format!("{}{}", EMBED_PREFIX, token);
+    EmbedLink {
+        id: entry.id(),
+        timer: entry.timer,
+        url: format!("http://localhost:{}{}", port, path),
+        path,
+    }
+}
+
+#[tauri::command]
+pub async fn list_embed_links(state: State<'_, EmbedState>) -> Result<Vec<EmbedLinkInfo>, String> {
+    Ok(state
+        .lock()?
+        .iter()
+        .map(|entry| EmbedLinkInfo {
+            id: entry.id(),
+            timer: entry.timer,
+            created_at: entry.created_at,
+        })
+        .collect())
+}
+
+// A new link to a live page for one timer, or for the main countdown without an id
+#[tauri::command]
+pub async fn create_embed_link(
+    app: AppHandle,
+    state: State<'_, EmbedState>,
+    timers: State<'_, TimerState>,
+    timer: Option<u64>,
+) -> Result<EmbedLink, String> {
+    if let Some(id) = timer {
+        timers.lock()?.get(id).ok_or_else(|| t_args("error-timer-not-found", &[("id", id.into())]))?;
+    }
+    let token = passcode::random_token()?;
+    let entry = EmbedToken {
+        token_hash: passcode::token_hash(&token),
+        token: None,
+        timer,
+        created_at: Utc::now(),
+    };
+    state.lock()?.push(entry.clone());
+    state.save()?;
+    Ok(link(&app, &entry, &token))
+}
+
+// The link stops working straight away, including for pages already open
+#[tauri::command]
+pub async fn revoke_embed_link(state: State<'_, EmbedState>, id: String) -> Result<(), String> {
+    state.lock()?.retain(|entry| entry.id() != id);
+    state.save()
+}
@@ -0,0 +1,32 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::notifier::Notification;
+use crate::timers::Timer;
+
+// Lifecycle events the frontend and integrations listen for, so they can react to a change
+// instead of re-fetching state after every action
+pub const TIMER_CREATED: &str = "timer-created";
+pub const TIMER_UPDATED: &str = "timer-updated";
+pub const TIMER_EXPIRED: &str = "timer-expired";
+pub const TIMER_PROMOTED: &str = "timer-promoted";
+pub const NOTIFICATION_SENT: &str = "notification-sent";
+pub const NOTIFICATIONS_TOGGLED: &str = "notifications-toggled";
+
+pub fn emit<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    if let Err(e) = app.emit(event, payload) {
+        eprintln!("Failed to emit {}: {}", event, e);
+    }
+}
+
+pub fn timer(app: &AppHandle, event: &str, timer: &Timer) {
+    emit(app, event, timer);
+}
+
+pub fn notification_sent(app: &AppHandle, notification: &Notification) {
+    emit(app, NOTIFICATION_SENT, notification);
+}
+
+pub fn notifications_toggled(app: &AppHandle, enabled: bool) {
+    emit(app, NOTIFICATIONS_TOGGLED, enabled);
+}
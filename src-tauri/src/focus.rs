@@ -0,0 +1,220 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tauri::{AppHandle, Emitter, Manager, State, WebviewUrl, WebviewWindowBuilder, WindowEvent};
+
+use crate::actions::SystemAction;
+use crate::i18n::{t, t_args};
+use crate::notifier::EventKind;
+use crate::send_notification;
+use crate::settings::SettingsState;
+use crate::timers::{Timer, TimerState};
+
+const BREAK_WINDOW_LABEL: &str = "break";
+// Poll quickly during a session so a blocked app doesn't get much of a head start
+const BLOCKER_SESSION_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+const BLOCKER_IDLE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+// What happens when a work-session timer runs out
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusEnforcement {
+    LockScreen,
+    BreakWindow,
+}
+
+// What to do about blocklisted applications during a work session
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BlocklistMode {
+    #[default]
+    Off,
+    Warn,
+    Close,
+}
+
+#[derive(Serialize, Clone)]
+struct BlockedApp {
+    name: String,
+    pid: u32,
+    closed: bool,
+}
+
+#[derive(Default)]
+pub struct FocusState {
+    break_until: Arc<Mutex<Option<DateTime<Utc>>>>,
+}
+
+pub fn enforce(app: &AppHandle, enforcement: FocusEnforcement, break_minutes: u32) {
+    match enforcement {
+        // No grace period here, unlike expiry actions: strict mode is the point
+        FocusEnforcement::LockScreen => {
+            tauri::async_runtime::spawn_blocking(|| {
+                if let Err(e) = SystemAction::LockScreen.perform() {
+                    eprintln!("Failed to lock the screen: {}", e);
+                }
+            });
+        }
+        FocusEnforcement::BreakWindow => {
+            if let Err(e) = start_break(app, break_minutes) {
+                eprintln!("Failed to start break: {}", e);
+            }
+        }
+    }
+}
+
+// A borderless, full-screen, always-on-top window that refuses to close or lose focus until
+// the break is over. The frontend renders it from the "break" window label.
+fn start_break(app: &AppHandle, minutes: u32) -> Result<(), String> {
+    let state = app.state::<FocusState>();
+    let ends_at = Utc::now() + Duration::minutes(minutes.into());
+    *state.break_until.lock().map_err(|e| format!("Failed to lock break state: {}", e))? = Some(ends_at);
+
+    let window = match app.get_webview_window(BREAK_WINDOW_LABEL) {
+        Some(window) => window,
+        None => {
+            let window = WebviewWindowBuilder::new(app, BREAK_WINDOW_LABEL, WebviewUrl::App("index.html?window=break".into()))
+                .title(t("break-window-title"))
+                .fullscreen(true)
+                .always_on_top(true)
+                .decorations(false)
+                .skip_taskbar(true)
+                .resizable(false)
+                .closable(false)
+                .focused(true)
+                .build()
+                .map_err(|e| format!("Failed to open break window: {}", e))?;
+            let break_until = state.break_until.clone();
+            let window_clone = window.clone();
+            window.on_window_event(move |event| {
+                let on_break = break_until
+                    .lock()
+                    .map(|until| until.is_some_and(|until| until > Utc::now()))
+                    .unwrap_or(false);
+                match event {
+                    WindowEvent::CloseRequested { api, .. } if on_break => api.prevent_close(),
+                    WindowEvent::Focused(false) if on_break => {
+                        let _ = window_clone.set_focus();
+                    }
+                    _ => {}
+                }
+            });
+            window
+        }
+    };
+    let _ = window.set_focus();
+    if let Err(e) = app.emit("break-started", ends_at) {
+        eprintln!("Failed to emit break start: {}", e);
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(u64::from(minutes) * 60)).await;
+        let state = app.state::<FocusState>();
+        // A newer break may have extended this one
+        let finished = match state.break_until.lock() {
+            Ok(mut until) if until.is_some_and(|until| until <= Utc::now()) => {
+                *until = None;
+                true
+            }
+            _ => false,
+        };
+        if finished {
+            if let Some(window) = app.get_webview_window(BREAK_WINDOW_LABEL) {
+                let _ = window.destroy();
+            }
+            if let Err(e) = app.emit("break-ended", ()) {
+                eprintln!("Failed to emit break end: {}", e);
+            }
+        }
+    });
+    Ok(())
+}
+
+// "Slack.exe", "slack" and "SLACK" all name the same app
+fn normalize_app_name(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    match name.strip_suffix(".exe") {
+        Some(stem) => stem.to_string(),
+        None => name,
+    }
+}
+
+// Title of the running work session, if any
+pub fn focus_session_title(app: &AppHandle) -> Option<String> {
+    let now = Utc::now();
+    let state = app.state::<TimerState>();
+    let store = state.lock().ok()?;
+    store.timers.iter().find(|timer| timer.is_focus_session(now)).map(Timer::display_title)
+}
+
+// Watches running processes while a work session is on and warns about or closes anything on
+// the blocklist. Each process is dealt with once per session; the list resets when it ends.
+pub fn spawn_app_blocker(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut system = System::new();
+        let mut handled: HashSet<Pid> = HashSet::new();
+        loop {
+            let (mode, blocklist) = match app.state::<SettingsState>().lock() {
+                Ok(settings) => (
+                    settings.blocklist_mode,
+                    settings.app_blocklist.iter().map(|name| normalize_app_name(name)).collect::<HashSet<_>>(),
+                ),
+                Err(e) => {
+                    eprintln!("Failed to lock settings in app blocker: {}", e);
+                    (BlocklistMode::Off, HashSet::new())
+                }
+            };
+            let session = focus_session_title(&app).filter(|_| mode != BlocklistMode::Off && !blocklist.is_empty());
+            let Some(title) = session else {
+                handled.clear();
+                tokio::time::sleep(BLOCKER_IDLE_INTERVAL).await;
+                continue;
+            };
+
+            system.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::nothing());
+            let own_pid = std::process::id();
+            for (pid, process) in system.processes() {
+                if pid.as_u32() == own_pid || handled.contains(pid) {
+                    continue;
+                }
+                let name = process.name().to_string_lossy();
+                if !blocklist.contains(&normalize_app_name(&name)) {
+                    continue;
+                }
+                handled.insert(*pid);
+                let closed = mode == BlocklistMode::Close && process.kill();
+                let key = if closed { "blocked-app-closed" } else { "blocked-app-warning" };
+                send_notification(&app, EventKind::System, &title, &t_args(key, &[("app", name.to_string().into())]));
+                let blocked = BlockedApp { name: name.to_string(), pid: pid.as_u32(), closed };
+                if let Err(e) = app.emit("blocked-app-detected", blocked) {
+                    eprintln!("Failed to emit blocked app: {}", e);
+                }
+            }
+            // Forget processes that have exited so a relaunch is caught again
+            handled.retain(|pid| system.process(*pid).is_some());
+            tokio::time::sleep(BLOCKER_SESSION_INTERVAL).await;
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_break_status(state: State<'_, FocusState>) -> Result<Option<DateTime<Utc>>, String> {
+    let until = *state.break_until.lock().map_err(|e| format!("Failed to lock break state: {}", e))?;
+    Ok(until.filter(|until| *until > Utc::now()))
+}
+
+#[tauri::command]
+pub async fn set_timer_focus_enforcement(
+    state: State<'_, TimerState>,
+    id: u64,
+    enforcement: Option<FocusEnforcement>,
+) -> Result<Timer, String> {
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        timer.focus_enforcement = enforcement;
+        Ok(())
+    })
+}
@@ -0,0 +1,159 @@
+use std::sync::RwLock;
+use chrono::{DateTime, Datelike, Local, Timelike, Utc};
+use hourglass_core::countdown::{round_to_unit, time_components, CalendarSpan};
+use hourglass_core::format as rules;
+
+pub use hourglass_core::countdown::Rounding;
+pub use hourglass_core::format::ClockFormat;
+
+use crate::i18n::{self, t, t_args};
+
+const MINUTE_MS: i64 = 60 * 1000;
+const HOUR_MS: i64 = 60 * MINUTE_MS;
+const DAY_MS: i64 = 24 * HOUR_MS;
+
+static CLOCK_FORMAT: RwLock<ClockFormat> = RwLock::new(ClockFormat::Auto);
+static ROUNDING: RwLock<Rounding> = RwLock::new(Rounding::Truncate);
+
+pub fn set_clock_format(format: ClockFormat) {
+    match CLOCK_FORMAT.write() {
+        Ok(mut current) => *current = format,
+        Err(e) => eprintln!("Failed to lock clock format: {}", e),
+    }
+}
+
+pub fn set_rounding(rounding: Rounding) {
+    match ROUNDING.write() {
+        Ok(mut current) => *current = rounding,
+        Err(e) => eprintln!("Failed to lock rounding: {}", e),
+    }
+}
+
+pub fn rounding() -> Rounding {
+    ROUNDING.read().map(|rounding| *rounding).unwrap_or_default()
+}
+
+// Time left in whole `unit_ms`, as the rounding setting says
+pub fn round_remaining(ms: i64, unit_ms: i64) -> i64 {
+    round_to_unit(ms, unit_ms, rounding())
+}
+
+// A bare "en" is read as American English, which is what most systems mean by it
+fn region() -> String {
+    i18n::current_region().unwrap_or_else(|| {
+        if i18n::current_locale() == "en" { "US" } else { "" }.to_string()
+    })
+}
+
+pub fn month_first() -> bool {
+    rules::month_first(&region())
+}
+
+fn uses_12_hour_clock() -> bool {
+    rules::uses_12_hour_clock(CLOCK_FORMAT.read().map(|format| *format).unwrap_or_default(), &region())
+}
+
+pub fn format_time(value: DateTime<Utc>) -> String {
+    format_clock_time(&value.with_timezone(&Local))
+}
+
+// A wall-clock time as the clock format setting says, in whatever zone it's already in
+pub fn format_clock_time<T: Timelike>(local: &T) -> String {
+    if uses_12_hour_clock() {
+        let (pm, hour) = local.hour12();
+        let key = if pm { "time-pm" } else { "time-am" };
+        t_args(key, &[("hour", hour.into()), ("minute", format!("{:02}", local.minute()).into())])
+    } else {
+        format!("{:02}:{:02}", local.hour(), local.minute())
+    }
+}
+
+// The year is left out for dates in the current year, like most calendars do
+pub fn format_date(value: DateTime<Utc>) -> String {
+    let local = value.with_timezone(&Local);
+    let order = if month_first() { "date-month-first" } else { "date-day-first" };
+    let key = if local.year() == Local::now().year() { order.to_string() } else { format!("{}-with-year", order) };
+    t_args(&key, &[
+        ("weekday", t(&format!("weekday-{}", local.weekday().number_from_monday())).into()),
+        ("day", local.day().into()),
+        ("month", t(&format!("month-{}", local.month())).into()),
+        ("year", local.year().to_string().into()),
+    ])
+}
+
+pub fn format_date_time(value: DateTime<Utc>) -> String {
+    t_args("date-time", &[("date", format_date(value).into()), ("time", format_time(value).into())])
+}
+
+// Largest units first, down to minutes: "3 days, 2 hours, 5 minutes", "2 hours, 5 minutes"
+pub fn format_duration(ms: i64) -> String {
+    let (days, hours, minutes, _) = time_components(round_remaining(ms, MINUTE_MS));
+    let mut parts = Vec::new();
+    if days > 0 {
+        parts.push(t_args("duration-days", &[("count", days.into())]));
+    }
+    if days > 0 || hours > 0 {
+        parts.push(t_args("duration-hours", &[("count", hours.into())]));
+    }
+    parts.push(t_args("duration-minutes", &[("count", minutes.into())]));
+    parts.join(", ")
+}
+
+// Only the largest unit, for a headline such as "12 days until launch"
+pub fn format_duration_short(ms: i64) -> String {
+    // Rounded to the minute first, so 59 minutes 30 seconds can round up to "1 hour"
+    let ms = round_remaining(ms, MINUTE_MS);
+    let unit = if ms >= DAY_MS { DAY_MS } else if ms >= HOUR_MS { HOUR_MS } else { MINUTE_MS };
+    let (days, hours, minutes, _) = time_components(round_remaining(ms, unit));
+    if days > 0 {
+        t_args("duration-days", &[("count", days.into())])
+    } else if hours > 0 {
+        t_args("duration-hours", &[("count", hours.into())])
+    } else {
+        t_args("duration-minutes", &[("count", minutes.into())])
+    }
+}
+
+// Rounded the way people talk about time: "a few minutes", "about 3 hours", "just over a day",
+// "about 2 weeks"
+pub fn format_duration_casual(ms: i64) -> String {
+    let minutes = ms.max(0) as f64 / 60_000.0;
+    let hours = minutes / 60.0;
+    let days = hours / 24.0;
+    let about = |key: &str, count: f64| t_args(key, &[("count", (count.round().max(1.0) as i64).into())]);
+    if minutes < 1.0 {
+        t("casual-under-minute")
+    } else if minutes < 5.0 {
+        t("casual-few-minutes")
+    } else if minutes < 50.0 {
+        about("casual-minutes", (minutes / 5.0).round() * 5.0)
+    } else if hours < 20.0 {
+        about("casual-hours", hours)
+    } else if hours < 24.0 {
+        t("casual-about-day")
+    } else if days < 1.5 {
+        t("casual-over-day")
+    } else if days < 10.0 {
+        about("casual-days", days)
+    } else if days < 7.0 * 7.0 {
+        about("casual-weeks", days / 7.0)
+    } else if days < 330.0 {
+        about("casual-months", days / 30.44)
+    } else {
+        about("casual-years", days / 365.25)
+    }
+}
+
+// Calendar units down to days: "1 year, 3 months, 2 days". Under a day, hours and minutes.
+pub fn format_calendar_span(span: &CalendarSpan) -> String {
+    let units = [("duration-years", span.years), ("duration-months", span.months), ("duration-days", span.days)];
+    let parts: Vec<String> = units
+        .iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(key, count)| t_args(key, &[("count", (*count).into())]))
+        .collect();
+    if parts.is_empty() {
+        return format_duration((span.hours * 60 + span.minutes) * 60 * 1000);
+    }
+    parts.join(", ")
+}
@@ -0,0 +1,51 @@
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::notifications::{self, DeliveryState, NotificationPermission};
+use crate::plugins::{self, PluginStatus};
+use crate::scripting::{self, ScriptStatus};
+use crate::tray::{self, TrayStatus};
+use crate::NotificationState;
+
+#[derive(Serialize)]
+pub struct NotificationHealth {
+    pub permission: NotificationPermission,
+    // Set while reminders go to the in-app alert window instead of the OS
+    pub degraded: Option<String>,
+    // Times the reminder loop died and was started again
+    pub loop_restarts: u32,
+    pub last_loop_failure: Option<String>,
+}
+
+// What's working and what isn't, for a diagnostics panel in the UI
+#[derive(Serialize)]
+pub struct Health {
+    pub notifications: NotificationHealth,
+    pub tray: TrayStatus,
+    pub plugins: Vec<PluginStatus>,
+    pub scripts: Vec<ScriptStatus>,
+}
+
+pub fn notification_health(app: &AppHandle, permission: NotificationPermission) -> NotificationHealth {
+    let restarts = app.state::<NotificationState>().restarts.lock().map(|restarts| restarts.clone()).unwrap_or_default();
+    NotificationHealth {
+        permission,
+        degraded: app.state::<DeliveryState>().degraded(),
+        loop_restarts: restarts.count,
+        last_loop_failure: restarts.last_reason,
+    }
+}
+
+#[tauri::command]
+pub async fn get_health(app: AppHandle) -> Result<Health, String> {
+    let checked = app.clone();
+    let (permission, tray) = tauri::async_runtime::spawn_blocking(move || (notifications::permission(&checked), tray::status(&checked)))
+        .await
+        .map_err(|e| format!("Failed to run health checks: {}", e))?;
+    Ok(Health {
+        notifications: notification_health(&app, permission),
+        tray,
+        plugins: plugins::status(&app),
+        scripts: scripting::status(&app),
+    })
+}
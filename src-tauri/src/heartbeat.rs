@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::persistence;
+
+// When the reminder loop last looked at the clock. Saved so that after a restart the loop knows
+// how long it was gone and what it missed meanwhile.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Heartbeat {
+    pub last_tick: Option<DateTime<Utc>>,
+}
+
+#[derive(Default)]
+pub struct HeartbeatState {
+    pub heartbeat: Arc<Mutex<Heartbeat>>,
+    pub data_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl HeartbeatState {
+    pub fn lock(&self) -> Result<MutexGuard<'_, Heartbeat>, String> {
+        self.heartbeat.lock().map_err(|e| format!("Failed to lock heartbeat: {}", e))
+    }
+
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let loaded: Heartbeat = persistence::load_json(&path);
+        *self.lock()? = loaded;
+        *self.data_path.lock().map_err(|e| format!("Failed to lock heartbeat path: {}", e))? = Some(path);
+        Ok(())
+    }
+
+    pub fn last_tick(&self) -> Option<DateTime<Utc>> {
+        self.lock().ok()?.last_tick
+    }
+
+    pub fn beat(&self, now: DateTime<Utc>) -> Result<(), String> {
+        self.set(Some(now))
+    }
+
+    pub fn clear(&self) -> Result<(), String> {
+        self.set(None)
+    }
+
+    fn set(&self, last_tick: Option<DateTime<Utc>>) -> Result<(), String> {
+        self.lock()?.last_tick = last_tick;
+        let path = self.data_path.lock().map_err(|e| format!("Failed to lock heartbeat path: {}", e))?.clone();
+        match path {
+            Some(path) => persistence::save_json(&path, &*self.lock()?),
+            None => Ok(()),
+        }
+    }
+}
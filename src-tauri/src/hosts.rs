@@ -0,0 +1,153 @@
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::focus::focus_session_title;
+use crate::settings::SettingsState;
+
+const BEGIN_MARKER: &str = "# BEGIN hourglass focus block";
+const END_MARKER: &str = "# END hourglass focus block";
+const SESSION_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+fn hosts_path() -> PathBuf {
+    #[cfg(windows)]
+    let path = PathBuf::from(std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".to_string()))
+        .join("System32\\drivers\\etc\\hosts");
+    #[cfg(not(windows))]
+    let path = PathBuf::from("/etc/hosts");
+    path
+}
+
+// Accepts what people paste from the address bar: "https://www.YouTube.com/feed" -> "youtube.com"
+pub fn normalize_site(site: &str) -> Option<String> {
+    let site = site.trim().to_lowercase();
+    let site = site.split_once("://").map_or(site.as_str(), |(_, rest)| rest);
+    let host = site.split(['/', '?', '#', ':']).next().unwrap_or_default();
+    let host = host.strip_prefix("www.").unwrap_or(host).trim_end_matches('.');
+    let valid = host.contains('.')
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+    valid.then(|| host.to_string())
+}
+
+// Drops our block, leaving everything else in the file exactly as it was. A block that was never
+// closed is an error rather than a reason to drop the rest of the file.
+fn strip_block(contents: &str) -> Result<String, String> {
+    let mut inside = false;
+    let mut kept = String::with_capacity(contents.len());
+    for line in contents.split_inclusive('\n') {
+        match line.trim() {
+            BEGIN_MARKER => inside = true,
+            END_MARKER => inside = false,
+            _ if !inside => kept.push_str(line),
+            _ => {}
+        }
+    }
+    if inside {
+        return Err(format!("The hosts file has \"{}\" without \"{}\"; leaving it as it is", BEGIN_MARKER, END_MARKER));
+    }
+    Ok(kept)
+}
+
+fn with_block(contents: &str, sites: &[String]) -> Result<String, String> {
+    let mut updated = strip_block(contents)?;
+    if sites.is_empty() {
+        return Ok(updated);
+    }
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated.push_str(BEGIN_MARKER);
+    updated.push('\n');
+    for site in sites {
+        for host in [site.clone(), format!("www.{}", site)] {
+            updated.push_str(&format!("0.0.0.0 {}\n:: {}\n", host, host));
+        }
+    }
+    updated.push_str(END_MARKER);
+    updated.push('\n');
+    Ok(updated)
+}
+
+// Rewrites the hosts file only when the block actually changes, so nothing needs elevated
+// rights unless blocking is in use
+fn apply(sites: &[String]) -> Result<bool, String> {
+    let path = hosts_path();
+    let contents = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let updated = with_block(&contents, sites)?;
+    if updated == contents {
+        return Ok(false);
+    }
+    // Written next to it and renamed over it, so a crash can't leave name resolution broken
+    let tmp_path = path.with_file_name("hosts.hourglass.tmp");
+    let written = fs::write(&tmp_path, updated)
+        .map_err(|e| format!("Failed to write {}: {}", tmp_path.display(), e))
+        .and_then(|_| {
+            let permissions = fs::metadata(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?.permissions();
+            fs::set_permissions(&tmp_path, permissions).map_err(|e| format!("Failed to set permissions on {}: {}", tmp_path.display(), e))
+        })
+        .and_then(|_| fs::rename(&tmp_path, &path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e)));
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    written.map(|_| true)
+}
+
+// Takes out any block, for when the app exits
+pub fn clear() -> Result<bool, String> {
+    apply(&[])
+}
+
+// Blocks the configured sites through the hosts file while a work session runs and takes the
+// block out again once it ends. The first pass also clears a block left behind by a crash.
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // What the hosts file was last set to; None until the first pass. A failed write isn't
+        // retried until the wanted list changes, so a missing permission doesn't spam errors.
+        let mut applied: Option<Vec<String>> = None;
+        loop {
+            let sites: Vec<String> = match app.state::<SettingsState>().lock() {
+                Ok(settings) if settings.block_sites => {
+                    settings.blocked_sites.iter().filter_map(|site| normalize_site(site)).collect()
+                }
+                Ok(_) => Vec::new(),
+                Err(e) => {
+                    // Keep whatever is in place rather than guessing
+                    eprintln!("Failed to lock settings in site blocker: {}", e);
+                    applied.clone().unwrap_or_default()
+                }
+            };
+            let wanted = if focus_session_title(&app).is_some() { sites } else { Vec::new() };
+            if applied.as_ref() != Some(&wanted) {
+                let result = tauri::async_runtime::spawn_blocking({
+                    let wanted = wanted.clone();
+                    move || apply(&wanted)
+                })
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|result| result);
+                match result {
+                    Ok(true) => {
+                        if let Err(e) = app.emit("site-block-changed", &wanted) {
+                            eprintln!("Failed to emit site block change: {}", e);
+                        }
+                    }
+                    Ok(false) => {}
+                    Err(e) => {
+                        eprintln!("Failed to update site block: {}", e);
+                        if let Err(e) = app.emit("site-block-failed", e) {
+                            eprintln!("Failed to emit site block failure: {}", e);
+                        }
+                    }
+                }
+                applied = Some(wanted);
+            }
+            tokio::time::sleep(SESSION_INTERVAL).await;
+        }
+    });
+}
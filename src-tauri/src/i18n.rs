@@ -0,0 +1,157 @@
+use std::sync::{LazyLock, RwLock};
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use tauri::{AppHandle, Emitter, State};
+use unic_langid::LanguageIdentifier;
+
+use crate::settings::SettingsState;
+use crate::tray;
+
+// Bundled translations; the first one is also the fallback for keys a translation is missing
+const LOCALES: [(&str, &str); 3] = [
+    ("en", include_str!("../locales/en.ftl")),
+    ("es", include_str!("../locales/es.ftl")),
+    ("pt-BR", include_str!("../locales/pt-BR.ftl")),
+];
+
+struct Catalog {
+    bundles: Vec<(LanguageIdentifier, FluentBundle<FluentResource>)>,
+    current: RwLock<usize>,
+    // Region of the requested locale (e.g. "GB" for en-GB), which decides date conventions even
+    // when the translation itself is shared
+    region: RwLock<Option<String>>,
+}
+
+static CATALOG: LazyLock<Catalog> = LazyLock::new(|| {
+    let bundles = LOCALES
+        .iter()
+        .map(|(tag, source)| {
+            let langid: LanguageIdentifier = tag.parse().expect("bundled locale tag is valid");
+            let resource = FluentResource::try_new(source.to_string())
+                .unwrap_or_else(|(resource, errors)| {
+                    eprintln!("Failed to parse {} translations: {:?}", tag, errors);
+                    resource
+                });
+            let mut bundle = FluentBundle::new_concurrent(vec![langid.clone()]);
+            // Notifications are plain text, so the bidi isolation marks would show up as junk
+            bundle.set_use_isolating(false);
+            if let Err(errors) = bundle.add_resource(resource) {
+                eprintln!("Failed to load {} translations: {:?}", tag, errors);
+            }
+            (langid, bundle)
+        })
+        .collect();
+    Catalog { bundles, current: RwLock::new(0), region: RwLock::new(None) }
+});
+
+pub fn available_locales() -> Vec<String> {
+    LOCALES.iter().map(|(tag, _)| tag.to_string()).collect()
+}
+
+fn parse_locale(locale: &str) -> Option<LanguageIdentifier> {
+    // POSIX-style tags come as "pt_BR.UTF-8"
+    let tag = locale.trim().split('.').next().unwrap_or_default().replace('_', "-");
+    tag.parse().ok()
+}
+
+// Exact matches win; otherwise any bundle for the same language will do ("pt-PT" -> "pt-BR")
+fn resolve(locale: &str) -> Option<usize> {
+    let requested = parse_locale(locale)?;
+    let bundles = &CATALOG.bundles;
+    bundles
+        .iter()
+        .position(|(langid, _)| *langid == requested)
+        .or_else(|| bundles.iter().position(|(langid, _)| langid.language == requested.language))
+}
+
+pub fn validate(locale: &str) -> Result<(), String> {
+    match resolve(locale) {
+        Some(_) => Ok(()),
+        None => Err(t_args("error-unsupported-locale", &[
+            ("locale", locale.into()),
+            ("available", available_locales().join(", ").into()),
+        ])),
+    }
+}
+
+// Switches the active locale and returns the one actually in use. No preference means following
+// the OS language, falling back to English when there is no translation for it.
+pub fn apply(locale: Option<&str>) -> Result<String, String> {
+    if let Some(locale) = locale {
+        validate(locale)?;
+    }
+    let requested = locale.map(str::to_string).or_else(sys_locale::get_locale);
+    let index = requested.as_deref().and_then(resolve).unwrap_or(0);
+    let region = requested
+        .as_deref()
+        .and_then(parse_locale)
+        .and_then(|langid| langid.region)
+        .map(|region| region.as_str().to_string());
+    *CATALOG.current.write().map_err(|e| format!("Failed to lock locale: {}", e))? = index;
+    *CATALOG.region.write().map_err(|e| format!("Failed to lock locale: {}", e))? = region;
+    Ok(CATALOG.bundles[index].0.to_string())
+}
+
+pub fn current_locale() -> String {
+    let index = CATALOG.current.read().map(|index| *index).unwrap_or(0);
+    CATALOG.bundles[index].0.to_string()
+}
+
+pub fn current_region() -> Option<String> {
+    CATALOG.region.read().map(|region| region.clone()).unwrap_or_default()
+}
+
+fn format(key: &str, args: Option<&FluentArgs>) -> String {
+    let current = CATALOG.current.read().map(|index| *index).unwrap_or(0);
+    for index in [current, 0] {
+        let bundle = &CATALOG.bundles[index].1;
+        let Some(pattern) = bundle.get_message(key).and_then(|message| message.value()) else {
+            continue;
+        };
+        let mut errors = Vec::new();
+        let text = bundle.format_pattern(pattern, args, &mut errors);
+        if !errors.is_empty() {
+            eprintln!("Failed to format translation '{}': {:?}", key, errors);
+        }
+        return text.into_owned();
+    }
+    eprintln!("Missing translation for '{}'", key);
+    key.to_string()
+}
+
+pub fn t(key: &str) -> String {
+    format(key, None)
+}
+
+pub fn t_args(key: &str, args: &[(&str, FluentValue<'_>)]) -> String {
+    let args: FluentArgs = args.iter().map(|(name, value)| (*name, value.clone())).collect();
+    format(key, Some(&args))
+}
+
+// Everything the backend renders up front (tray labels) has to be redrawn after a switch;
+// notifications pick up the new locale the next time they are composed
+pub fn locale_changed(app: &AppHandle) {
+    tray::refresh_labels(app);
+    if let Err(e) = app.emit("locale-changed", current_locale()) {
+        eprintln!("Failed to emit locale change: {}", e);
+    }
+}
+
+#[tauri::command]
+pub async fn get_available_locales() -> Result<Vec<String>, String> {
+    Ok(available_locales())
+}
+
+#[tauri::command]
+pub async fn set_locale(
+    app: AppHandle,
+    state: State<'_, SettingsState>,
+    locale: Option<String>,
+) -> Result<String, String> {
+    let locale = locale.map(|l| l.trim().to_string()).filter(|l| !l.is_empty());
+    let applied = apply(locale.as_deref())?;
+    state.lock()?.locale = locale;
+    state.save()?;
+    locale_changed(&app);
+    Ok(applied)
+}
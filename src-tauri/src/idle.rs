@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+// How long since the last keyboard or mouse input. None when the platform doesn't tell us,
+// in which case the user is treated as present.
+#[cfg(windows)]
+pub fn idle_duration() -> Option<Duration> {
+    use windows_sys::Win32::System::SystemInformation::GetTickCount;
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+    let mut info = LASTINPUTINFO { cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32, dwTime: 0 };
+    // SAFETY: `info` is a valid, correctly sized LASTINPUTINFO for the duration of the call
+    if unsafe { GetLastInputInfo(&mut info) } == 0 {
+        return None;
+    }
+    // Both counters wrap after ~49 days, so compare them with wrapping arithmetic
+    let now = unsafe { GetTickCount() };
+    Some(Duration::from_millis(now.wrapping_sub(info.dwTime).into()))
+}
+
+// HIDIdleTime is reported in nanoseconds
+#[cfg(target_os = "macos")]
+pub fn idle_duration() -> Option<Duration> {
+    let output = std::process::Command::new("ioreg").args(["-c", "IOHIDSystem", "-d", "4"]).output().ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let line = text.lines().find(|line| line.contains("\"HIDIdleTime\""))?;
+    let nanos: u64 = line.rsplit('=').next()?.trim().parse().ok()?;
+    Some(Duration::from_nanos(nanos))
+}
+
+// GNOME answers over D-Bus (and works on Wayland); other X11 desktops need xprintidle
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn idle_duration() -> Option<Duration> {
+    use std::process::Command;
+
+    let mutter = Command::new("gdbus")
+        .args([
+            "call",
+            "--session",
+            "--dest",
+            "org.gnome.Mutter.IdleMonitor",
+            "--object-path",
+            "/org/gnome/Mutter/IdleMonitor/Core",
+            "--method",
+            "org.gnome.Mutter.IdleMonitor.GetIdletime",
+        ])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| {
+            // Replies look like "(uint64 12345,)"
+            let text = String::from_utf8_lossy(&output.stdout).to_string();
+            text.trim_matches(|c| c == '(' || c == ')' || c == ',' || char::is_whitespace(c))
+                .trim_start_matches("uint64")
+                .trim()
+                .parse::<u64>()
+                .ok()
+        });
+    let millis = mutter.or_else(|| {
+        let output = Command::new("xprintidle").output().ok().filter(|output| output.status.success())?;
+        String::from_utf8_lossy(&output.stdout).trim().parse::<u64>().ok()
+    })?;
+    Some(Duration::from_millis(millis))
+}
+
+pub fn is_away(threshold: Duration) -> bool {
+    idle_duration().is_some_and(|idle| idle >= threshold)
+}
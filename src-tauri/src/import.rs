@@ -0,0 +1,423 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State, Url};
+
+use crate::events;
+use crate::i18n::{t, t_args};
+use crate::main_window;
+use crate::timers::{self, Timer, TimerState};
+
+// Bumped when the layout of .hourglass files changes incompatibly
+pub const HOURGLASS_FILE_VERSION: u32 = 1;
+
+// A timer as it travels between machines: what the user set up, none of the runtime state
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PortableTimer {
+    pub label: String,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+impl From<&Timer> for PortableTimer {
+    fn from(timer: &Timer) -> Self {
+        Self {
+            label: timer.label.clone(),
+            start_date: timer.start_date,
+            end_date: timer.end_date,
+            tags: timer.tags.clone(),
+            color: timer.color.clone(),
+            icon: timer.icon.clone(),
+            note: timer.note.clone(),
+        }
+    }
+}
+
+// Contents of a .hourglass file
+#[derive(Serialize, Deserialize)]
+pub struct HourglassFile {
+    pub version: u32,
+    pub timers: Vec<PortableTimer>,
+}
+
+// What came out of one file or link, for the UI to confirm before anything is created
+#[derive(Serialize, Clone)]
+pub struct ImportPreview {
+    // The file path or link it came from
+    pub source: String,
+    pub timers: Vec<PortableTimer>,
+    pub error: Option<String>,
+}
+
+fn parse_hourglass(contents: &str) -> Result<Vec<PortableTimer>, String> {
+    let file: HourglassFile = serde_json::from_str(contents).map_err(|e| t_args("error-unreadable-file", &[("reason", e.to_string().into())]))?;
+    if file.version > HOURGLASS_FILE_VERSION {
+        return Err(t("error-newer-file-version"));
+    }
+    Ok(file.timers)
+}
+
+// Undoes RFC 5545 line folding: a line starting with a space or tab continues the previous one
+fn unfold(contents: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in contents.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+// Handles UTC ("...Z"), floating and all-day (VALUE=DATE) values. Times with a TZID are read
+// as local time, which is right for the common case of invites from the same time zone.
+fn parse_ics_date(params: &str, value: &str) -> Option<DateTime<Utc>> {
+    if (params.contains("VALUE=DATE") && !params.contains("VALUE=DATE-TIME")) || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Local.from_local_datetime(&date.and_hms_opt(0, 0, 0)?).earliest().map(|d| d.with_timezone(&Utc));
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        return NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok().map(|d| d.and_utc());
+    }
+    let local = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    Local.from_local_datetime(&local).earliest().map(|d| d.with_timezone(&Utc))
+}
+
+// "P1D", "PT1H30M", "P2W"
+fn parse_ics_duration(value: &str) -> Option<Duration> {
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in value.strip_prefix('P')?.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            unit => {
+                let amount: i64 = number.parse().ok()?;
+                number.clear();
+                total += match unit {
+                    'W' => Duration::weeks(amount),
+                    'D' => Duration::days(amount),
+                    'H' => Duration::hours(amount),
+                    'M' => Duration::minutes(amount),
+                    'S' => Duration::seconds(amount),
+                    _ => return None,
+                };
+            }
+        }
+    }
+    Some(if negative { -total } else { total })
+}
+
+#[derive(Default)]
+struct EventFields {
+    summary: Option<String>,
+    description: Option<String>,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    duration: Option<Duration>,
+}
+
+// Every VEVENT becomes a timer running from its start to its end. Events without an end
+// last for their DURATION, or for the whole day when they're all-day events.
+pub fn parse_ics(contents: &str) -> Result<Vec<PortableTimer>, String> {
+    let mut timers = Vec::new();
+    let mut event: Option<EventFields> = None;
+    for line in unfold(contents) {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (name, params) = name.split_once(';').unwrap_or((name, ""));
+        let value = value.trim();
+        match (name.to_ascii_uppercase().as_str(), value) {
+            ("BEGIN", "VEVENT") => event = Some(EventFields::default()),
+            ("END", "VEVENT") => {
+                let Some(fields) = event.take() else {
+                    continue;
+                };
+                let Some(start) = fields.start else {
+                    continue;
+                };
+                let end = fields.end.or_else(|| fields.duration.map(|d| start + d)).unwrap_or(start + Duration::days(1));
+                if end <= start {
+                    continue;
+                }
+                timers.push(PortableTimer {
+                    label: fields.summary.unwrap_or_default(),
+                    start_date: start,
+                    end_date: end,
+                    tags: Vec::new(),
+                    color: None,
+                    icon: None,
+                    note: fields.description.filter(|d| !d.trim().is_empty()),
+                });
+            }
+            (field, value) => {
+                let Some(fields) = event.as_mut() else {
+                    continue;
+                };
+                match field {
+                    "SUMMARY" => fields.summary = Some(unescape(value)),
+                    "DESCRIPTION" => fields.description = Some(unescape(value)),
+                    "DTSTART" => fields.start = parse_ics_date(params, value),
+                    "DTEND" => fields.end = parse_ics_date(params, value),
+                    "DURATION" => fields.duration = parse_ics_duration(value),
+                    _ => {}
+                }
+            }
+        }
+    }
+    if timers.is_empty() {
+        return Err(t("error-no-events-in-file"));
+    }
+    Ok(timers)
+}
+
+pub fn is_importable(path: &Path) -> bool {
+    let extension = path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase);
+    matches!(extension.as_deref(), Some("ics" | "hourglass"))
+}
+
+pub fn read_file(path: &Path) -> Result<Vec<PortableTimer>, String> {
+    let contents = fs::read_to_string(path).map_err(|e| t_args("error-unreadable-file", &[("reason", e.to_string().into())]))?;
+    let extension = path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("hourglass") => parse_hourglass(&contents),
+        _ => parse_ics(&contents),
+    }
+}
+
+// Previews that arrived before the UI was listening, e.g. a file that launched the app
+#[derive(Default)]
+pub struct ImportState {
+    pending: Mutex<Vec<ImportPreview>>,
+}
+
+// Parses the files straight away and hands the results to the UI for confirmation. Files of
+// other types are ignored so dropping e.g. an image does nothing.
+pub fn offer_files(app: &AppHandle, paths: &[PathBuf]) {
+    let previews: Vec<ImportPreview> = paths
+        .iter()
+        .filter(|path| is_importable(path))
+        .map(|path| match read_file(path) {
+            Ok(timers) => ImportPreview { source: path.display().to_string(), timers, error: None },
+            Err(error) => ImportPreview { source: path.display().to_string(), timers: Vec::new(), error: Some(error) },
+        })
+        .collect();
+    offer(app, previews);
+}
+
+// hourglass://timer links, as encoded in shared QR codes. Links of other kinds are ignored.
+pub fn offer_links(app: &AppHandle, urls: &[Url]) {
+    let previews = urls
+        .iter()
+        .filter(|url| url.scheme() == "hourglass" && url.host_str() == Some("timer"))
+        .map(|url| match parse_link(url) {
+            Ok(timer) => ImportPreview { source: url.to_string(), timers: vec![timer], error: None },
+            Err(error) => ImportPreview { source: url.to_string(), timers: Vec::new(), error: Some(error) },
+        })
+        .collect();
+    offer(app, previews);
+}
+
+fn parse_link(url: &Url) -> Result<PortableTimer, String> {
+    let param = |name: &str| url.query_pairs().find(|(key, _)| key == name).map(|(_, value)| value.into_owned());
+    let end = timers::parse_date(&param("end").unwrap_or_default(), "field-end-date")?;
+    let start = match param("start") {
+        Some(start) => timers::parse_date(&start, "field-start-date")?,
+        None => Utc::now(),
+    };
+    Ok(PortableTimer {
+        label: param("label").unwrap_or_default(),
+        start_date: start,
+        end_date: end,
+        tags: Vec::new(),
+        color: None,
+        icon: None,
+        note: None,
+    })
+}
+
+fn offer(app: &AppHandle, previews: Vec<ImportPreview>) {
+    if previews.is_empty() {
+        return;
+    }
+    match app.state::<ImportState>().pending.lock() {
+        Ok(mut pending) => pending.extend(previews.iter().cloned()),
+        Err(e) => eprintln!("Failed to lock pending imports: {}", e),
+    }
+    if let Some(window) = main_window(app) {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    if let Err(e) = app.emit("import-preview", previews) {
+        eprintln!("Failed to emit import preview: {}", e);
+    }
+}
+
+// Files passed on the command line, which is how Windows and Linux hand over a double-clicked
+// .hourglass file
+pub fn offer_launch_arguments(app: &AppHandle) {
+    let paths: Vec<PathBuf> = std::env::args_os().skip(1).map(PathBuf::from).filter(|path| path.is_file()).collect();
+    offer_files(app, &paths);
+}
+
+// Called by the UI once it's ready; a preview is only handed out once
+#[tauri::command]
+pub async fn take_pending_imports(state: State<'_, ImportState>) -> Result<Vec<ImportPreview>, String> {
+    let mut pending = state.pending.lock().map_err(|e| format!("Failed to lock pending imports: {}", e))?;
+    Ok(std::mem::take(&mut *pending))
+}
+
+// Writes the given timers to a .hourglass file that can be opened or dropped on another machine
+#[tauri::command]
+pub async fn export_timers(state: State<'_, TimerState>, ids: Vec<u64>, path: String) -> Result<(), String> {
+    let timers = {
+        let store = state.lock()?;
+        ids.iter()
+            .map(|id| {
+                store.get(*id).map(PortableTimer::from).ok_or_else(|| t_args("error-timer-not-found", &[("id", (*id).into())]))
+            })
+            .collect::<Result<Vec<_>, String>>()?
+    };
+    let file = HourglassFile { version: HOURGLASS_FILE_VERSION, timers };
+    let contents = serde_json::to_string_pretty(&file).map_err(|e| format!("Failed to serialize timers: {}", e))?;
+    fs::write(&path, contents).map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(';', "\\;").replace(',', "\\,").replace('\n', "\\n")
+}
+
+// Lines longer than 75 bytes are folded, continuing after a space, without splitting a character
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded
+}
+
+fn format_ics_date(date: DateTime<Utc>) -> String {
+    date.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+// "-PT90S" style durations, which every calendar reads; larger units are optional
+fn format_ics_duration(seconds_before: i64) -> String {
+    if seconds_before == 0 {
+        "PT0S".to_string()
+    } else {
+        format!("-PT{}S", seconds_before)
+    }
+}
+
+// One event at the deadline with an alarm for each T-minus reminder and one for the deadline
+// itself. Alarms are relative to the event start, the one anchor every calendar app honors.
+fn timer_to_ics(timer: &Timer) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Hourglass//Hourglass//EN".to_string(),
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:timer-{}@{}", timer.id, crate::cli::APP_IDENTIFIER),
+        format!("DTSTAMP:{}", format_ics_date(Utc::now())),
+        format!("DTSTART:{}", format_ics_date(timer.end_date)),
+        format!("SUMMARY:{}", escape(&timer.display_title())),
+    ];
+    if let Some(note) = timer.note.as_deref().filter(|note| !note.trim().is_empty()) {
+        lines.push(format!("DESCRIPTION:{}", escape(note)));
+    }
+    let mut offsets: Vec<i64> = timer.relative_reminders.iter().map(|reminder| reminder.seconds_before).collect();
+    offsets.push(0);
+    offsets.sort_unstable_by(|a, b| b.cmp(a));
+    offsets.dedup();
+    for seconds_before in offsets {
+        lines.extend([
+            "BEGIN:VALARM".to_string(),
+            "ACTION:DISPLAY".to_string(),
+            format!("DESCRIPTION:{}", escape(&timer.display_title())),
+            format!("TRIGGER:{}", format_ics_duration(seconds_before)),
+            "END:VALARM".to_string(),
+        ]);
+    }
+    lines.extend(["END:VEVENT".to_string(), "END:VCALENDAR".to_string()]);
+    lines.iter().map(|line| fold(line) + "\r\n").collect()
+}
+
+// Writes a timer to an .ics file whose alarms repeat its reminder schedule, for phone calendars
+#[tauri::command]
+pub async fn export_timer_with_alarms(state: State<'_, TimerState>, id: u64, path: String) -> Result<(), String> {
+    let contents = {
+        let store = state.lock()?;
+        let timer = store.get(id).ok_or_else(|| t_args("error-timer-not-found", &[("id", id.into())]))?;
+        timer_to_ics(timer)
+    };
+    fs::write(&path, contents).map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+// Creates the timers the user confirmed from a preview. Everything is checked first so a bad
+// entry doesn't leave half the batch imported.
+#[tauri::command]
+pub async fn import_timers(state: State<'_, TimerState>, timers: Vec<PortableTimer>) -> Result<Vec<Timer>, String> {
+    let mut checked = Vec::new();
+    for portable in timers {
+        if portable.end_date <= portable.start_date {
+            return Err(t("error-end-before-start"));
+        }
+        let color = portable.color.as_deref().map(timers::normalize_color).transpose()?;
+        let icon = portable.icon.as_deref().map(timers::normalize_icon).transpose()?;
+        checked.push((portable, color, icon));
+    }
+
+    let mut store = state.lock()?;
+    let mut created = Vec::new();
+    for (portable, color, icon) in checked {
+        let timer = store.add(portable.label.trim().to_string(), portable.start_date, portable.end_date);
+        timer.tags = timers::normalize_tags(portable.tags);
+        timer.color = color;
+        timer.icon = icon;
+        timer.note = portable.note;
+        created.push(timer.clone());
+    }
+    store.save()?;
+    for timer in &created {
+        store.emit(events::TIMER_CREATED, timer);
+    }
+    Ok(created)
+}
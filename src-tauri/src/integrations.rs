@@ -0,0 +1,142 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::{events, persistence};
+use crate::scheduler::{EventSource, SchedulerMessage};
+use crate::timers::{ExternalSource, TimerState, TimerStatus};
+
+// A dated item in another service that should have a matching timer
+pub struct ExternalDeadline {
+    pub id: String,
+    pub title: String,
+    pub due: DateTime<Utc>,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct SyncReport {
+    pub created: usize,
+    pub updated: usize,
+    pub archived: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TodoistConfig {
+    pub token: String,
+    // Only tasks from this project; all projects when unset
+    pub project_id: Option<String>,
+}
+
+// Credentials for connected services, kept out of settings.json so sharing settings doesn't
+// share tokens
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct IntegrationConfig {
+    pub todoist: Option<TodoistConfig>,
+    // A Markdown, TOML or org-mode file whose dates become timers
+    pub deadline_file: Option<PathBuf>,
+    // For the email notification channel's SMTP login
+    pub smtp_password: Option<String>,
+}
+
+#[derive(Default)]
+pub struct IntegrationState {
+    pub config: Arc<Mutex<IntegrationConfig>>,
+    pub data_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl IntegrationState {
+    pub fn lock(&self) -> Result<MutexGuard<'_, IntegrationConfig>, String> {
+        self.config.lock().map_err(|e| format!("Failed to lock integrations: {}", e))
+    }
+
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let loaded: IntegrationConfig = persistence::load_json(&path);
+        *self.lock()? = loaded;
+        *self.data_path.lock().map_err(|e| format!("Failed to lock integrations path: {}", e))? = Some(path);
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = self.data_path.lock().map_err(|e| format!("Failed to lock integrations path: {}", e))?.clone();
+        match path {
+            Some(path) => persistence::save_json(&path, &*self.lock()?),
+            None => Ok(()),
+        }
+    }
+}
+
+// Services hand out either a day or a moment. A bare day is due when it ends, in local time.
+pub fn parse_due(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(moment) = DateTime::parse_from_rfc3339(value) {
+        return Some(moment.with_timezone(&Utc));
+    }
+    if let Ok(floating) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S") {
+        return Local.from_local_datetime(&floating).earliest().map(|moment| moment.with_timezone(&Utc));
+    }
+    let day = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    Local
+        .from_local_datetime(&day.and_hms_opt(23, 59, 59)?)
+        .earliest()
+        .map(|moment| moment.with_timezone(&Utc))
+}
+
+// Brings the timers mirroring `provider` in line with its deadlines: new ones become timers and
+// moved ones are rescheduled. With `archive_missing`, a timer whose item is gone (done or
+// deleted upstream) is archived.
+pub fn sync_deadlines(app: &AppHandle, provider: &str, deadlines: &[ExternalDeadline], archive_missing: bool) -> Result<SyncReport, String> {
+    let state = app.state::<TimerState>();
+    let mut store = state.lock()?;
+    let now = Utc::now();
+    let mut report = SyncReport::default();
+
+    for deadline in deadlines {
+        let existing = store.timers.iter_mut().find(|timer| {
+            timer.status != TimerStatus::Trashed
+                && timer.source.as_ref().is_some_and(|source| source.provider == provider && source.id == deadline.id)
+        });
+        match existing {
+            Some(timer) => {
+                if timer.end_date != deadline.due || timer.label != deadline.title {
+                    timer.reschedule(deadline.due, now);
+                    timer.label = deadline.title.clone();
+                    let updated = timer.clone();
+                    store.scheduler.send(SchedulerMessage::Replan(EventSource::Timer(updated.id)));
+                    store.emit(events::TIMER_UPDATED, &updated);
+                    report.updated += 1;
+                }
+            }
+            None => {
+                let start = now.min(deadline.due);
+                let timer = store.add(deadline.title.clone(), start, deadline.due);
+                timer.source = Some(ExternalSource {
+                    provider: provider.to_string(),
+                    id: deadline.id.clone(),
+                });
+                let created = timer.clone();
+                store.emit(events::TIMER_CREATED, &created);
+                report.created += 1;
+            }
+        }
+    }
+
+    if archive_missing {
+        for timer in store.timers.iter_mut().filter(|timer| {
+            timer.status == TimerStatus::Active
+                && timer.source.as_ref().is_some_and(|source| {
+                    source.provider == provider && !deadlines.iter().any(|deadline| deadline.id == source.id)
+                })
+        }) {
+            timer.status = TimerStatus::Archived;
+            timer.archived_at = Some(now);
+            report.archived += 1;
+        }
+    }
+
+    if report.created + report.updated + report.archived > 0 {
+        store.save()?;
+    }
+    Ok(report)
+}
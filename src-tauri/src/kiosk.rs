@@ -0,0 +1,228 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::i18n::t;
+use crate::passcode::PasscodeHash;
+use crate::{create_auto_launch, main_window, persistence, viewer};
+
+const KIOSK_FLAG: &str = "--kiosk";
+
+// Commands that only read, or that a locked-down display still needs (silencing the alarm,
+// the passcode to leave). Everything else is refused while locked down, so a command added
+// later is locked out until it's listed here.
+const READ_ONLY_COMMANDS: [&str; 54] = [
+    "get_notification_status",
+    "start_notifications",
+    "send_test_notification",
+    "get_notification_permission",
+    "request_notification_permission",
+    "take_fallback_alerts",
+    "get_health",
+    "get_app_snapshot",
+    "get_tray_status",
+    "check_for_updates_now",
+    "get_changelog",
+    "get_usage_stats",
+    "get_time_offset",
+    "get_clock_drift",
+    "get_tamper_status",
+    "open_timer_window",
+    "list_displays",
+    "get_kiosk_status",
+    "disable_kiosk",
+    "get_edit_lock_status",
+    "get_viewer_status",
+    "list_embed_links",
+    "list_api_tokens",
+    "get_api_audit_log",
+    "get_time_remaining",
+    "get_startup_enabled",
+    "get_timer_timeline",
+    "get_pace",
+    "list_timers",
+    "get_timer",
+    "search_timers",
+    "list_trash",
+    "get_timer_history",
+    "get_tag_defaults",
+    "preview_reminder_schedule",
+    "get_timer_streak",
+    "get_settings",
+    "validate_settings",
+    "get_settings_problems",
+    "dismiss_alarm",
+    "get_system_appearance",
+    "get_available_locales",
+    "get_countdown_announcement",
+    "get_tick_rate",
+    "get_pending_expiry_action",
+    "get_break_status",
+    "get_app_usage",
+    "get_statistics",
+    "copy_countdown_text",
+    "propose_timer_from_clipboard",
+    "take_pending_imports",
+    "get_timer_qr",
+    "get_countdown_breakdown",
+    "get_deadline_in_zones",
+];
+
+// A viewer only mirrors another countdown, so it may still leave viewing, quit or restart
+const VIEWER_COMMANDS: [&str; 5] = ["stop_viewing", "quit_app", "restart_app", "answer_close_prompt", "enable_kiosk"];
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct KioskRecord {
+    pub enabled: bool,
+    pub passcode: Option<PasscodeHash>,
+    // Whether entering kiosk mode is what turned on launch at login, so leaving undoes it
+    pub enabled_startup: bool,
+}
+
+#[derive(Default)]
+pub struct KioskState {
+    pub record: Arc<Mutex<KioskRecord>>,
+    pub data_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl KioskState {
+    pub fn lock(&self) -> Result<MutexGuard<'_, KioskRecord>, String> {
+        self.record.lock().map_err(|e| format!("Failed to lock kiosk state: {}", e))
+    }
+
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let loaded: KioskRecord = persistence::load_json(&path);
+        *self.lock()? = loaded;
+        *self.data_path.lock().map_err(|e| format!("Failed to lock kiosk path: {}", e))? = Some(path);
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = self.data_path.lock().map_err(|e| format!("Failed to lock kiosk path: {}", e))?.clone();
+        match path {
+            Some(path) => persistence::save_json(&path, &*self.lock()?),
+            None => Ok(()),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.record.lock().map(|record| record.enabled).unwrap_or(false)
+    }
+}
+
+// Called for every command before it runs. Viewer mode is read-only in the same way.
+pub fn check_command(app: &AppHandle, command: &str) -> Result<(), String> {
+    if READ_ONLY_COMMANDS.contains(&command) {
+        return Ok(());
+    }
+    ensure_unlocked(app)?;
+    if viewer::is_active(app) && !VIEWER_COMMANDS.contains(&command) {
+        return Err(t("error-viewer-read-only"));
+    }
+    Ok(())
+}
+
+// For ways out of the app that don't go through a command, like the tray menu
+pub fn ensure_unlocked(app: &AppHandle) -> Result<(), String> {
+    if app.state::<KioskState>().is_active() {
+        return Err(t("error-kiosk-locked"));
+    }
+    Ok(())
+}
+
+// Full screen with no way to close it; the close handler also refuses while kiosk mode is on
+fn apply_window(app: &AppHandle, active: bool) {
+    let Some(window) = main_window(app) else {
+        return;
+    };
+    if active {
+        let _ = window.show();
+        let _ = window.unminimize();
+    }
+    let _ = window.set_fullscreen(active);
+    let _ = window.set_closable(!active);
+    let _ = window.set_minimizable(!active);
+    if active {
+        let _ = window.set_focus();
+    }
+    if let Err(e) = app.emit("kiosk-changed", active) {
+        eprintln!("Failed to emit kiosk change: {}", e);
+    }
+}
+
+// Kiosk mode survives restarts, and the --kiosk flag turns it on using the passcode set earlier
+pub fn restore(app: &AppHandle) {
+    let state = app.state::<KioskState>();
+    let flagged = std::env::args().skip(1).any(|arg| arg == KIOSK_FLAG);
+    let active = match state.lock() {
+        Ok(mut record) => {
+            if flagged && !record.enabled {
+                if record.passcode.is_some() {
+                    record.enabled = true;
+                } else {
+                    eprintln!("Ignoring {}: set a kiosk passcode in the app first", KIOSK_FLAG);
+                }
+            }
+            record.enabled
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+    if flagged {
+        if let Err(e) = state.save() {
+            eprintln!("Failed to save kiosk state: {}", e);
+        }
+    }
+    if active {
+        apply_window(app, true);
+    }
+}
+
+#[tauri::command]
+pub async fn get_kiosk_status(state: State<'_, KioskState>) -> Result<bool, String> {
+    Ok(state.is_active())
+}
+
+// Locks the app down for a dedicated display and starts it with the computer
+#[tauri::command]
+pub async fn enable_kiosk(app: AppHandle, state: State<'_, KioskState>, passcode: String) -> Result<(), String> {
+    let hash = PasscodeHash::new(&passcode)?;
+    let auto = create_auto_launch()?;
+    let enabled_startup = !auto.is_enabled().unwrap_or(false);
+    if enabled_startup {
+        auto.enable().map_err(|e| format!("Failed to enable launch at login: {}", e))?;
+    }
+    {
+        let mut record = state.lock()?;
+        record.enabled = true;
+        record.passcode = Some(hash);
+        record.enabled_startup = enabled_startup;
+    }
+    state.save()?;
+    apply_window(&app, true);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn disable_kiosk(app: AppHandle, state: State<'_, KioskState>, passcode: String) -> Result<(), String> {
+    let enabled_startup = {
+        let mut record = state.lock()?;
+        if !record.passcode.as_ref().is_some_and(|hash| hash.verify(&passcode)) {
+            return Err(t("error-wrong-passcode"));
+        }
+        record.enabled = false;
+        std::mem::take(&mut record.enabled_startup)
+    };
+    state.save()?;
+    if enabled_startup {
+        create_auto_launch()?
+            .disable()
+            .map_err(|e| format!("Failed to disable launch at login: {}", e))?;
+    }
+    apply_window(&app, false);
+    Ok(())
+}
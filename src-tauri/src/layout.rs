@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize, State, WebviewUrl, WebviewWindow, WebviewWindowBuilder, WindowEvent};
+
+use crate::i18n::t_args;
+use crate::{opacity, persistence};
+use crate::timers::TimerState;
+
+const TIMER_WINDOW_PREFIX: &str = "timer-";
+const TIMER_WINDOW_WIDTH: f64 = 320.0;
+const TIMER_WINDOW_HEIGHT: f64 = 150.0;
+// Gap left between a window placed in a corner and the screen edge, in logical pixels
+const CORNER_MARGIN: f64 = 16.0;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Center,
+}
+
+// A window pinned to a corner of a particular monitor. The name is matched first because
+// indices shift when monitors are plugged in or out.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Placement {
+    pub display: usize,
+    pub display_name: Option<String>,
+    pub corner: Corner,
+}
+
+#[derive(Serialize)]
+pub struct DisplayInfo {
+    pub index: usize,
+    pub name: Option<String>,
+    pub width: u32,
+    pub height: u32,
+    pub primary: bool,
+}
+
+// Where a window was last, in physical pixels
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct TimerWindow {
+    pub geometry: Option<WindowGeometry>,
+    // Still open when the app quit, so it comes back on the next launch
+    pub open: bool,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct WindowLayout {
+    pub timers: HashMap<u64, TimerWindow>,
+    // Keyed by window label
+    pub placements: HashMap<String, Placement>,
+    // Percent, for windows that aren't fully opaque, keyed by window label
+    pub opacity: HashMap<String, u8>,
+}
+
+#[derive(Default)]
+pub struct LayoutState {
+    pub layout: Arc<Mutex<WindowLayout>>,
+    pub data_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl LayoutState {
+    pub fn lock(&self) -> Result<MutexGuard<'_, WindowLayout>, String> {
+        self.layout.lock().map_err(|e| format!("Failed to lock window layout: {}", e))
+    }
+
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let loaded: WindowLayout = persistence::load_json(&path);
+        *self.lock()? = loaded;
+        *self.data_path.lock().map_err(|e| format!("Failed to lock window layout path: {}", e))? = Some(path);
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = self.data_path.lock().map_err(|e| format!("Failed to lock window layout path: {}", e))?.clone();
+        match path {
+            Some(path) => persistence::save_json(&path, &*self.lock()?),
+            None => Ok(()),
+        }
+    }
+}
+
+fn geometry(window: &WebviewWindow) -> Option<WindowGeometry> {
+    let position = window.outer_position().ok()?;
+    let size = window.inner_size().ok()?;
+    Some(WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+// A saved position is only used while some monitor still contains it
+fn on_a_monitor(window: &WebviewWindow, geometry: &WindowGeometry) -> bool {
+    window.available_monitors().unwrap_or_default().iter().any(|monitor| {
+        let (position, size) = (monitor.position(), monitor.size());
+        (position.x..position.x + size.width as i32).contains(&geometry.x)
+            && (position.y..position.y + size.height as i32).contains(&geometry.y)
+    })
+}
+
+pub fn restore_geometry(window: &WebviewWindow, geometry: Option<WindowGeometry>) {
+    let Some(geometry) = geometry.filter(|geometry| on_a_monitor(window, geometry)) else {
+        return;
+    };
+    let _ = window.set_size(PhysicalSize::new(geometry.width, geometry.height));
+    let _ = window.set_position(PhysicalPosition::new(geometry.x, geometry.y));
+}
+
+fn place(window: &WebviewWindow, placement: &Placement) -> Result<(), String> {
+    let monitors = window.available_monitors().map_err(|e| format!("Failed to list displays: {}", e))?;
+    let monitor = monitors
+        .iter()
+        .find(|monitor| placement.display_name.is_some() && monitor.name() == placement.display_name.as_ref())
+        .or_else(|| monitors.get(placement.display))
+        .ok_or_else(|| t_args("error-display-not-found", &[("index", placement.display.into())]))?;
+    let area = monitor.work_area();
+    let margin = (CORNER_MARGIN * monitor.scale_factor()) as i32;
+    // A maximized window can't be moved
+    let _ = window.unmaximize();
+    let size = window.outer_size().map_err(|e| format!("Failed to read window size: {}", e))?;
+    let (width, height) = (size.width.min(area.size.width) as i32, size.height.min(area.size.height) as i32);
+    let (left, top) = (area.position.x, area.position.y);
+    let (right, bottom) = (left + area.size.width as i32 - width, top + area.size.height as i32 - height);
+    let (x, y) = match placement.corner {
+        Corner::TopLeft => (left + margin, top + margin),
+        Corner::TopRight => (right - margin, top + margin),
+        Corner::BottomLeft => (left + margin, bottom - margin),
+        Corner::BottomRight => (right - margin, bottom - margin),
+        Corner::Center => ((left + right) / 2, (top + bottom) / 2),
+    };
+    window
+        .set_position(PhysicalPosition::new(x, y))
+        .map_err(|e| format!("Failed to move window: {}", e))
+}
+
+// Puts a window back where it was pinned, if it was
+pub fn apply_placement(app: &AppHandle, window: &WebviewWindow) {
+    let placement = match app.state::<LayoutState>().lock() {
+        Ok(layout) => layout.placements.get(window.label()).cloned(),
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+    if let Some(placement) = placement {
+        if let Err(e) = place(window, &placement) {
+            eprintln!("Failed to place window {}: {}", window.label(), e);
+        }
+    }
+}
+
+fn update_timer_window(app: &AppHandle, id: u64, update: impl FnOnce(&mut TimerWindow)) {
+    let state = app.state::<LayoutState>();
+    match state.lock() {
+        Ok(mut layout) => update(layout.timers.entry(id).or_default()),
+        Err(e) => eprintln!("{}", e),
+    };
+}
+
+fn save(app: &AppHandle) {
+    if let Err(e) = app.state::<LayoutState>().save() {
+        eprintln!("Failed to save window layout: {}", e);
+    }
+}
+
+// A small always-on-top window showing one timer; the frontend renders it from the
+// "timer" window kind and the id in the URL. Moving it around is remembered per timer.
+fn show_timer_window(app: &AppHandle, id: u64) -> Result<(), String> {
+    let label = format!("{}{}", TIMER_WINDOW_PREFIX, id);
+    if let Some(window) = app.get_webview_window(&label) {
+        let _ = window.show();
+        let _ = window.set_focus();
+        return Ok(());
+    }
+    let title = {
+        let state = app.state::<TimerState>();
+        let store = state.lock()?;
+        store.get(id).ok_or_else(|| t_args("error-timer-not-found", &[("id", id.into())]))?.display_title()
+    };
+    let window = WebviewWindowBuilder::new(app, &label, WebviewUrl::App(format!("index.html?window=timer&id={}", id).into()))
+        .title(title)
+        .inner_size(TIMER_WINDOW_WIDTH, TIMER_WINDOW_HEIGHT)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .build()
+        .map_err(|e| format!("Failed to open timer window: {}", e))?;
+    let saved = app.state::<LayoutState>().lock()?.timers.get(&id).and_then(|entry| entry.geometry);
+    restore_geometry(&window, saved);
+    apply_placement(app, &window);
+    opacity::restore(app, &window);
+
+    let app_handle = app.clone();
+    let window_clone = window.clone();
+    window.on_window_event(move |event| match event {
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) => {
+            let geometry = geometry(&window_clone);
+            update_timer_window(&app_handle, id, |entry| entry.geometry = geometry.or(entry.geometry));
+        }
+        // Written when the user is done with the window rather than on every pixel of a drag
+        WindowEvent::Focused(false) => save(&app_handle),
+        // Quitting the app doesn't close windows this way, so they stay marked open
+        WindowEvent::CloseRequested { .. } => {
+            update_timer_window(&app_handle, id, |entry| entry.open = false);
+            save(&app_handle);
+        }
+        _ => {}
+    });
+    update_timer_window(app, id, |entry| entry.open = true);
+    save(app);
+    Ok(())
+}
+
+// Brings back the timer windows that were open when the app last quit
+pub fn reopen_timer_windows(app: &AppHandle) {
+    let open: Vec<u64> = match app.state::<LayoutState>().lock() {
+        Ok(layout) => layout.timers.iter().filter(|(_, entry)| entry.open).map(|(id, _)| *id).collect(),
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+    for id in open {
+        if let Err(e) = show_timer_window(app, id) {
+            eprintln!("Failed to reopen window for timer {}: {}", id, e);
+            update_timer_window(app, id, |entry| entry.open = false);
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn open_timer_window(app: AppHandle, id: u64) -> Result<(), String> {
+    show_timer_window(&app, id)
+}
+
+#[tauri::command]
+pub async fn close_timer_window(app: AppHandle, state: State<'_, LayoutState>, id: u64) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(&format!("{}{}", TIMER_WINDOW_PREFIX, id)) {
+        window.destroy().map_err(|e| format!("Failed to close timer window: {}", e))?;
+    }
+    if let Some(entry) = state.lock()?.timers.get_mut(&id) {
+        entry.open = false;
+    }
+    state.save()
+}
+
+#[tauri::command]
+pub async fn list_displays(app: AppHandle) -> Result<Vec<DisplayInfo>, String> {
+    let monitors = app.available_monitors().map_err(|e| format!("Failed to list displays: {}", e))?;
+    let primary = app.primary_monitor().ok().flatten();
+    Ok(monitors
+        .iter()
+        .enumerate()
+        .map(|(index, monitor)| DisplayInfo {
+            index,
+            name: monitor.name().cloned(),
+            width: monitor.size().width,
+            height: monitor.size().height,
+            primary: primary.as_ref().is_some_and(|primary| primary.name() == monitor.name() && primary.position() == monitor.position()),
+        })
+        .collect())
+}
+
+// Moves a window (the main one unless a label is given) to a corner of a display and keeps
+// it there on later launches
+#[tauri::command]
+pub async fn move_to_display(
+    app: AppHandle,
+    state: State<'_, LayoutState>,
+    index: usize,
+    corner: Corner,
+    window: Option<String>,
+) -> Result<(), String> {
+    let label = window.unwrap_or_else(|| "main".to_string());
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| format!("Window {} not found", label))?;
+    let display_name = window
+        .available_monitors()
+        .map_err(|e| format!("Failed to list displays: {}", e))?
+        .get(index)
+        .ok_or_else(|| t_args("error-display-not-found", &[("index", index.into())]))?
+        .name()
+        .cloned();
+    let placement = Placement {
+        display: index,
+        display_name,
+        corner,
+    };
+    place(&window, &placement)?;
+    state.lock()?.placements.insert(label, placement);
+    state.save()
+}
+
+// Lets the window move freely again on later launches
+#[tauri::command]
+pub async fn clear_window_placement(state: State<'_, LayoutState>, window: Option<String>) -> Result<(), String> {
+    state.lock()?.placements.remove(window.as_deref().unwrap_or("main"));
+    state.save()
+}
@@ -2,19 +2,128 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, State, Manager};
-use tokio::time::{interval, Duration};
+use tauri::{AppHandle, Emitter, State, Manager};
+use tokio::time::Duration;
 use tauri_plugin_notification::NotificationExt;
-use tauri::tray::{TrayIconBuilder, TrayIconEvent};
+use tauri_plugin_deep_link::DeepLinkExt;
 use auto_launch::AutoLaunchBuilder;
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Local, Utc};
+use hourglass_core::countdown::time_components;
+
+mod a11y;
+mod activity;
+mod actions;
+mod api_tokens;
+mod appearance;
+mod audio;
+mod audit;
+mod cli;
+mod clipboard;
+mod clock;
+#[cfg(all(unix, not(target_os = "macos")))]
+mod dbus;
+mod deadline_file;
+mod focus;
+mod edit_lock;
+mod embed;
+mod events;
+mod format;
+mod health;
+mod heartbeat;
+mod hosts;
+mod http;
+mod i18n;
+mod idle;
+mod import;
+mod integrations;
+mod kiosk;
+mod layout;
+mod message;
+mod milestones;
+mod notifications;
+mod notifier;
+mod ntp;
+mod opacity;
+mod pace;
+mod passcode;
+mod persistence;
+mod phrase;
+mod plugins;
+mod power;
+mod remote;
+mod schedule;
+mod scheduler;
+mod scripting;
+mod server;
+mod settings;
+mod share;
+mod shutdown;
+mod snapshot;
+mod stats;
+mod support;
+mod tamper;
+mod tick;
+mod timers;
+mod todoist;
+mod tray;
+mod updates;
+mod usage;
+mod viewer;
+mod wallpaper;
+
+use actions::ActionState;
+use activity::ActivityState;
+use api_tokens::ApiTokenState;
+use appearance::AppearanceState;
+use edit_lock::EditLockState;
+use audio::AudioState;
+use audit::AuditState;
+use focus::FocusState;
+use embed::EmbedState;
+use import::ImportState;
+use heartbeat::HeartbeatState;
+use integrations::IntegrationState;
+use kiosk::KioskState;
+use layout::LayoutState;
+use notifications::DeliveryState;
+use notifier::{EventKind, Notification};
+use ntp::DriftState;
+use i18n::t;
+use plugins::PluginState;
+use power::PowerState;
+use schedule::ReminderSchedule;
+use scheduler::{EventQueue, EventSource, SchedulerMessage, SchedulerState};
+use scripting::{ScriptEvent, ScriptState};
+use settings::SettingsState;
+use stats::StatsState;
+use tamper::{TamperKind, TamperState};
+use tick::TickState;
+use timers::TimerState;
+use tray::TrayState;
+use updates::ChangelogState;
+use usage::{UsageEvent, UsageState};
+use viewer::ViewerState;
 
 struct NotificationState {
     is_enabled: Arc<Mutex<bool>>,
     handle: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
-    start_date: Arc<Mutex<Option<String>>>,
-    end_date: Arc<Mutex<Option<String>>>,
+    start_date: Arc<Mutex<Option<DateTime<Utc>>>>,
+    end_date: Arc<Mutex<Option<DateTime<Utc>>>>,
+    // Routine reminders are held like for an idle user until this passes
+    snoozed_until: Arc<Mutex<Option<DateTime<Utc>>>>,
+    // How often the supervisor had to bring the reminder loop back, and why it last did
+    restarts: Arc<Mutex<LoopRestarts>>,
+    // Set when the next loop should pick up from the saved heartbeat: after launch or a crash,
+    // but not when the user turns reminders back on after muting them
+    resume_from_heartbeat: Arc<Mutex<bool>>,
+}
+
+#[derive(Serialize, Clone, Default)]
+struct LoopRestarts {
+    count: u32,
+    last_reason: Option<String>,
+    last_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -34,17 +143,133 @@ impl Default for NotificationState {
             handle: Arc::new(Mutex::new(None)),
             start_date: Arc::new(Mutex::new(None)),
             end_date: Arc::new(Mutex::new(None)),
+            snoozed_until: Arc::new(Mutex::new(None)),
+            restarts: Arc::new(Mutex::new(LoopRestarts::default())),
+            resume_from_heartbeat: Arc::new(Mutex::new(true)),
         }
     }
 }
 
-fn calculate_time_components(time_remaining_ms: i64) -> (i64, i64, i64, i64) {
-    let total_seconds = time_remaining_ms / 1000;
-    let days = total_seconds / (24 * 60 * 60);
-    let hours = (total_seconds % (24 * 60 * 60)) / (60 * 60);
-    let minutes = (total_seconds % (60 * 60)) / 60;
-    let seconds = total_seconds % 60;
-    (days, hours, minutes, seconds)
+impl NotificationState {
+    fn record_restart(&self, reason: String) {
+        let mut restarts = match self.restarts.lock() {
+            Ok(restarts) => restarts,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        restarts.count += 1;
+        restarts.last_reason = Some(reason);
+        restarts.last_at = Some(Utc::now());
+    }
+}
+
+// While reminders are held for an idle user, check this often whether they're back
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+// Timers don't advance while the machine is suspended and the wall clock can be changed under
+// us, so even with nothing scheduled the task re-checks this often
+const MAX_SCHEDULER_SLEEP: Duration = Duration::from_secs(5 * 60);
+
+// A gap between passes of the reminder loop longer than this gets a catch-up summary
+const CATCH_UP_GAP: ChronoDuration = ChronoDuration::minutes(10);
+
+// Bounds of the supervisor's back-off between restarts of a failing reminder loop
+const MIN_RESTART_DELAY: Duration = Duration::from_secs(1);
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+
+fn primary_end_date(state: &NotificationState) -> Option<DateTime<Utc>> {
+    *state.end_date.lock().ok()?
+}
+
+fn primary_start_date(state: &NotificationState) -> Option<DateTime<Utc>> {
+    *state.start_date.lock().ok()?
+}
+
+fn primary_reminder_body(start_date: Option<DateTime<Utc>>, end_date: Option<DateTime<Utc>>, now: DateTime<Utc>) -> String {
+    match end_date {
+        Some(end) => message::compose_reminder(start_date, end, now),
+        None => t("reminder-no-dates"),
+    }
+}
+
+// Sends the primary countdown reminder right away when the user opted into it, as instant
+// confirmation that reminders are running
+fn send_immediate_reminder(app: &AppHandle) -> Result<(), String> {
+    let enabled = app.state::<SettingsState>().lock()?.remind_immediately;
+    if !enabled {
+        return Ok(());
+    }
+    let state = app.state::<NotificationState>();
+    let start_date = *state.start_date.lock().map_err(|e| format!("Failed to lock start date: {}", e))?;
+    let end_date = *state.end_date.lock().map_err(|e| format!("Failed to lock end date: {}", e))?;
+    send_notification(app, EventKind::Reminder, &t("reminder-title"), &primary_reminder_body(start_date, end_date, clock::now()));
+    Ok(())
+}
+
+// The main window is the one from tauri.conf.json; Tauri labels it "main" when none is given
+fn main_window(app: &AppHandle) -> Option<tauri::WebviewWindow> {
+    app.get_webview_window("main")
+        .or_else(|| app.webview_windows().values().next().cloned())
+}
+
+// True when the countdown is already on screen, so a periodic reminder would only repeat it
+fn main_window_in_view(app: &AppHandle) -> bool {
+    main_window(app).is_some_and(|window| {
+        window.is_visible().unwrap_or(false) && window.is_focused().unwrap_or(false)
+    })
+}
+
+// Sends through the channels routed for the notification's kind, counting it once the OS
+// showed it
+fn deliver_notification(app: &AppHandle, notification: &Notification) {
+    if let Err(e) = notifier::dispatch(app, notification) {
+        eprintln!("Failed to send notification, showed it in the app instead: {}", e);
+        return;
+    }
+    events::notification_sent(app, notification);
+    if let Err(e) = app.state::<StatsState>().record_notification() {
+        eprintln!("Failed to record notification: {}", e);
+    }
+}
+
+fn send_notification(app: &AppHandle, kind: EventKind, title: &str, body: &str) {
+    deliver_notification(app, &Notification::new(kind, title, body));
+}
+
+fn send_timer_notification(app: &AppHandle, timer: &timers::Timer, body: &str) {
+    usage::record(app, UsageEvent::Reminder, 1);
+    deliver_notification(app, &Notification::for_timer(EventKind::Reminder, timer, body));
+}
+
+// Everything that came due during a gap in the reminder loop, as one notification. Expired
+// timers still carry the end they expired at; a timer due for several reminders is listed once.
+fn send_downtime_catch_up(
+    app: &AppHandle,
+    gap_started: DateTime<Utc>,
+    now: DateTime<Utc>,
+    primary_end: Option<DateTime<Utc>>,
+    timers: &[timers::Timer],
+    milestones: &[(timers::Timer, timers::Milestone, bool)],
+) {
+    let mut items: Vec<(String, DateTime<Utc>)> = primary_end.map(|end| (t("primary-countdown-label"), end)).into_iter().collect();
+    let mut listed = Vec::new();
+    for timer in timers {
+        if listed.contains(&timer.id) {
+            continue;
+        }
+        listed.push(timer.id);
+        items.push((timer.display_title(), timer.end_date));
+    }
+    let reached: Vec<(String, String)> = milestones
+        .iter()
+        .filter(|(_, _, is_due)| *is_due)
+        .map(|(timer, milestone, _)| (timer.display_title(), milestone.name.clone()))
+        .collect();
+    if items.is_empty() && reached.is_empty() {
+        return;
+    }
+    let body = message::compose_downtime_catch_up((now - gap_started).num_milliseconds(), &items, &reached, now);
+    usage::record(app, UsageEvent::Reminder, 1);
+    send_notification(app, EventKind::CatchUp, &t("catch-up-downtime-title"), &body);
 }
 
 #[tauri::command]
@@ -55,38 +280,138 @@ async fn get_notification_status(state: State<'_, NotificationState>) -> Result<
 
 #[tauri::command]
 async fn set_timer_dates(
+    app: AppHandle,
     state: State<'_, NotificationState>,
+    timer_state: State<'_, TimerState>,
+    scheduler_state: State<'_, SchedulerState>,
     start_date: String,
-    end_date: String
+    end_date: String,
+    passcode: Option<String>,
 ) -> Result<(), String> {
-    // Validate date formats before storing
-    chrono::DateTime::parse_from_rfc3339(&start_date)
-        .map_err(|e| format!("Invalid start date format: {}", e))?;
-    chrono::DateTime::parse_from_rfc3339(&end_date)
-        .map_err(|e| format!("Invalid end date format: {}", e))?;
-    
-    {
-        let mut start = state.start_date.lock().map_err(|e| format!("Failed to lock start date: {}", e))?;
-        *start = Some(start_date);
+    // Parsed once here; everything else works with the parsed dates
+    let start_date = timers::parse_date(&start_date, "field-start-date")?;
+    let end_date = timers::parse_date(&end_date, "field-end-date")?;
+    let edit_lock = app.state::<EditLockState>();
+    let pinned = edit_lock.lock()?.pinned_dates;
+    edit_lock.check_dates(start_date, end_date, passcode.as_deref())?;
+    edit_lock.pin_dates(start_date, end_date)?;
+    // Even with the passcode, moving a locked deadline goes on the record
+    if let Some((_, pinned_end)) = pinned.filter(|(_, pinned_end)| *pinned_end != end_date) {
+        tamper::record(&app, TamperKind::EndDateChanged { from: pinned_end, to: end_date });
     }
-    {
+
+    let dates_changed = replace_primary_dates(&app, &state, &timer_state, &scheduler_state, start_date, end_date)?;
+    let notifications_enabled = *state.is_enabled.lock().map_err(|e| format!("Failed to lock notification state: {}", e))?;
+    if dates_changed && notifications_enabled {
+        send_immediate_reminder(&app)?;
+    }
+    Ok(())
+}
+
+// Swaps in the main countdown's new dates, archiving the old one if it had already finished.
+// Returns whether the end date moved.
+fn replace_primary_dates(
+    app: &AppHandle,
+    state: &NotificationState,
+    timer_state: &TimerState,
+    scheduler_state: &SchedulerState,
+    start_date: DateTime<Utc>,
+    end_date: DateTime<Utc>,
+) -> Result<bool, String> {
+    let previous_start = {
+        let mut start = state.start_date.lock().map_err(|e| format!("Failed to lock start date: {}", e))?;
+        start.replace(start_date)
+    };
+    let previous_end = {
         let mut end = state.end_date.lock().map_err(|e| format!("Failed to lock end date: {}", e))?;
-        *end = Some(end_date);
+        end.replace(end_date)
+    };
+
+    let dates_changed = previous_end != Some(end_date);
+
+    // Keep a finished countdown in the archive instead of silently overwriting it
+    if let (Some(prev_start), Some(prev_end)) = (previous_start, previous_end) {
+        if prev_end != end_date && prev_end <= clock::now() {
+            let mut store = timer_state.store.lock().map_err(|e| format!("Failed to lock timer store: {}", e))?;
+            let timer = store.add(t("previous-countdown-label"), prev_start, prev_end);
+            timer.status = timers::TimerStatus::Archived;
+            timer.archived_at = Some(clock::now());
+            let archived = timer.clone();
+            store.save()?;
+            store.emit(events::TIMER_CREATED, &archived);
+        }
     }
+
+    tray::refresh_labels(app);
+    // The new end date may now be the next thing the scheduler has to wake up for
+    scheduler_state.sender.send(SchedulerMessage::Replan(EventSource::Primary));
+    Ok(dates_changed)
+}
+
+// With auto-archive on, finished timers go to the archive, and once the main countdown runs
+// out the next upcoming timer takes its place, so the tray never sits on a dead countdown.
+// A locked countdown is left alone.
+fn archive_expired(app: &AppHandle, expired: &[timers::Timer], primary_expired: bool) -> Result<(), String> {
+    let timer_state = app.state::<TimerState>();
+    let promoted = {
+        let mut store = timer_state.lock()?;
+        // Recurring timers and those following a calendar period have already moved on
+        for timer in expired.iter().filter(|timer| !timer.repeats()) {
+            store.update(timer.id, |timer| {
+                timer.status = timers::TimerStatus::Archived;
+                timer.archived_at = Some(clock::now());
+                Ok(())
+            })?;
+        }
+        let locked = app.state::<EditLockState>().lock()?.passcode.is_some();
+        if !primary_expired || locked {
+            return Ok(());
+        }
+        let now = clock::now();
+        let next = store
+            .timers
+            .iter()
+            .filter(|timer| timer.is_active() && !timer.repeats() && timer.end_date > now)
+            .min_by_key(|timer| timer.end_date)
+            .cloned();
+        // The promoted timer becomes the main countdown rather than running twice. It's archived,
+        // not removed, so its tags, notes and history stay with it.
+        match next {
+            Some(next) => Some(store.update(next.id, |timer| {
+                timer.status = timers::TimerStatus::Archived;
+                timer.archived_at = Some(now);
+                timer.promoted_to_primary = true;
+                Ok(())
+            })?),
+            None => None,
+        }
+    };
+    let Some(next) = promoted else {
+        return Ok(());
+    };
+    replace_primary_dates(
+        app,
+        &app.state::<NotificationState>(),
+        &timer_state,
+        &app.state::<SchedulerState>(),
+        next.start_date,
+        next.end_date,
+    )?;
+    events::timer(app, events::TIMER_PROMOTED, &next);
     Ok(())
 }
 
 #[tauri::command]
 async fn get_time_remaining(state: State<'_, NotificationState>) -> Result<TimeRemaining, String> {
-    let start_date = state.start_date.lock().map_err(|e| format!("Failed to lock start date: {}", e))?.clone();
-    let end_date = state.end_date.lock().map_err(|e| format!("Failed to lock end date: {}", e))?.clone();
+    compute_time_remaining(&state)
+}
+
+fn compute_time_remaining(state: &NotificationState) -> Result<TimeRemaining, String> {
+    let start_date = *state.start_date.lock().map_err(|e| format!("Failed to lock start date: {}", e))?;
+    let end_date = *state.end_date.lock().map_err(|e| format!("Failed to lock end date: {}", e))?;
     
-    if let (Some(_), Some(end)) = (start_date, end_date) {
-        let now = chrono::Utc::now();
-        let end_time = chrono::DateTime::parse_from_rfc3339(&end)
-            .map_err(|e| format!("Invalid end date: {}", e))?;
-        
-        let time_remaining = (end_time.with_timezone(&Utc) - now).num_milliseconds();
+    if let (Some(_), Some(end_time)) = (start_date, end_date) {
+        let time_remaining = (end_time - clock::now()).num_milliseconds();
         
         if time_remaining <= 0 {
             return Ok(TimeRemaining {
@@ -99,7 +424,7 @@ async fn get_time_remaining(state: State<'_, NotificationState>) -> Result<TimeR
             });
         }
         
-        let (days, hours, minutes, seconds) = calculate_time_components(time_remaining);
+        let (days, hours, minutes, seconds) = time_components(format::round_remaining(time_remaining, 1000));
         
         Ok(TimeRemaining {
             days,
@@ -110,126 +435,428 @@ async fn get_time_remaining(state: State<'_, NotificationState>) -> Result<TimeR
             is_expired: false,
         })
     } else {
-        Err("Timer dates not set".to_string())
+        Err(t("error-timer-dates-not-set"))
     }
 }
 
-#[tauri::command]
-async fn start_notifications(
-    app: AppHandle,
-    state: State<'_, NotificationState>,
-) -> Result<(), String> {
-    // Check and set enabled status atomically to prevent race conditions
-    {
-        let mut is_enabled = state.is_enabled.lock().map_err(|e| format!("Failed to lock notification state: {}", e))?;
-        if *is_enabled {
-            return Ok(()); // Already enabled
+// The reminder loop itself. It returns when notifications are turned off or the scheduler
+// channel closes; anything else ending it is a failure the supervisor restarts from.
+async fn run_notification_loop(app: AppHandle) {
+    let notification_state = app.state::<NotificationState>();
+    let app_clone = app.clone();
+    let is_enabled_clone = notification_state.is_enabled.clone();
+    let start_date_clone = notification_state.start_date.clone();
+    let end_date_clone = notification_state.end_date.clone();
+    let snoozed_until_clone = notification_state.snoozed_until.clone();
+    let timer_store_clone = app.state::<TimerState>().store.clone();
+    let settings_clone = app.state::<SettingsState>().settings.clone();
+    
+    let receiver = app.state::<SchedulerState>().receiver.clone();
+    
+    let mut receiver = receiver.lock().await;
+    let primary_schedule = ReminderSchedule::default();
+    let mut next_primary_reminder = primary_schedule.next_after(clock::now());
+    // After launch or a crash, carry on from the last pass so whatever came due while the app
+    // was down is caught on the first pass
+    let heartbeat = app.state::<HeartbeatState>();
+    let resuming = notification_state.resume_from_heartbeat.lock().map(|mut resume| std::mem::take(&mut *resume)).unwrap_or(false);
+    let mut last_check = heartbeat
+        .last_tick()
+        .filter(|last| resuming && *last < clock::now())
+        .unwrap_or_else(clock::now);
+    // One queue for every timer and the primary countdown, holding each one's next event.
+    // Commands push changes in over the channel instead of the task rescanning everything.
+    let mut queue = EventQueue::default();
+    let mut replan_all = true;
+    // Routine reminders held back while the user was away, delivered as one summary later
+    let mut deferred: Vec<EventSource> = Vec::new();
+    
+    loop {
+        if std::mem::take(&mut replan_all) {
+            queue.clear();
+            match timer_store_clone.lock() {
+                Ok(store) => {
+                    for timer in &store.timers {
+                        queue.plan(EventSource::Timer(timer.id), timer.next_event_at());
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Failed to lock timer store in notification task: {}", e);
+                    break;
+                }
+            }
         }
-        *is_enabled = true;
-    }
+        // The primary countdown's next event depends on this task's own state, so it is
+        // refreshed on every pass rather than through the channel
+        let primary_end = match end_date_clone.lock() {
+            Ok(end) => end.filter(|end| *end > last_check),
+            Err(_) => None,
+        };
+        queue.plan(EventSource::Primary, [next_primary_reminder, primary_end].into_iter().flatten().min());
 
-    // Stop any existing notification task
-    {
-        let mut handle = state.handle.lock().map_err(|e| format!("Failed to lock task handle: {}", e))?;
-        if let Some(task) = handle.take() {
-            task.abort();
+        // Sleep until the earliest event instead of polling
+        let mut delay = queue
+            .next_at()
+            .map(|next| (next - clock::now()).to_std().unwrap_or(Duration::ZERO))
+            .unwrap_or(MAX_SCHEDULER_SLEEP)
+            .min(MAX_SCHEDULER_SLEEP);
+        if !deferred.is_empty() {
+            delay = delay.min(IDLE_POLL_INTERVAL);
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            message = receiver.recv() => {
+                match message {
+                    Some(SchedulerMessage::Replan(EventSource::Timer(id))) => match timer_store_clone.lock() {
+                        Ok(store) => queue.plan(EventSource::Timer(id), store.get(id).and_then(|t| t.next_event_at())),
+                        Err(_) => replan_all = true,
+                    },
+                    Some(SchedulerMessage::Replan(EventSource::Primary)) => {} // Re-planned at the top of the loop
+                    Some(SchedulerMessage::ReplanAll) => replan_all = true,
+                    None => break,
+                }
+                continue;
+            }
         }
-    }
-
-    // Start new notification task
-    let app_clone = app.clone();
-    let is_enabled_clone = state.is_enabled.clone();
-    let start_date_clone = state.start_date.clone();
-    let end_date_clone = state.end_date.clone();
-    
-    let task = tokio::spawn(async move {
-        let mut interval = interval(Duration::from_secs(6 * 60 * 60)); // 6 hours
         
-        loop {
-            interval.tick().await;
-            
-            // Check if notifications are still enabled
-            {
-                match is_enabled_clone.lock() {
-                    Ok(enabled_guard) => {
-                        if !*enabled_guard {
-                            break;
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to lock notification state in task: {}", e);
-                        break; // Exit on lock failure
+        // Check if notifications are still enabled
+        {
+            match is_enabled_clone.lock() {
+                Ok(enabled_guard) => {
+                    if !*enabled_guard {
+                        break;
                     }
                 }
+                Err(e) => {
+                    eprintln!("Failed to lock notification state in task: {}", e);
+                    break; // Exit on lock failure
+                }
             }
-            
-            // Get time remaining for notification
-            let notification_body = {
-                let start_date = match start_date_clone.lock() {
-                    Ok(guard) => guard.clone(),
-                    Err(_) => {
-                        eprintln!("Failed to lock start date in notification task");
-                        continue;
+        }
+
+        let now = clock::now();
+        let previous_check = std::mem::replace(&mut last_check, now);
+        if let Err(e) = heartbeat.beat(now) {
+            eprintln!("Failed to save reminder heartbeat: {}", e);
+        }
+        // A pass never comes later than MAX_SCHEDULER_SLEEP on its own, so a longer gap means
+        // the app wasn't running or the machine was asleep
+        let catching_up = now - previous_check > CATCH_UP_GAP;
+        let due_sources = queue.pop_due(now);
+        // Waking with nothing due means the safety timeout ran out; the clock may have jumped,
+        // so rebuild the whole plan after this pass
+        if due_sources.is_empty() {
+            replan_all = true;
+        }
+        let settings = match settings_clone.lock() {
+            Ok(settings) => settings.clone(),
+            Err(e) => {
+                eprintln!("Failed to lock settings in notification task: {}", e);
+                // Fail quiet: keep reminders going but don't ring or act on anything
+                settings::Settings {
+                    suppress_when_visible: false,
+                    expiry_alarm: false,
+                    ..Default::default()
+                }
+            }
+        };
+        let skip_periodic = settings.suppress_when_visible && main_window_in_view(&app_clone);
+        let snoozed = snoozed_until_clone.lock().ok().and_then(|until| *until).is_some_and(|until| until > now);
+        let away = snoozed
+            || (settings.defer_when_idle
+                && idle::is_away(Duration::from_secs(u64::from(settings.idle_threshold_minutes) * 60)));
+
+        // Explicit reminders and each timer's own schedule
+        let (expired, due_reminders, due_scheduled, due_milestones) = match timer_store_clone.lock() {
+            Ok(mut store) => {
+                let due_milestones = store.take_due_milestones(now);
+                let expired = store.take_newly_expired(now);
+                let due_reminders = store.take_due_reminders(now);
+                let due_scheduled = store.take_due_scheduled(now, &settings.reminder_days);
+                if !expired.is_empty() || !due_reminders.is_empty() || !due_scheduled.is_empty() || !due_milestones.is_empty() {
+                    if let Err(e) = store.save() {
+                        eprintln!("Failed to persist reminder state: {}", e);
                     }
-                };
-                let end_date = match end_date_clone.lock() {
-                    Ok(guard) => guard.clone(),
-                    Err(_) => {
-                        eprintln!("Failed to lock end date in notification task");
-                        continue;
+                }
+                for source in &due_sources {
+                    if let EventSource::Timer(id) = *source {
+                        queue.plan(*source, store.get(id).and_then(|t| t.next_event_at()));
                     }
+                }
+                (expired, due_reminders, due_scheduled, due_milestones)
+            }
+            Err(e) => {
+                eprintln!("Failed to lock timer store in notification task: {}", e);
+                (Vec::new(), Vec::new(), Vec::new(), Vec::new())
+            }
+        };
+
+        let audio = app_clone.state::<AudioState>();
+
+        // Back at the keyboard: one summary instead of every reminder that was held back
+        if !away && !deferred.is_empty() {
+            let mut items = Vec::new();
+            for source in deferred.drain(..) {
+                let item = match source {
+                    EventSource::Primary => end_date_clone
+                        .lock()
+                        .ok()
+                        .and_then(|end| *end)
+                        .map(|end| (t("primary-countdown-label"), end)),
+                    EventSource::Timer(id) => timer_store_clone
+                        .lock()
+                        .ok()
+                        .and_then(|store| store.get(id).filter(|t| t.is_active()).map(|t| (t.display_title(), t.end_date))),
                 };
-                
-                if let (Some(_), Some(end)) = (start_date, end_date) {
-                    if let Ok(end_time) = DateTime::parse_from_rfc3339(&end) {
-                        let now = Utc::now();
-                        let time_remaining = (end_time.with_timezone(&Utc) - now).num_milliseconds();
-                        
-                        if time_remaining <= 0 {
-                            "⏰ Time's up! Your hourglass has run out of sand.".to_string()
-                        } else {
-                            let (days, hours, minutes, _) = calculate_time_components(time_remaining);
-                            
-                            if days > 0 {
-                                format!("⏳ Time remaining: {} days, {} hours, {} minutes", days, hours, minutes)
-                            } else if hours > 0 {
-                                format!("⏳ Time remaining: {} hours, {} minutes", hours, minutes)
-                            } else {
-                                format!("⏳ Time remaining: {} minutes", minutes)
-                            }
-                        }
-                    } else {
-                        "⏳ Time keeps flowing... Check your hourglass progress!".to_string()
-                    }
-                } else {
-                    "⏳ Time keeps flowing... Set your dates to see time remaining!".to_string()
+                items.extend(item);
+            }
+            if !items.is_empty() {
+                usage::record(&app_clone, UsageEvent::Reminder, 1);
+                send_notification(&app_clone, EventKind::CatchUp, &t("catch-up-title"), &message::compose_catch_up(&items, now));
+            }
+        }
+
+        // Expiry alerts always go out, whatever the reminder filters say
+        let primary_expired = match end_date_clone.lock() {
+            Ok(end) => end.is_some_and(|end| previous_check < end && end <= now),
+            Err(_) => false,
+        };
+        // Back from a gap: one summary of what happened meanwhile instead of a burst of stale
+        // reminders, and no alarm or expiry action for something long over
+        if catching_up {
+            usage::record(&app_clone, UsageEvent::Completed, u32::from(primary_expired) + expired.len() as u32);
+            for timer in &expired {
+                events::timer(&app_clone, events::TIMER_EXPIRED, timer);
+                scripting::dispatch(&app_clone, ScriptEvent::Expiry, serde_json::json!({ "timer": timer }));
+            }
+            for (timer, milestone, _) in due_milestones.iter().filter(|(_, _, is_due)| *is_due) {
+                scripting::dispatch(&app_clone, ScriptEvent::Milestone, serde_json::json!({ "timer": timer, "milestone": milestone }));
+            }
+            if settings.auto_archive_expired && (primary_expired || !expired.is_empty()) {
+                if let Err(e) = archive_expired(&app_clone, &expired, primary_expired) {
+                    eprintln!("Failed to archive expired countdowns: {}", e);
+                }
+            }
+            let primary_end = end_date_clone.lock().ok().and_then(|end| *end).filter(|_| primary_expired);
+            let came_due: Vec<timers::Timer> = expired.iter().chain(&due_reminders).chain(&due_scheduled).cloned().collect();
+            send_downtime_catch_up(&app_clone, previous_check, now, primary_end, &came_due, &due_milestones);
+            next_primary_reminder = primary_schedule.next_after(now);
+            continue;
+        }
+        if primary_expired {
+            send_notification(&app_clone, EventKind::Expiry, &t("reminder-title"), &t("reminder-expired"));
+        }
+        usage::record(&app_clone, UsageEvent::Completed, u32::from(primary_expired) + expired.len() as u32);
+        for timer in &expired {
+            let mut body = match &timer.goal {
+                Some(goal) => message::compose_goal_result(goal),
+                None => message::compose_timer_reminder(timer, now),
+            };
+            if timer.include_note_in_expiry {
+                if let Some(note) = &timer.note {
+                    body = format!("{}\n\n{}", body, note);
+                }
+            }
+            events::timer(&app_clone, events::TIMER_EXPIRED, timer);
+            deliver_notification(&app_clone, &Notification::for_timer(EventKind::Expiry, timer, &body));
+            scripting::dispatch(&app_clone, ScriptEvent::Expiry, serde_json::json!({ "timer": timer }));
+        }
+        if settings.expiry_alarm && (primary_expired || !expired.is_empty()) {
+            let sound = expired.iter().find_map(|t| t.sound_path.as_deref());
+            if let Err(e) = audio.start_alarm(sound) {
+                eprintln!("Failed to start expiry alarm: {}", e);
+            }
+        }
+        let expiry_action = settings
+            .expiry_action
+            .filter(|_| primary_expired)
+            .map(|action| (action, t("reminder-title")))
+            .or_else(|| expired.iter().find_map(|timer| timer.expiry_action.map(|action| (action, timer.display_title()))));
+        if let Some((action, title)) = expiry_action {
+            if let Err(e) = app_clone.state::<ActionState>().schedule(&app_clone, action, &title) {
+                eprintln!("Failed to schedule expiry action: {}", e);
+            }
+        }
+        // The end of a work session is enforced, not just announced
+        if let Some(enforcement) = expired.iter().find_map(|timer| timer.focus_enforcement) {
+            focus::enforce(&app_clone, enforcement, settings.break_minutes);
+        }
+        if settings.auto_archive_expired && (primary_expired || !expired.is_empty()) {
+            if let Err(e) = archive_expired(&app_clone, &expired, primary_expired) {
+                eprintln!("Failed to archive expired countdowns: {}", e);
+            }
+        }
+
+        let compose = if settings.pace_in_reminders { message::compose_reminder_with_projection } else { message::compose_timer_reminder };
+        for timer in due_reminders {
+            let body = compose(&timer, now);
+            send_timer_notification(&app_clone, &timer, &body);
+        }
+        for (timer, milestone, is_due) in due_milestones {
+            let body = message::compose_milestone(&milestone, is_due, now);
+            if is_due {
+                scripting::dispatch(&app_clone, ScriptEvent::Milestone, serde_json::json!({ "timer": timer, "milestone": milestone }));
+            }
+            usage::record(&app_clone, UsageEvent::Reminder, 1);
+            deliver_notification(&app_clone, &Notification::for_timer(EventKind::Milestone, &timer, &body));
+        }
+        // High and critical timers get through whatever would hold routine reminders back
+        for timer in due_scheduled {
+            let held_back = timer.importance.can_be_held_back();
+            if skip_periodic && held_back {
+                continue;
+            }
+            if away && held_back {
+                if !deferred.contains(&EventSource::Timer(timer.id)) {
+                    deferred.push(EventSource::Timer(timer.id));
+                }
+                continue;
+            }
+            let body = compose(&timer, now);
+            send_timer_notification(&app_clone, &timer, &body);
+        }
+
+        if next_primary_reminder.is_none_or(|next| next > now) {
+            continue;
+        }
+        next_primary_reminder = primary_schedule.next_after(now);
+
+        // Routine reminders respect the selected days and stay quiet while the countdown is
+        // on screen; explicit and T-minus reminders above always go out
+        if skip_periodic || !settings.reminder_days.contains(&now.with_timezone(&Local).weekday()) {
+            continue;
+        }
+        if away {
+            if !deferred.contains(&EventSource::Primary) {
+                deferred.push(EventSource::Primary);
+            }
+            continue;
+        }
+        
+        // Get time remaining for notification
+        let notification_body = {
+            let start_date = match start_date_clone.lock() {
+                Ok(guard) => *guard,
+                Err(_) => {
+                    eprintln!("Failed to lock start date in notification task");
+                    continue;
+                }
+            };
+            let end_date = match end_date_clone.lock() {
+                Ok(guard) => *guard,
+                Err(_) => {
+                    eprintln!("Failed to lock end date in notification task");
+                    continue;
                 }
             };
             
-            // Send notification
-            if let Err(e) = app_clone
-                .notification()
-                .builder()
-                .title("Hourglass Reminder")
-                .body(&notification_body)
-                .show()
-            {
-                eprintln!("Failed to send notification: {}", e);
+            primary_reminder_body(start_date, end_date, now)
+        };
+        
+        // Send notification
+        usage::record(&app_clone, UsageEvent::Reminder, 1);
+        send_notification(&app_clone, EventKind::Reminder, &t("reminder-title"), &notification_body);
+    }
+}
+
+// Aborts the loop when the supervisor itself is aborted, so stopping notifications can't leave
+// an orphaned loop behind
+struct AbortOnDrop(tokio::task::JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+// Runs the reminder loop and starts it again whenever it panics, is aborted or returns while
+// notifications are still on. Restarts back off so a loop that dies straight away doesn't spin.
+fn spawn_supervised_notifications(app: AppHandle) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut delay = MIN_RESTART_DELAY;
+        loop {
+            let started = std::time::Instant::now();
+            let mut task = AbortOnDrop(tokio::spawn(run_notification_loop(app.clone())));
+            let failure = match (&mut task.0).await {
+                Ok(()) => "reminder loop stopped".to_string(),
+                Err(e) if e.is_panic() => format!("reminder loop panicked: {}", panic_message(e.into_panic())),
+                Err(_) => "reminder loop was aborted".to_string(),
+            };
+            let state = app.state::<NotificationState>();
+            let enabled = match state.is_enabled.lock() {
+                Ok(enabled) => *enabled,
+                Err(poisoned) => *poisoned.into_inner(),
+            };
+            if !enabled {
+                break;
+            }
+            eprintln!("Restarting notifications, {}", failure);
+            state.record_restart(failure);
+            if let Ok(mut resume) = state.resume_from_heartbeat.lock() {
+                *resume = true;
+            }
+            // A panic while holding a lock would otherwise make every restart fail the same way
+            clear_notification_poison(&app);
+            if started.elapsed() > MAX_RESTART_DELAY {
+                delay = MIN_RESTART_DELAY;
             }
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(MAX_RESTART_DELAY);
         }
-    });
+    })
+}
+
+fn clear_notification_poison(app: &AppHandle) {
+    let state = app.state::<NotificationState>();
+    state.is_enabled.clear_poison();
+    state.start_date.clear_poison();
+    state.end_date.clear_poison();
+    state.snoozed_until.clear_poison();
+    app.state::<TimerState>().store.clear_poison();
+    app.state::<SettingsState>().settings.clear_poison();
+}
 
-    // Store the task handle
+#[tauri::command]
+async fn start_notifications(
+    app: AppHandle,
+    state: State<'_, NotificationState>,
+) -> Result<(), String> {
+    // The handle stays locked from the check until the new task is stored, so two calls racing
+    // here can't both start a loop
     {
         let mut handle = state.handle.lock().map_err(|e| format!("Failed to lock task handle: {}", e))?;
-        *handle = Some(task);
+        let running = handle.as_ref().is_some_and(|task| !task.is_finished());
+        {
+            // Notifications are enabled by default, so "enabled" alone doesn't mean the task is running yet
+            let mut is_enabled = state.is_enabled.lock().map_err(|e| format!("Failed to lock notification state: {}", e))?;
+            if *is_enabled && running {
+                return Ok(()); // Already enabled
+            }
+            *is_enabled = true;
+        }
+        // Stop any existing notification task
+        if let Some(task) = handle.take() {
+            task.abort();
+        }
+        *handle = Some(spawn_supervised_notifications(app.clone()));
     }
 
+    events::notifications_toggled(&app, true);
+    send_immediate_reminder(&app)?;
+
     Ok(())
 }
 
 #[tauri::command]
-async fn stop_notifications(state: State<'_, NotificationState>) -> Result<(), String> {
+async fn stop_notifications(app: AppHandle, state: State<'_, NotificationState>) -> Result<(), String> {
     {
         let mut is_enabled = state.is_enabled.lock().map_err(|e| format!("Failed to lock notification state: {}", e))?;
         *is_enabled = false;
@@ -243,19 +870,61 @@ async fn stop_notifications(state: State<'_, NotificationState>) -> Result<(), S
         }
     }
 
+    // Time spent muted isn't downtime, even if the app is closed before reminders come back on
+    if let Err(e) = app.state::<HeartbeatState>().clear() {
+        eprintln!("Failed to clear reminder heartbeat: {}", e);
+    }
+
+    events::notifications_toggled(&app, false);
     Ok(())
 }
 
+// Without `remaining_seconds` this only checks that notifications get through. With it, shows
+// the reminder as it would go out with that much time left: for the timer `timer_id` (its
+// importance and sound included), a timer called `label`, or else the main countdown. Each
+// keeps its length, so the pace line matches too. Previews aren't counted in statistics.
 #[tauri::command]
-async fn send_test_notification(app: AppHandle) -> Result<(), String> {
-    app.notification()
-        .builder()
-        .title("Test Notification")
-        .body("This is a test notification from Hourglass!")
-        .show()
-        .map_err(|e| e.to_string())?;
-    
-    Ok(())
+async fn send_test_notification(
+    app: AppHandle,
+    state: State<'_, NotificationState>,
+    timer_state: State<'_, TimerState>,
+    remaining_seconds: Option<i64>,
+    label: Option<String>,
+    timer_id: Option<u64>,
+) -> Result<(), String> {
+    let Some(remaining_seconds) = remaining_seconds else {
+        app.notification()
+            .builder()
+            .title(t("test-notification-title"))
+            .body(t("test-notification-body"))
+            .show()
+            .map_err(|e| e.to_string())?;
+        return Ok(());
+    };
+
+    let now = clock::now();
+    let end = now + chrono::Duration::seconds(remaining_seconds.max(0));
+    let timer = match timer_id {
+        Some(id) => Some(timer_state.lock()?.get(id).cloned().ok_or_else(|| format!("Timer {} not found", id))?),
+        None => label.as_ref().map(|label| timers::Timer::new(0, label.clone(), now, end)),
+    };
+    match timer {
+        Some(mut timer) => {
+            let length = timer.end_date - timer.start_date;
+            timer.start_date = end - length;
+            timer.end_date = end;
+            if let Some(label) = label {
+                timer.label = label;
+            }
+            let body = message::compose_timer_reminder(&timer, now);
+            notifier::dispatch(&app, &Notification::for_timer(EventKind::Reminder, &timer, &body))
+        }
+        None => {
+            let length = primary_start_date(&state).zip(primary_end_date(&state)).map(|(start, end)| end - start);
+            let body = primary_reminder_body(length.map(|length| end - length), Some(end), now);
+            notifier::dispatch(&app, &Notification::new(EventKind::Reminder, &t("reminder-title"), &body))
+        }
+    }
 }
 
 fn create_auto_launch() -> Result<auto_launch::AutoLaunch, String> {
@@ -290,52 +959,278 @@ async fn disable_startup() -> Result<(), String> {
 }
 
 fn main() {
+    if let Some(code) = cli::run() {
+        std::process::exit(code);
+    }
+
+    let handler: fn(tauri::ipc::Invoke) -> bool = tauri::generate_handler![
+        get_notification_status,
+        start_notifications,
+        stop_notifications,
+        send_test_notification,
+        notifier::set_smtp_password,
+        notifications::get_notification_permission,
+        notifications::request_notification_permission,
+        notifications::take_fallback_alerts,
+        health::get_health,
+        snapshot::get_app_snapshot,
+        tray::get_tray_status,
+        tray::answer_close_prompt,
+        tray::quit_app,
+        shutdown::restart_app,
+        updates::check_for_updates_now,
+        updates::get_changelog,
+        usage::get_usage_stats,
+        usage::clear_usage_stats,
+        support::create_support_bundle,
+        clock::set_time_offset,
+        clock::get_time_offset,
+        ntp::get_clock_drift,
+        tamper::get_tamper_status,
+        layout::open_timer_window,
+        layout::close_timer_window,
+        layout::list_displays,
+        layout::move_to_display,
+        layout::clear_window_placement,
+        opacity::set_window_opacity,
+        kiosk::get_kiosk_status,
+        kiosk::enable_kiosk,
+        kiosk::disable_kiosk,
+        edit_lock::get_edit_lock_status,
+        edit_lock::set_edit_passcode,
+        edit_lock::clear_edit_passcode,
+        viewer::get_viewer_status,
+        viewer::stop_viewing,
+        todoist::connect_todoist,
+        todoist::disconnect_todoist,
+        todoist::sync_todoist,
+        milestones::import_github_milestones,
+        milestones::import_jira_sprints,
+        embed::list_embed_links,
+        embed::create_embed_link,
+        embed::revoke_embed_link,
+        api_tokens::list_api_tokens,
+        api_tokens::create_api_token,
+        api_tokens::revoke_api_token,
+        audit::get_api_audit_log,
+        audit::clear_api_audit_log,
+        set_timer_dates,
+        get_time_remaining,
+        get_startup_enabled,
+        enable_startup,
+        disable_startup,
+        timers::create_timer,
+        timers::create_preset_timer,
+        timers::add_timer_milestone,
+        timers::remove_timer_milestone,
+        timers::set_milestone_done,
+        timers::get_timer_timeline,
+        timers::log_checkin,
+        timers::create_goal_timer,
+        timers::log_goal_progress,
+        pace::get_pace,
+        timers::list_timers,
+        timers::get_timer,
+        timers::search_timers,
+        timers::delete_timer,
+        timers::archive_timer,
+        timers::unarchive_timer,
+        timers::list_trash,
+        timers::restore_timer,
+        timers::empty_trash,
+        timers::get_timer_history,
+        timers::set_timer_tags,
+        timers::get_tag_defaults,
+        timers::set_tag_defaults,
+        timers::set_timer_appearance,
+        timers::set_timer_note,
+        timers::add_timer_reminder,
+        timers::remove_timer_reminder,
+        timers::set_relative_reminders,
+        timers::set_timer_schedule,
+        timers::set_timer_importance,
+        timers::set_timer_wording,
+        timers::set_timer_schedules,
+        timers::preview_reminder_schedule,
+        timers::set_timer_recurrence,
+        timers::complete_timer_occurrence,
+        timers::get_timer_streak,
+        settings::get_settings,
+        settings::update_settings,
+        settings::validate_settings,
+        settings::get_settings_problems,
+        audio::dismiss_alarm,
+        audio::set_timer_sound,
+        appearance::get_system_appearance,
+        i18n::get_available_locales,
+        i18n::set_locale,
+        a11y::get_countdown_announcement,
+        tick::get_tick_rate,
+        tick::set_tick_rate,
+        actions::get_pending_expiry_action,
+        actions::cancel_expiry_action,
+        actions::set_timer_expiry_action,
+        focus::get_break_status,
+        focus::set_timer_focus_enforcement,
+        activity::get_app_usage,
+        activity::clear_app_usage,
+        stats::get_statistics,
+        clipboard::copy_countdown_text,
+        clipboard::propose_timer_from_clipboard,
+        import::import_timers,
+        import::take_pending_imports,
+        import::export_timers,
+        import::export_timer_with_alarms,
+        share::get_timer_qr,
+        share::export_countdown_image,
+        share::get_countdown_breakdown,
+        share::get_deadline_in_zones,
+        remote::subscribe_timer,
+        remote::refresh_remote_deadline,
+        deadline_file::watch_deadline_file,
+        deadline_file::unwatch_deadline_file,
+        deadline_file::sync_deadline_file
+    ];
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_deep_link::init())
         .manage(NotificationState::default())
-        .invoke_handler(tauri::generate_handler![
-            get_notification_status,
-            start_notifications,
-            stop_notifications,
-            send_test_notification,
-            set_timer_dates,
-            get_time_remaining,
-            get_startup_enabled,
-            enable_startup,
-            disable_startup
-        ])
+        .manage(TimerState::default())
+        .manage(SettingsState::default())
+        .manage(AudioState::default())
+        .manage(PluginState::default())
+        .manage(ScriptState::default())
+        .manage(AppearanceState::default())
+        .manage(TickState::default())
+        .manage(SchedulerState::default())
+        .manage(PowerState::default())
+        .manage(ActionState::default())
+        .manage(FocusState::default())
+        .manage(ActivityState::default())
+        .manage(StatsState::default())
+        .manage(ImportState::default())
+        .manage(DeliveryState::default())
+        .manage(TrayState::default())
+        .manage(LayoutState::default())
+        .manage(KioskState::default())
+        .manage(EditLockState::default())
+        .manage(ViewerState::default())
+        .manage(IntegrationState::default())
+        .manage(HeartbeatState::default())
+        .manage(EmbedState::default())
+        .manage(ChangelogState::default())
+        .manage(UsageState::default())
+        .manage(DriftState::default())
+        .manage(TamperState::default())
+        .manage(ApiTokenState::default())
+        .manage(AuditState::default())
+        .manage(server::RateLimits::default())
+        // Kiosk and viewer modes get a say before any command runs
+        .invoke_handler(move |invoke| {
+            let app = invoke.message.webview().app_handle().clone();
+            if let Err(e) = kiosk::check_command(&app, invoke.message.command()) {
+                invoke.resolver.reject(e);
+                return true;
+            }
+            handler(invoke)
+        })
         .setup(|app| {
-            // Setup system tray only if we have a default icon
-            if let Some(icon) = app.default_window_icon() {
-                let _tray = TrayIconBuilder::new()
-                    .icon(icon.clone())
-                    .title("Hourglass")
-                    .tooltip("Hourglass - Time Tracker")
-                    .on_tray_icon_event(|tray, event| {
-                        if let TrayIconEvent::Click { .. } = event {
-                            let app = tray.app_handle();
-                            // Get the first available window since no specific label is set
-                            if let Some(window) = app.webview_windows().values().next() {
-                                let _ = window.show();
-                                let _ = window.set_focus();
-                            }
-                        }
-                    })
-                    .build(app)?;
+            // Restore timers (and which reminders already fired) from the previous session
+            let data_dir = app.path().app_data_dir()?;
+            app.state::<TimerState>().lock()?.scheduler = app.state::<SchedulerState>().sender.clone();
+            app.state::<TimerState>().lock()?.events = Some(app.handle().clone());
+            app.state::<TimerState>().load(data_dir.join("timers.json"))?;
+            app.state::<SettingsState>().load(data_dir.join("settings.json"))?;
+            app.state::<ActivityState>().load(data_dir.join("activity.json"))?;
+            app.state::<StatsState>().load(data_dir.join("stats.json"))?;
+            app.state::<LayoutState>().load(data_dir.join("windows.json"))?;
+            app.state::<KioskState>().load(data_dir.join("kiosk.json"))?;
+            app.state::<EditLockState>().load(data_dir.join("edit_lock.json"))?;
+            app.state::<IntegrationState>().load(data_dir.join("integrations.json"))?;
+            app.state::<EmbedState>().load(data_dir.join("embed.json"))?;
+            app.state::<ApiTokenState>().load(data_dir.join("api_tokens.json"))?;
+            app.state::<AuditState>().load(data_dir.join("api_audit.json"))?;
+            app.state::<ChangelogState>().load(data_dir.join("changelog.json"))?;
+            app.state::<UsageState>().load(data_dir.join("usage.json"))?;
+            app.state::<TamperState>().load(data_dir.join("tamper_log.json"))?;
+            app.state::<HeartbeatState>().load(data_dir.join("heartbeat.json"))?;
+            app.state::<PluginState>().set_dir(data_dir.join("plugins"))?;
+            app.state::<ScriptState>().set_dir(data_dir.join("scripts"))?;
+            usage::record(app.handle(), UsageEvent::Launch, 1);
+
+            // Apply the saved language and clock before anything user-facing is rendered
+            let settings = app.state::<SettingsState>().lock()?.clone();
+            format::set_clock_format(settings.clock_format);
+            format::set_rounding(settings.remaining_rounding);
+            timers::set_fiscal_year_start(settings.fiscal_year_start_month);
+            if let Err(e) = i18n::apply(settings.locale.as_deref()) {
+                eprintln!("Failed to apply saved locale, following the system language: {}", e);
+                let _ = i18n::apply(None);
             }
 
-            // Configure window close behavior to minimize to tray
-            if let Some(window) = app.webview_windows().values().next() {
-                let window_clone = window.clone();
-                window.on_window_event(move |event| {
-                    if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+            tray::setup(app)?;
+            tray::detect(app.handle().clone());
+            layout::reopen_timer_windows(app.handle());
+            kiosk::restore(app.handle());
+
+            // Route the close button through the close-behavior setting, and keep the
+            // frontend's view of the OS appearance current
+            if let Some(window) = main_window(app.handle()) {
+                layout::apply_placement(app.handle(), &window);
+                opacity::restore(app.handle(), &window);
+                let app_handle = app.handle().clone();
+                window.on_window_event(move |event| match event {
+                    tauri::WindowEvent::CloseRequested { api, .. } => {
                         api.prevent_close();
-                        let _ = window_clone.hide();
+                        if app_handle.state::<KioskState>().is_active() {
+                            // Stays on screen until kiosk mode is turned off with the passcode
+                        } else {
+                            tray::close_requested(&app_handle);
+                        }
+                    }
+                    tauri::WindowEvent::ThemeChanged(theme) => {
+                        let name = if *theme == tauri::Theme::Dark { "dark" } else { "light" };
+                        if let Err(e) = app_handle.emit("theme-changed", name) {
+                            eprintln!("Failed to emit theme change: {}", e);
+                        }
+                        tray::apply_theme(&app_handle, *theme);
+                        appearance::refresh(&app_handle);
+                    }
+                    tauri::WindowEvent::Focused(true) => {
+                        appearance::refresh(&app_handle);
                     }
+                    tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) => {
+                        import::offer_files(&app_handle, paths);
+                    }
+                    _ => {}
                 });
             }
 
+            a11y::spawn(app.handle().clone());
+            tick::spawn(app.handle().clone());
+            notifications::spawn(app.handle().clone());
+            power::spawn(app.handle().clone());
+            ntp::spawn(app.handle().clone());
+            tamper::spawn(app.handle().clone());
+            focus::spawn_app_blocker(app.handle().clone());
+            hosts::spawn(app.handle().clone());
+            activity::spawn(app.handle().clone());
+            wallpaper::spawn(app.handle().clone());
+            server::spawn(app.handle().clone());
+            viewer::spawn(app.handle().clone());
+            remote::spawn(app.handle().clone());
+            deadline_file::spawn(app.handle().clone());
+            todoist::spawn(app.handle().clone());
+            import::offer_launch_arguments(app.handle());
+            let app_handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| import::offer_links(&app_handle, &event.urls()));
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                import::offer_links(app.handle(), &urls);
+            }
+
             // Auto-start notifications on app launch
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
@@ -351,6 +1246,17 @@ fn main() {
             
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                shutdown::run(app);
+            }
+            // macOS hands over double-clicked files as an event rather than as arguments
+            #[cfg(any(target_os = "macos", target_os = "ios"))]
+            if let tauri::RunEvent::Opened { urls } = &event {
+                let paths: Vec<std::path::PathBuf> = urls.iter().filter_map(|url| url.to_file_path().ok()).collect();
+                import::offer_files(app, &paths);
+            }
+        });
 }
\ No newline at end of file
@@ -0,0 +1,134 @@
+use chrono::{DateTime, Utc};
+
+use hourglass_core::countdown::time_components;
+
+pub use hourglass_core::countdown::percent_elapsed;
+
+use crate::{format, pace};
+use crate::i18n::{t, t_args};
+use crate::timers::{DailyGoal, Milestone, ReminderWording, Timer};
+
+const HOUR_MS: i64 = 60 * 60 * 1000;
+const DAY_MS: i64 = 24 * HOUR_MS;
+
+fn pace_line(percent: i64) -> String {
+    let outlook = if percent >= 90 {
+        "pace-almost-done"
+    } else if percent >= 75 {
+        "pace-wrap-up"
+    } else if percent >= 50 {
+        "pace-halfway"
+    } else if percent >= 25 {
+        "pace-room-to-plan"
+    } else {
+        "pace-plenty-left"
+    };
+    t_args("pace-used", &[("percent", percent.into()), ("outlook", t(outlook).into())])
+}
+
+// Builds the reminder body: remaining time, progress through the countdown and a tone that
+// gets more urgent as the deadline approaches
+pub fn compose_reminder(start: Option<DateTime<Utc>>, end: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let remaining = (end - now).num_milliseconds();
+    if remaining <= 0 {
+        return t("reminder-expired");
+    }
+
+    let (_, _, minutes, _) = time_components(format::round_remaining(remaining, 60 * 1000));
+    let duration = format::format_duration(remaining);
+    let headline = if remaining <= HOUR_MS {
+        t_args("reminder-final-hour", &[("minutes", minutes.into()), ("duration", duration.into())])
+    } else if remaining <= DAY_MS {
+        t_args("reminder-final-day", &[("duration", duration.into())])
+    } else {
+        t_args("reminder-remaining", &[("duration", duration.into())])
+    };
+    let ends = t_args("reminder-ends", &[("date", format::format_date_time(end).into())]);
+
+    match start.and_then(|start| percent_elapsed(start, end, now)) {
+        // The final-day tone already carries the urgency; the pace line would only repeat it
+        Some(percent) if remaining > DAY_MS => format!("{}\n{}\n{}", headline, ends, pace_line(percent)),
+        _ => format!("{}\n{}", headline, ends),
+    }
+}
+
+// A timer's reminder, worded the way the timer asks for
+pub fn compose_timer_reminder(timer: &Timer, now: DateTime<Utc>) -> String {
+    match timer.wording {
+        ReminderWording::Exact => compose_reminder(Some(timer.start_date), timer.end_date, now),
+        ReminderWording::Casual => compose_casual_reminder(timer.end_date, now),
+    }
+}
+
+// The reminder with where the latest check-in's pace ends up, once there is one
+pub fn compose_reminder_with_projection(timer: &Timer, now: DateTime<Utc>) -> String {
+    let body = compose_timer_reminder(timer, now);
+    let Some(late_by) = pace::for_timer(timer, now).and_then(|report| report.pace.late_by_seconds) else {
+        return body;
+    };
+    let projection = if late_by > 0 {
+        t_args("projection-late", &[("duration", format::format_duration_casual(late_by * 1000).into())])
+    } else {
+        t("projection-on-time")
+    };
+    format!("{}\n{}", body, projection)
+}
+
+// Only the rounded time left: no exact date, minutes or percentage
+pub fn compose_casual_reminder(end: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let remaining = (end - now).num_milliseconds();
+    if remaining <= 0 {
+        return t("reminder-expired");
+    }
+    t_args("reminder-casual", &[("duration", format::format_duration_casual(remaining).into())])
+}
+
+// A milestone coming up, or due now
+pub fn compose_milestone(milestone: &Milestone, is_due: bool, now: DateTime<Utc>) -> String {
+    let name = milestone.name.as_str();
+    if is_due {
+        return t_args("milestone-due", &[("name", name.into())]);
+    }
+    let duration = format::format_duration((milestone.due - now).num_milliseconds().max(0));
+    t_args("milestone-reminder", &[("name", name.into()), ("duration", duration.into())])
+}
+
+// Sent at a daily goal's reset, for the day that just ended
+pub fn compose_goal_result(goal: &DailyGoal) -> String {
+    let args = [("done", goal.progress_minutes.into()), ("target", goal.target_minutes.into())];
+    if goal.progress_minutes >= goal.target_minutes {
+        t_args("goal-met", &args)
+    } else {
+        t_args("goal-missed", &args)
+    }
+}
+
+// After the app was closed or the machine slept for `gap_ms`: how long that was, then where each
+// countdown that came due meanwhile stands and which milestones were reached
+pub fn compose_downtime_catch_up(gap_ms: i64, items: &[(String, DateTime<Utc>)], milestones: &[(String, String)], now: DateTime<Utc>) -> String {
+    let mut lines = vec![t_args("catch-up-gap", &[("duration", format::format_duration(gap_ms).into())])];
+    if !items.is_empty() {
+        lines.push(compose_catch_up(items, now));
+    }
+    lines.extend(milestones.iter().map(|(title, milestone)| {
+        t_args("catch-up-line-milestone", &[("title", title.as_str().into()), ("milestone", milestone.as_str().into())])
+    }));
+    lines.join("\n")
+}
+
+// One line per countdown whose reminder was held back, showing where it stands now
+pub fn compose_catch_up(items: &[(String, DateTime<Utc>)], now: DateTime<Utc>) -> String {
+    items
+        .iter()
+        .map(|(title, end)| {
+            let remaining = (*end - now).num_milliseconds();
+            if remaining <= 0 {
+                t_args("catch-up-line-expired", &[("title", title.as_str().into())])
+            } else {
+                let duration = format::format_duration(remaining);
+                t_args("catch-up-line", &[("title", title.as_str().into()), ("duration", duration.into())])
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
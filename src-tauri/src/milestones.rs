@@ -0,0 +1,120 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::http;
+use crate::integrations::{self, ExternalDeadline, SyncReport};
+
+const GITHUB_API: &str = "https://api.github.com";
+const JIRA_PAGE_SIZE: usize = 50;
+
+#[derive(Deserialize)]
+struct GithubMilestone {
+    number: u64,
+    title: String,
+    due_on: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraSprint {
+    id: u64,
+    name: String,
+    end_date: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraSprintPage {
+    values: Vec<JiraSprint>,
+    is_last: bool,
+}
+
+fn finish(app: &AppHandle, provider: &str, deadlines: &[ExternalDeadline]) -> Result<SyncReport, String> {
+    // Closed milestones and finished sprints drop out of the listings, so their timers are archived
+    let report = integrations::sync_deadlines(app, provider, deadlines, true)?;
+    if let Err(e) = app.emit("timers-synced", provider) {
+        eprintln!("Failed to emit milestone sync: {}", e);
+    }
+    Ok(report)
+}
+
+// Open milestones of an "owner/name" repository that have a due date. Public repositories
+// don't need a token.
+#[tauri::command]
+pub async fn import_github_milestones(app: AppHandle, repo: String, token: Option<String>) -> Result<SyncReport, String> {
+    let repo = repo.trim().trim_matches('/');
+    if repo.split('/').count() != 2 || repo.split('/').any(str::is_empty) {
+        return Err(format!("Expected a repository as owner/name, got {}", repo));
+    }
+    let client = http::client()?;
+    let mut deadlines = Vec::new();
+    for page in 1.. {
+        let url = format!("{}/repos/{}/milestones?state=open&per_page=100&page={}", GITHUB_API, repo, page);
+        let mut request = client.get(url).header("Accept", "application/vnd.github+json");
+        if let Some(token) = token.as_deref().map(str::trim).filter(|token| !token.is_empty()) {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await.map_err(|e| format!("Failed to reach GitHub: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("GitHub answered {}", response.status()));
+        }
+        let milestones: Vec<GithubMilestone> = response.json().await.map_err(|e| format!("Failed to read GitHub milestones: {}", e))?;
+        if milestones.is_empty() {
+            break;
+        }
+        deadlines.extend(milestones.into_iter().filter_map(|milestone| {
+            Some(ExternalDeadline {
+                id: milestone.number.to_string(),
+                title: milestone.title,
+                due: milestone.due_on?,
+            })
+        }));
+    }
+    finish(&app, &format!("github:{}", repo), &deadlines)
+}
+
+// Active and upcoming sprints of a Jira Software board, ending when the sprint does. `site` is
+// the Jira host, e.g. example.atlassian.net, and the token is an API token for `email`.
+#[tauri::command]
+pub async fn import_jira_sprints(
+    app: AppHandle,
+    site: String,
+    board_id: u64,
+    email: String,
+    token: String,
+) -> Result<SyncReport, String> {
+    let site = site.trim().trim_start_matches("https://").trim_end_matches('/');
+    let client = http::client()?;
+    let mut deadlines = Vec::new();
+    let mut start = 0;
+    loop {
+        let url = format!(
+            "https://{}/rest/agile/1.0/board/{}/sprint?state=active,future&startAt={}&maxResults={}",
+            site, board_id, start, JIRA_PAGE_SIZE
+        );
+        let response = client
+            .get(url)
+            .basic_auth(email.trim(), Some(token.trim()))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach Jira: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Jira answered {}", response.status()));
+        }
+        let page: JiraSprintPage = response.json().await.map_err(|e| format!("Failed to read Jira sprints: {}", e))?;
+        start += page.values.len();
+        let done = page.is_last || page.values.is_empty();
+        deadlines.extend(page.values.into_iter().filter_map(|sprint| {
+            Some(ExternalDeadline {
+                id: sprint.id.to_string(),
+                title: sprint.name,
+                due: integrations::parse_due(&sprint.end_date?)?,
+            })
+        }));
+        if done {
+            break;
+        }
+    }
+    finish(&app, &format!("jira:{}:{}", site, board_id), &deadlines)
+}
@@ -0,0 +1,273 @@
+#[cfg(any(windows, target_os = "macos"))]
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::plugin::PermissionState;
+use tauri::{AppHandle, Emitter, Manager, State, WebviewUrl, WebviewWindowBuilder};
+use tauri_plugin_notification::NotificationExt;
+
+#[cfg(all(unix, not(target_os = "macos")))]
+use crate::dbus;
+use crate::i18n::t;
+use crate::tray;
+
+const ALERT_WINDOW_LABEL: &str = "alert";
+const AVAILABILITY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const TRAY_FLASHES: u32 = 6;
+const TRAY_FLASH_INTERVAL: Duration = Duration::from_millis(400);
+// Alerts kept for the alert window if nobody collects them
+const MAX_PENDING_ALERTS: usize = 20;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationPermission {
+    Granted,
+    // Turned off for the app or system-wide by the user
+    Denied,
+    // Nothing on the system to show them, e.g. no notification daemon
+    Unavailable,
+    // The platform gives no way to tell
+    Unknown,
+}
+
+// Windows keeps a global switch and a per-app one; either set to 0 silences every toast
+#[cfg(windows)]
+fn system_permission() -> NotificationPermission {
+    use windows_sys::Win32::System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD};
+
+    fn read_dword(key: &str, value: &str) -> Option<u32> {
+        let key: Vec<u16> = key.encode_utf16().chain([0]).collect();
+        let value: Vec<u16> = value.encode_utf16().chain([0]).collect();
+        let mut data = 0u32;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        // SAFETY: NUL-terminated strings and a buffer of the size passed in
+        let status = unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                key.as_ptr(),
+                value.as_ptr(),
+                RRF_RT_REG_DWORD,
+                std::ptr::null_mut(),
+                (&mut data as *mut u32).cast(),
+                &mut size,
+            )
+        };
+        (status == 0).then_some(data)
+    }
+
+    let global = read_dword("Software\\Microsoft\\Windows\\CurrentVersion\\PushNotifications", "ToastEnabled");
+    let app = read_dword(
+        &format!("Software\\Microsoft\\Windows\\CurrentVersion\\Notifications\\Settings\\{}", crate::cli::APP_IDENTIFIER),
+        "Enabled",
+    );
+    if global == Some(0) || app == Some(0) {
+        NotificationPermission::Denied
+    } else {
+        NotificationPermission::Granted
+    }
+}
+
+// Notification Center settings live in an undocumented preferences format
+#[cfg(target_os = "macos")]
+fn system_permission() -> NotificationPermission {
+    NotificationPermission::Unknown
+}
+
+// Notifications need a daemon owning org.freedesktop.Notifications on the session bus. Asking it
+// for its server information also starts one that's only installed as a D-Bus service.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn system_permission() -> NotificationPermission {
+    match dbus::call_session(
+        "org.freedesktop.Notifications",
+        "/org/freedesktop/Notifications",
+        "org.freedesktop.Notifications.GetServerInformation",
+        &[],
+    ) {
+        Some(Ok(_)) => NotificationPermission::Granted,
+        Some(Err(_)) => NotificationPermission::Unavailable,
+        None => NotificationPermission::Unknown,
+    }
+}
+
+pub fn permission(app: &AppHandle) -> NotificationPermission {
+    match app.notification().permission_state() {
+        Ok(PermissionState::Granted) => system_permission(),
+        Ok(PermissionState::Denied) => NotificationPermission::Denied,
+        Ok(_) | Err(_) => NotificationPermission::Unknown,
+    }
+}
+
+// Where the user can turn notifications back on, when the platform has such a page
+#[cfg(windows)]
+fn open_system_settings() -> Result<(), String> {
+    Command::new("explorer")
+        .arg("ms-settings:notifications")
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open notification settings: {}", e))
+}
+
+#[cfg(target_os = "macos")]
+fn open_system_settings() -> Result<(), String> {
+    Command::new("open")
+        .arg("x-apple.systempreferences:com.apple.preference.notifications")
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open notification settings: {}", e))
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn open_system_settings() -> Result<(), String> {
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_notification_permission(app: AppHandle) -> Result<NotificationPermission, String> {
+    tauri::async_runtime::spawn_blocking(move || permission(&app))
+        .await
+        .map_err(|e| format!("Failed to check notification permission: {}", e))
+}
+
+// Asks the OS where it can prompt; where it can't and notifications are off, opens the
+// settings page instead. Returns the state afterwards.
+#[tauri::command]
+pub async fn request_notification_permission(app: AppHandle) -> Result<NotificationPermission, String> {
+    app.notification()
+        .request_permission()
+        .map_err(|e| format!("Failed to request notification permission: {}", e))?;
+    let status = get_notification_permission(app).await?;
+    if status == NotificationPermission::Denied {
+        open_system_settings()?;
+    }
+    Ok(status)
+}
+
+// A notification the OS couldn't show, waiting for the in-app alert window
+#[derive(Serialize, Clone)]
+pub struct FallbackAlert {
+    pub title: String,
+    pub body: String,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+pub struct DeliveryState {
+    // Why notifications currently can't reach the OS; None while they can
+    pub degraded: Arc<Mutex<Option<String>>>,
+    pub pending: Arc<Mutex<Vec<FallbackAlert>>>,
+}
+
+impl DeliveryState {
+    pub fn degraded(&self) -> Option<String> {
+        self.degraded.lock().ok().and_then(|reason| reason.clone())
+    }
+
+    fn set_degraded(&self, app: &AppHandle, reason: Option<String>) {
+        let Ok(mut degraded) = self.degraded.lock() else {
+            return;
+        };
+        if *degraded != reason {
+            *degraded = reason.clone();
+            let _ = app.emit("notification-delivery-changed", reason);
+        }
+    }
+}
+
+// The plugin hands notifications off in the background and never hears whether they were shown,
+// so whether anything can show them is checked up front instead, and rechecked now and then in
+// case a daemon is started later
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let checked = app.clone();
+            if let Ok(permission) = tauri::async_runtime::spawn_blocking(move || permission(&checked)).await {
+                let reason = (permission == NotificationPermission::Unavailable).then(|| "No notification daemon is running".to_string());
+                app.state::<DeliveryState>().set_degraded(&app, reason);
+            }
+            tokio::time::sleep(AVAILABILITY_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+// Shows a notification, falling back to an in-app alert when the OS can't
+pub fn show(app: &AppHandle, title: &str, body: &str) -> Result<(), String> {
+    let state = app.state::<DeliveryState>();
+    if let Some(reason) = state.degraded() {
+        show_fallback(app, title, body);
+        return Err(reason);
+    }
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        state.set_degraded(app, Some(e.to_string()));
+        show_fallback(app, title, body);
+        return Err(e.to_string());
+    }
+    Ok(())
+}
+
+// Critical reminders also open the in-app alert, which stays on top until it's dismissed
+pub fn show_critical(app: &AppHandle, title: &str, body: &str) -> Result<(), String> {
+    show(app, title, body)?;
+    show_fallback(app, title, body);
+    Ok(())
+}
+
+// A small always-on-top window the frontend fills from take_fallback_alerts, plus a flashing
+// tray icon for when the window ends up behind a full-screen app anyway
+fn show_fallback(app: &AppHandle, title: &str, body: &str) {
+    let alert = FallbackAlert {
+        title: title.to_string(),
+        body: body.to_string(),
+        at: Utc::now(),
+    };
+    if let Ok(mut pending) = app.state::<DeliveryState>().pending.lock() {
+        pending.push(alert.clone());
+        let overflow = pending.len().saturating_sub(MAX_PENDING_ALERTS);
+        pending.drain(..overflow);
+    }
+
+    match app.get_webview_window(ALERT_WINDOW_LABEL) {
+        Some(window) => {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+        None => {
+            let built = WebviewWindowBuilder::new(app, ALERT_WINDOW_LABEL, WebviewUrl::App("index.html?window=alert".into()))
+                .title(t("alert-window-title"))
+                .inner_size(420.0, 220.0)
+                .center()
+                .always_on_top(true)
+                .resizable(false)
+                .focused(true)
+                .build();
+            if let Err(e) = built {
+                eprintln!("Failed to open alert window: {}", e);
+            }
+        }
+    }
+    let _ = app.emit_to(ALERT_WINDOW_LABEL, "fallback-alert", alert);
+
+    // With no tray the icon is hidden on purpose, and flashing would bring it back
+    if !tray::uses_tray(app) {
+        return;
+    }
+    let Some(tray) = app.tray_by_id(tray::TRAY_ID) else {
+        return;
+    };
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        for flash in 0..TRAY_FLASHES * 2 {
+            let _ = tray.set_visible(flash % 2 == 1);
+            tokio::time::sleep(TRAY_FLASH_INTERVAL).await;
+        }
+        // Left as the tray mode wants it, which may have changed during the flash
+        let _ = tray.set_visible(tray::uses_tray(&app));
+    });
+}
+
+#[tauri::command]
+pub async fn take_fallback_alerts(state: State<'_, DeliveryState>) -> Result<Vec<FallbackAlert>, String> {
+    let mut pending = state.pending.lock().map_err(|e| format!("Failed to lock fallback alerts: {}", e))?;
+    Ok(std::mem::take(&mut *pending))
+}
@@ -0,0 +1,263 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::audio::AudioState;
+use crate::integrations::IntegrationState;
+use crate::plugins::{self, PluginNotifier};
+use crate::settings::SettingsState;
+use crate::timers::{Importance, Timer};
+use crate::{clock, http, notifications};
+
+// What a notification is about, which decides the channels it goes out on
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    Reminder,
+    Expiry,
+    // The summary of reminders held back while the user was away
+    CatchUp,
+    Milestone,
+    // Everything else the app has to say, e.g. a failed action
+    System,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Channel {
+    Desktop,
+    // The timer's own sound, unless it's of low importance
+    Sound,
+    Webhook,
+    // Mailed through the SMTP server in settings
+    Email,
+    // Every executable in the plugins folder
+    Plugins,
+}
+
+// Expiry already has the alarm, so only reminders play the timer's sound by default. Plugins
+// get everything: with none installed, that channel does nothing.
+pub fn default_routes() -> BTreeMap<EventKind, Vec<Channel>> {
+    BTreeMap::from([
+        (EventKind::Reminder, vec![Channel::Desktop, Channel::Sound, Channel::Plugins]),
+        (EventKind::Expiry, vec![Channel::Desktop, Channel::Plugins]),
+        (EventKind::CatchUp, vec![Channel::Desktop, Channel::Plugins]),
+        (EventKind::Milestone, vec![Channel::Desktop, Channel::Plugins]),
+        (EventKind::System, vec![Channel::Desktop, Channel::Plugins]),
+    ])
+}
+
+#[derive(Serialize, Clone)]
+pub struct Notification {
+    pub kind: EventKind,
+    pub title: String,
+    pub body: String,
+    pub importance: Importance,
+    pub timer_id: Option<u64>,
+    #[serde(skip)]
+    pub sound: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+impl Notification {
+    pub fn new(kind: EventKind, title: &str, body: &str) -> Self {
+        Self {
+            kind,
+            title: title.to_string(),
+            body: body.to_string(),
+            importance: Importance::default(),
+            timer_id: None,
+            sound: None,
+            at: clock::now(),
+        }
+    }
+
+    pub fn for_timer(kind: EventKind, timer: &Timer, body: &str) -> Self {
+        Self {
+            importance: timer.importance,
+            timer_id: Some(timer.id),
+            sound: timer.sound_path.clone(),
+            ..Self::new(kind, &timer.display_title(), body)
+        }
+    }
+}
+
+// One way of getting a notification to the user
+pub trait Notifier {
+    fn deliver(&self, app: &AppHandle, notification: &Notification) -> Result<(), String>;
+}
+
+// The OS notification, falling back to the in-app alert; critical ones get the alert as well
+pub struct DesktopNotifier;
+
+impl Notifier for DesktopNotifier {
+    fn deliver(&self, app: &AppHandle, notification: &Notification) -> Result<(), String> {
+        if notification.importance == Importance::Critical {
+            notifications::show_critical(app, &notification.title, &notification.body)
+        } else {
+            notifications::show(app, &notification.title, &notification.body)
+        }
+    }
+}
+
+pub struct SoundNotifier;
+
+impl Notifier for SoundNotifier {
+    fn deliver(&self, app: &AppHandle, notification: &Notification) -> Result<(), String> {
+        if notification.importance != Importance::Low {
+            if let Some(sound) = &notification.sound {
+                app.state::<AudioState>().play_sound(sound);
+            }
+        }
+        Ok(())
+    }
+}
+
+// POSTs the notification as JSON. Sent in the background, so a slow endpoint never holds up
+// the scheduler; failures are only logged.
+pub struct WebhookNotifier {
+    pub url: String,
+}
+
+impl Notifier for WebhookNotifier {
+    fn deliver(&self, _app: &AppHandle, notification: &Notification) -> Result<(), String> {
+        let client = http::client()?;
+        let request = client.post(&self.url).json(notification);
+        tauri::async_runtime::spawn(async move {
+            match request.send().await {
+                Ok(response) if !response.status().is_success() => eprintln!("Webhook answered {}", response.status()),
+                Ok(_) => {}
+                Err(e) => eprintln!("Failed to call webhook: {}", e),
+            }
+        });
+        Ok(())
+    }
+}
+
+// Port 465 speaks TLS from the start; any other port has to upgrade with STARTTLS
+const SMTPS_PORT: u16 = 465;
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Mails the notification as plain text. Sent in the background like the webhook, so failures are
+// only logged.
+pub struct EmailNotifier {
+    pub host: String,
+    pub port: u16,
+    pub credentials: Option<Credentials>,
+    pub from: Mailbox,
+    pub to: Mailbox,
+}
+
+impl Notifier for EmailNotifier {
+    fn deliver(&self, _app: &AppHandle, notification: &Notification) -> Result<(), String> {
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(self.to.clone())
+            .subject(&notification.title)
+            .header(ContentType::TEXT_PLAIN)
+            .body(notification.body.clone())
+            .map_err(|e| format!("Failed to build email: {}", e))?;
+        let builder = if self.port == SMTPS_PORT {
+            AsyncSmtpTransport::<Tokio1Executor>::relay(&self.host)
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.host)
+        };
+        let mut builder = builder
+            .map_err(|e| format!("Failed to set up SMTP for {}: {}", self.host, e))?
+            .port(self.port)
+            .timeout(Some(SMTP_TIMEOUT));
+        if let Some(credentials) = &self.credentials {
+            builder = builder.credentials(credentials.clone());
+        }
+        let transport = builder.build();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = transport.send(message).await {
+                eprintln!("Failed to send notification email: {}", e);
+            }
+        });
+        Ok(())
+    }
+}
+
+pub fn validate_mailbox(address: &str) -> bool {
+    address.parse::<Mailbox>().is_ok()
+}
+
+// None until the server and both addresses are set
+fn email_notifier(app: &AppHandle) -> Option<EmailNotifier> {
+    let (host, port, username, from, to) = {
+        let settings = app.state::<SettingsState>();
+        let settings = settings.lock().ok()?;
+        (
+            settings.smtp_host.clone()?,
+            settings.smtp_port,
+            settings.smtp_username.clone(),
+            settings.email_from.as_deref()?.parse().ok()?,
+            settings.email_to.as_deref()?.parse().ok()?,
+        )
+    };
+    let password = app.state::<IntegrationState>().lock().ok().and_then(|config| config.smtp_password.clone());
+    Some(EmailNotifier {
+        host,
+        port,
+        credentials: username.map(|username| Credentials::new(username, password.unwrap_or_default())),
+        from,
+        to,
+    })
+}
+
+// Kept with the other credentials rather than in settings; None forgets it
+#[tauri::command]
+pub async fn set_smtp_password(state: State<'_, IntegrationState>, password: Option<String>) -> Result<(), String> {
+    state.lock()?.smtp_password = password.filter(|password| !password.is_empty());
+    state.save()
+}
+
+pub fn validate_webhook_url(url: &str) -> bool {
+    tauri::Url::parse(url).is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host().is_some())
+}
+
+fn notifiers(app: &AppHandle, channel: Channel) -> Vec<Box<dyn Notifier>> {
+    match channel {
+        Channel::Desktop => vec![Box::new(DesktopNotifier)],
+        Channel::Sound => vec![Box::new(SoundNotifier)],
+        Channel::Webhook => {
+            let url = app.state::<SettingsState>().lock().ok().and_then(|settings| settings.webhook_url.clone());
+            url.map(|url| Box::new(WebhookNotifier { url }) as Box<dyn Notifier>).into_iter().collect()
+        }
+        Channel::Email => email_notifier(app).map(|email| Box::new(email) as Box<dyn Notifier>).into_iter().collect(),
+        Channel::Plugins => plugins::discover(app)
+            .into_iter()
+            .map(|path| Box::new(PluginNotifier { path }) as Box<dyn Notifier>)
+            .collect(),
+    }
+}
+
+// Sends the notification on every channel routed for its kind. The result is the desktop
+// channel's, since that's the one the user is sure to see; the others only log failures.
+pub fn dispatch(app: &AppHandle, notification: &Notification) -> Result<(), String> {
+    let channels = app
+        .state::<SettingsState>()
+        .lock()
+        .ok()
+        .and_then(|settings| settings.notification_routes.get(&notification.kind).cloned())
+        .or_else(|| default_routes().remove(&notification.kind))
+        .unwrap_or_default();
+    let mut shown = Ok(());
+    for channel in channels {
+        for notifier in notifiers(app, channel) {
+            match (channel, notifier.deliver(app, notification)) {
+                (Channel::Desktop, result) => shown = result,
+                (_, Err(e)) => eprintln!("Failed to deliver notification over {:?}: {}", channel, e),
+                (_, Ok(())) => {}
+            }
+        }
+    }
+    shown
+}
@@ -0,0 +1,130 @@
+use std::net::UdpSocket;
+use std::sync::Mutex;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+use tokio::time::interval;
+
+use crate::i18n::{t, t_args};
+use crate::notifier::EventKind;
+use crate::send_notification;
+use crate::settings::SettingsState;
+
+const NTP_PORT: u16 = 123;
+const NTP_TIMEOUT: Duration = Duration::from_secs(5);
+const CHECK_EVERY_HOURS: i64 = 6;
+// How soon turning the check on takes effect
+const POLL_INTERVAL: Duration = Duration::from_secs(10 * 60);
+// Seconds from the NTP epoch (1900) to the Unix epoch
+const NTP_UNIX_OFFSET: i64 = 2_208_988_800;
+
+#[derive(Serialize, Clone)]
+pub struct ClockDrift {
+    pub server: String,
+    // Positive when the system clock is behind the server
+    pub offset_ms: i64,
+    pub threshold_seconds: u32,
+    pub exceeds_threshold: bool,
+    pub checked_at: DateTime<Utc>,
+}
+
+// The last result, so a drift is only announced once rather than on every check
+#[derive(Default)]
+pub struct DriftState {
+    pub last: Mutex<Option<ClockDrift>>,
+}
+
+fn ntp_time(packet: &[u8]) -> Option<DateTime<Utc>> {
+    let seconds = u32::from_be_bytes(packet.get(..4)?.try_into().ok()?);
+    let fraction = u32::from_be_bytes(packet.get(4..8)?.try_into().ok()?);
+    let nanos = (u64::from(fraction) * 1_000_000_000) >> 32;
+    DateTime::from_timestamp(i64::from(seconds) - NTP_UNIX_OFFSET, nanos as u32)
+}
+
+// One SNTP exchange; the offset is corrected for the round trip as RFC 4330 describes
+fn query_offset(server: &str) -> Result<chrono::Duration, String> {
+    let socket = UdpSocket::bind("0.0.0.0:0").map_err(|e| format!("Failed to open a socket for NTP: {}", e))?;
+    socket.set_read_timeout(Some(NTP_TIMEOUT)).map_err(|e| format!("Failed to set NTP timeout: {}", e))?;
+    socket.connect((server, NTP_PORT)).map_err(|e| format!("Failed to reach {}: {}", server, e))?;
+
+    let mut request = [0u8; 48];
+    // No leap indicator, version 4, client mode
+    request[0] = 0x23;
+    let sent_at = Utc::now();
+    socket.send(&request).map_err(|e| format!("Failed to query {}: {}", server, e))?;
+    let mut response = [0u8; 48];
+    let received = socket.recv(&mut response).map_err(|e| format!("No answer from {}: {}", server, e))?;
+    let received_at = Utc::now();
+    if received < response.len() {
+        return Err(format!("Short answer from {}", server));
+    }
+
+    let server_received = ntp_time(&response[32..40]).ok_or_else(|| format!("Invalid time from {}", server))?;
+    let server_sent = ntp_time(&response[40..48]).ok_or_else(|| format!("Invalid time from {}", server))?;
+    Ok(((server_received - sent_at) + (server_sent - received_at)) / 2)
+}
+
+pub async fn check(app: &AppHandle) -> Result<ClockDrift, String> {
+    let (server, threshold_seconds) = {
+        let settings = app.state::<SettingsState>();
+        let settings = settings.lock()?;
+        (settings.ntp_server.trim().to_string(), settings.clock_drift_threshold_seconds)
+    };
+    let queried = server.clone();
+    let offset = tauri::async_runtime::spawn_blocking(move || query_offset(&queried))
+        .await
+        .map_err(|e| format!("Failed to run clock check: {}", e))??;
+    let drift = ClockDrift {
+        server,
+        offset_ms: offset.num_milliseconds(),
+        threshold_seconds,
+        exceeds_threshold: offset.num_milliseconds().unsigned_abs() > u64::from(threshold_seconds) * 1000,
+        checked_at: Utc::now(),
+    };
+
+    let state = app.state::<DriftState>();
+    let previous = state.last.lock().map_err(|e| format!("Failed to lock clock drift: {}", e))?.replace(drift.clone());
+    let already_warned = previous.is_some_and(|previous| previous.exceeds_threshold);
+    if drift.exceeds_threshold && !already_warned {
+        let seconds = drift.offset_ms.abs() / 1000;
+        send_notification(app, EventKind::System, &t("clock-drift-title"), &t_args("clock-drift-body", &[("seconds", seconds.into())]));
+    }
+    Ok(drift)
+}
+
+// Checks the system clock against an NTP server every few hours while the check is turned on.
+// A clock that's off makes every countdown and reminder off by the same amount.
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut tick = interval(POLL_INTERVAL);
+        loop {
+            tick.tick().await;
+            let enabled = app.state::<SettingsState>().lock().map(|settings| settings.clock_drift_check).unwrap_or(false);
+            let last_checked = app.state::<DriftState>().last.lock().ok().and_then(|last| last.as_ref().map(|drift| drift.checked_at));
+            let due = last_checked.is_none_or(|at| Utc::now() - at >= chrono::Duration::hours(CHECK_EVERY_HOURS));
+            if !enabled || !due {
+                continue;
+            }
+            if let Err(e) = check(&app).await {
+                eprintln!("Failed to check clock drift: {}", e);
+            }
+        }
+    });
+}
+
+// Checks right away, whether or not the background check is on
+#[tauri::command]
+pub async fn get_clock_drift(app: AppHandle, state: State<'_, DriftState>) -> Result<ClockDrift, String> {
+    match check(&app).await {
+        Ok(drift) => Ok(drift),
+        Err(e) => match state.last.lock().map_err(|e| format!("Failed to lock clock drift: {}", e))?.clone() {
+            // Offline: the last answer is better than none
+            Some(drift) => {
+                eprintln!("Failed to check clock drift, returning the last result: {}", e);
+                Ok(drift)
+            }
+            None => Err(e),
+        },
+    }
+}
@@ -0,0 +1,99 @@
+use tauri::{AppHandle, Manager, State, WebviewWindow};
+
+use crate::i18n::t_args;
+use crate::layout::LayoutState;
+
+// Any fainter and the window is easy to lose track of entirely
+const MIN_OPACITY_PERCENT: u8 = 10;
+
+#[cfg(windows)]
+fn set_native_opacity(window: &WebviewWindow, percent: u8) -> Result<(), String> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetWindowLongW, SetLayeredWindowAttributes, SetWindowLongW, GWL_EXSTYLE, LWA_ALPHA, WS_EX_LAYERED,
+    };
+
+    let hwnd = window.hwnd().map_err(|e| format!("Failed to get window handle: {}", e))?.0;
+    let alpha = (u32::from(percent) * 255 / 100) as u8;
+    // SAFETY: the handle belongs to a live window owned by this process
+    let ok = unsafe {
+        let style = GetWindowLongW(hwnd, GWL_EXSTYLE);
+        SetWindowLongW(hwnd, GWL_EXSTYLE, style | WS_EX_LAYERED as i32);
+        SetLayeredWindowAttributes(hwnd, 0, alpha, LWA_ALPHA)
+    } != 0;
+    if !ok {
+        return Err("Failed to set window opacity".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn set_native_opacity(window: &WebviewWindow, percent: u8) -> Result<(), String> {
+    // Carried across threads as an address, since raw pointers aren't Send
+    let ns_window = window.ns_window().map_err(|e| format!("Failed to get window handle: {}", e))? as usize;
+    let alpha = f64::from(percent) / 100.0;
+    // SAFETY: an NSWindow owned by this process, messaged on the main thread as AppKit requires
+    window
+        .run_on_main_thread(move || unsafe {
+            let _: () = objc2::msg_send![ns_window as *mut objc2::runtime::AnyObject, setAlphaValue: alpha];
+        })
+        .map_err(|e| format!("Failed to set window opacity: {}", e))
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn set_native_opacity(window: &WebviewWindow, percent: u8) -> Result<(), String> {
+    use gtk::prelude::WidgetExt;
+
+    let window = window.clone();
+    let alpha = f64::from(percent) / 100.0;
+    // GTK may only be touched from the main thread
+    window
+        .clone()
+        .run_on_main_thread(move || match window.gtk_window() {
+            Ok(gtk_window) => gtk_window.set_opacity(alpha),
+            Err(e) => eprintln!("Failed to get window handle: {}", e),
+        })
+        .map_err(|e| format!("Failed to set window opacity: {}", e))
+}
+
+// Applies the saved opacity, if any, to a newly created window
+pub fn restore(app: &AppHandle, window: &WebviewWindow) {
+    let percent = match app.state::<LayoutState>().lock() {
+        Ok(layout) => layout.opacity.get(window.label()).copied(),
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+    if let Some(percent) = percent.filter(|percent| *percent < 100) {
+        if let Err(e) = set_native_opacity(window, percent) {
+            eprintln!("Failed to restore opacity of window {}: {}", window.label(), e);
+        }
+    }
+}
+
+// Fades a window (the main one unless a label is given) and remembers it for later launches
+#[tauri::command]
+pub async fn set_window_opacity(
+    app: AppHandle,
+    state: State<'_, LayoutState>,
+    percent: u8,
+    window: Option<String>,
+) -> Result<(), String> {
+    if !(MIN_OPACITY_PERCENT..=100).contains(&percent) {
+        return Err(t_args("error-window-opacity", &[("min", MIN_OPACITY_PERCENT.into())]));
+    }
+    let label = window.unwrap_or_else(|| "main".to_string());
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| format!("Window {} not found", label))?;
+    set_native_opacity(&window, percent)?;
+    {
+        let mut layout = state.lock()?;
+        if percent == 100 {
+            layout.opacity.remove(&label);
+        } else {
+            layout.opacity.insert(label, percent);
+        }
+    }
+    state.save()
+}
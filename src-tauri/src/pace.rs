@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::State;
+
+use hourglass_core::countdown::{self, percent_elapsed, Pace};
+
+use crate::clock;
+use crate::i18n;
+use crate::timers::{Timer, TimerState};
+
+#[derive(Serialize)]
+pub struct PaceReport {
+    pub percent_done: u8,
+    pub checked_in_at: DateTime<Utc>,
+    pub percent_elapsed: Option<i64>,
+    #[serde(flatten)]
+    pub pace: Pace,
+}
+
+// Goes by the latest check-in; None until there is one
+pub fn for_timer(timer: &Timer, now: DateTime<Utc>) -> Option<PaceReport> {
+    let latest = timer.checkins.last()?;
+    let pace = countdown::pace(timer.start_date, timer.end_date, latest.at, f64::from(latest.percent_done))?;
+    Some(PaceReport {
+        percent_done: latest.percent_done,
+        checked_in_at: latest.at,
+        percent_elapsed: percent_elapsed(timer.start_date, timer.end_date, now),
+        pace,
+    })
+}
+
+// Required against actual pace and where the current one ends up
+#[tauri::command]
+pub async fn get_pace(state: State<'_, TimerState>, timer_id: u64) -> Result<Option<PaceReport>, String> {
+    let store = state.lock()?;
+    let timer = store.get(timer_id).ok_or_else(|| i18n::t_args("error-timer-not-found", &[("id", timer_id.into())]))?;
+    Ok(for_timer(timer, clock::now()))
+}
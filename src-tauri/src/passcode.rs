@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::i18n::t_args;
+
+const MIN_LENGTH: usize = 4;
+// Repeated hashing makes guessing a short passcode from the stored hash slow
+const ROUNDS: u32 = 100_000;
+
+// A passcode as stored on disk: never the passcode itself
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PasscodeHash {
+    salt: String,
+    hash: String,
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn derive(salt: &str, passcode: &str) -> String {
+    let mut digest = Sha256::new().chain_update(salt).chain_update(passcode).finalize();
+    for _ in 1..ROUNDS {
+        digest = Sha256::new().chain_update(digest).chain_update(passcode).finalize();
+    }
+    to_hex(&digest)
+}
+
+impl PasscodeHash {
+    pub fn new(passcode: &str) -> Result<Self, String> {
+        if passcode.chars().count() < MIN_LENGTH {
+            return Err(t_args("error-passcode-too-short", &[("min", MIN_LENGTH.into())]));
+        }
+        let mut salt = [0u8; 16];
+        getrandom::fill(&mut salt).map_err(|e| format!("Failed to generate salt: {}", e))?;
+        let salt = to_hex(&salt);
+        let hash = derive(&salt, passcode);
+        Ok(Self { salt, hash })
+    }
+
+    pub fn verify(&self, passcode: &str) -> bool {
+        secrets_match(&derive(&self.salt, passcode), &self.hash)
+    }
+}
+
+// Compares every byte so the time taken doesn't hint at how much matched
+pub fn secrets_match(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+// Random tokens are long enough that a single unsalted hash is as good as the slow one above
+pub fn token_hash(token: &str) -> String {
+    to_hex(&Sha256::digest(token.trim()))
+}
+
+// A random secret for links and API clients, too long to guess
+pub fn random_token() -> Result<String, String> {
+    let mut bytes = [0u8; 24];
+    getrandom::fill(&mut bytes).map_err(|e| format!("Failed to generate token: {}", e))?;
+    Ok(to_hex(&bytes))
+}
@@ -0,0 +1 @@
+pub use hourglass_core::persistence::{load_json, save_json};
@@ -0,0 +1,329 @@
+use chrono::{DateTime, Datelike, Days, Duration, Local, Months, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use serde::Serialize;
+
+use crate::format;
+
+const MONTHS: [&str; 12] = [
+    "january", "february", "march", "april", "may", "june",
+    "july", "august", "september", "october", "november", "december",
+];
+const WEEKDAYS: [&str; 7] = ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"];
+// Words that tie a date to the text around it and aren't part of the title
+const FILLER_WORDS: [&str; 10] = ["by", "due", "on", "at", "before", "until", "till", "deadline", "is", "the"];
+const MAX_LABEL_CHARS: usize = 80;
+
+// A deadline found in free text, e.g. "Report due next Friday at 5pm"
+#[derive(Serialize, Clone)]
+pub struct ParsedDeadline {
+    pub label: String,
+    pub end_date: DateTime<Utc>,
+}
+
+struct Token {
+    text: String,
+    start: usize,
+    end: usize,
+}
+
+// Words and numbers with their byte range in the original text. Leading and trailing dots,
+// dashes and colons are dropped so "Nov." and "Friday:" read as plain words.
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (index, c) in text.char_indices() {
+        let in_word = c.is_alphanumeric() || matches!(c, ':' | '/' | '-' | '.');
+        match (in_word, start) {
+            (true, None) => start = Some(index),
+            (false, Some(from)) => {
+                spans.push((from, index));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    spans.extend(start.map(|from| (from, text.len())));
+    let is_edge = |c: char| matches!(c, '.' | '-' | ':');
+    spans
+        .into_iter()
+        .filter_map(|(from, to)| {
+            let word = &text[from..to];
+            let trimmed = word.trim_matches(is_edge);
+            let start = from + word.len() - word.trim_start_matches(is_edge).len();
+            (!trimmed.is_empty()).then(|| Token {
+                text: trimmed.to_lowercase(),
+                start,
+                end: start + trimmed.len(),
+            })
+        })
+        .collect()
+}
+
+// "nov", "november" and "sept" all count
+fn month(word: &str) -> Option<u32> {
+    if word.len() < 3 {
+        return None;
+    }
+    let word = if word == "sept" { "sep" } else { word };
+    MONTHS.iter().position(|name| name.starts_with(word)).map(|index| index as u32 + 1)
+}
+
+// Abbreviations that double as everyday words ("sat", "wed", "sun") have to be spelled out
+fn weekday(word: &str) -> Option<Weekday> {
+    if word.len() < 3 || matches!(word, "sat" | "wed" | "sun") {
+        return None;
+    }
+    let index = WEEKDAYS.iter().position(|name| name.starts_with(word))?;
+    Weekday::try_from(index as u8).ok()
+}
+
+// "20", "20th", "1st"
+fn day_number(word: &str) -> Option<u32> {
+    let digits = ["st", "nd", "rd", "th"].iter().find_map(|suffix| word.strip_suffix(suffix)).unwrap_or(word);
+    digits.parse().ok().filter(|day| (1..=31).contains(day))
+}
+
+fn year_number(word: &str) -> Option<i32> {
+    let year: i32 = word.parse().ok()?;
+    match word.len() {
+        4 => Some(year),
+        2 => Some(2000 + year),
+        _ => None,
+    }
+}
+
+// A date without a year is the next time it comes round
+fn date_without_year(month: u32, day: u32, today: NaiveDate) -> Option<NaiveDate> {
+    let this_year = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+    if this_year >= today {
+        Some(this_year)
+    } else {
+        NaiveDate::from_ymd_opt(today.year() + 1, month, day)
+    }
+}
+
+fn numeric_date(word: &str, today: NaiveDate) -> Option<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(word, "%Y-%m-%d") {
+        return Some(date);
+    }
+    // "1.5" is more likely a number than a date, so dots need all three parts
+    let parts: Vec<&str> = word.split(['/', '.']).collect();
+    let dotted = word.contains('.');
+    if !(2..=3).contains(&parts.len()) || (dotted && parts.len() != 3) {
+        return None;
+    }
+    let first: u32 = parts[0].parse().ok()?;
+    let second: u32 = parts[1].parse().ok()?;
+    // The locale decides unless one of the numbers can only be a day
+    let (month, day) = if first > 12 {
+        (second, first)
+    } else if second > 12 || format::month_first() {
+        (first, second)
+    } else {
+        (second, first)
+    };
+    match parts.get(2) {
+        Some(year) => NaiveDate::from_ymd_opt(year_number(year)?, month, day),
+        None => date_without_year(month, day, today),
+    }
+}
+
+// Matches a date starting at `tokens[0]`, returning it with the number of tokens used
+fn match_date(tokens: &[Token], today: NaiveDate) -> Option<(NaiveDate, usize)> {
+    let word = |index: usize| tokens.get(index).map(|token| token.text.as_str());
+    let first = word(0)?;
+    match first {
+        "today" | "tonight" | "eod" => return Some((today, 1)),
+        "tomorrow" => return Some((today.succ_opt()?, 1)),
+        "this" => {
+            if let Some(weekday) = word(1).and_then(weekday) {
+                return Some((next_or_today(today, weekday), 2));
+            }
+        }
+        "next" => {
+            if let Some(weekday) = word(1).and_then(weekday) {
+                return Some((next_weekday(today, weekday), 2));
+            }
+            if word(1) == Some("week") {
+                return Some((next_weekday(today, Weekday::Mon), 2));
+            }
+            if word(1) == Some("month") {
+                return Some((today.with_day(1)?.checked_add_months(Months::new(1))?, 2));
+            }
+        }
+        "end" if word(1) == Some("of") => {
+            let offset = if word(2) == Some("the") { 3 } else { 2 };
+            let date = match word(offset)? {
+                "day" => today,
+                "week" => next_or_today(today, Weekday::Fri),
+                "month" => today.with_day(1)?.checked_add_months(Months::new(1))?.pred_opt()?,
+                "year" => NaiveDate::from_ymd_opt(today.year(), 12, 31)?,
+                _ => return None,
+            };
+            return Some((date, offset + 1));
+        }
+        _ => {}
+    }
+    if let Some(weekday) = weekday(first) {
+        return Some((next_or_today(today, weekday), 1));
+    }
+    if let Some(date) = numeric_date(first, today) {
+        return Some((date, 1));
+    }
+    // "November 20", "Nov 20th 2026"
+    if let (Some(month), Some(day)) = (month(first), word(1).and_then(day_number)) {
+        return Some(match word(2).and_then(year_number) {
+            Some(year) => (NaiveDate::from_ymd_opt(year, month, day)?, 3),
+            None => (date_without_year(month, day, today)?, 2),
+        });
+    }
+    // "20 November", "20th of Nov 2026"
+    if let Some(day) = day_number(first) {
+        let offset = if word(1) == Some("of") { 2 } else { 1 };
+        if let Some(month) = word(offset).and_then(month) {
+            return Some(match word(offset + 1).and_then(year_number) {
+                Some(year) => (NaiveDate::from_ymd_opt(year, month, day)?, offset + 2),
+                None => (date_without_year(month, day, today)?, offset + 1),
+            });
+        }
+    }
+    None
+}
+
+// "Friday" on a Friday means today; "next Friday" always means a later one
+fn next_or_today(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+    today + Days::new(ahead.into())
+}
+
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    next_or_today(today.succ_opt().unwrap_or(today), weekday)
+}
+
+// "5pm", "5 pm", "5:30pm", "17:00", "noon"
+fn match_time(tokens: &[Token]) -> Option<(NaiveTime, usize)> {
+    let word = |index: usize| tokens.get(index).map(|token| token.text.as_str());
+    let first = word(0)?;
+    match first {
+        "noon" | "midday" => return Some((NaiveTime::from_hms_opt(12, 0, 0)?, 1)),
+        "midnight" => return Some((NaiveTime::from_hms_opt(23, 59, 0)?, 1)),
+        _ => {}
+    }
+    let (clock, meridiem, used) = match ["am", "pm"].iter().find_map(|m| first.strip_suffix(m).map(|clock| (clock, *m))) {
+        Some((clock, meridiem)) if !clock.is_empty() => (clock, Some(meridiem), 1),
+        _ => match word(1) {
+            Some(next @ ("am" | "pm")) => (first, Some(next), 2),
+            _ => (first, None, 1),
+        },
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) => (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?),
+        // A bare number is only a time with am/pm after it
+        None if meridiem.is_some() => (clock.parse::<u32>().ok()?, 0),
+        None => return None,
+    };
+    let hour = match meridiem {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some("am") => hour % 12,
+        Some(_) => hour % 12 + 12,
+        None => hour,
+    };
+    Some((NaiveTime::from_hms_opt(hour, minute, 0)?, used))
+}
+
+// "in 3 days", "in 2 hours"
+fn match_relative(tokens: &[Token]) -> Option<(Duration, usize)> {
+    let word = |index: usize| tokens.get(index).map(|token| token.text.as_str());
+    if word(0)? != "in" {
+        return None;
+    }
+    let (amount, offset) = match word(1)? {
+        "a" | "an" | "one" => (1, 2),
+        number => (number.parse::<i64>().ok()?, 2),
+    };
+    let unit = word(offset)?;
+    let unit = unit.strip_suffix('s').unwrap_or(unit);
+    let duration = match unit {
+        "minute" | "min" => Duration::minutes(amount),
+        "hour" | "hr" => Duration::hours(amount),
+        "day" => Duration::days(amount),
+        "week" | "wk" => Duration::weeks(amount),
+        "month" => Duration::days(amount * 30),
+        _ => return None,
+    };
+    Some((duration, offset + 1))
+}
+
+fn to_utc(date: NaiveDate, time: NaiveTime) -> Option<DateTime<Utc>> {
+    Local.from_local_datetime(&date.and_time(time)).earliest().map(|at| at.with_timezone(&Utc))
+}
+
+// Whatever is left of the line once the date is cut out and the joining words are trimmed
+fn label_from(text: &str, start: usize, end: usize) -> String {
+    let line_start = text[..start].rfind('\n').map_or(0, |index| index + 1);
+    let line_end = text[end..].find('\n').map_or(text.len(), |index| end + index);
+    let remainder = format!("{} {}", &text[line_start..start], &text[end..line_end]);
+    let mut words: Vec<&str> = remainder.split_whitespace().collect();
+    let is_filler = |word: &str| {
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+        word.is_empty() || FILLER_WORDS.contains(&word.as_str())
+    };
+    while words.last().is_some_and(|word| is_filler(word)) {
+        words.pop();
+    }
+    while words.first().is_some_and(|word| is_filler(word)) {
+        words.remove(0);
+    }
+    let label = words.join(" ");
+    let label = label.trim_matches(|c: char| c.is_whitespace() || matches!(c, ',' | ':' | '-' | '—' | '.'));
+    label.chars().take(MAX_LABEL_CHARS).collect()
+}
+
+// Looks for the first deadline in English text such as an email or chat message. A date
+// without a time means the end of that day; a time on its own means the next time it comes up.
+pub fn parse_deadline(text: &str, now: DateTime<Utc>) -> Option<ParsedDeadline> {
+    if let Ok(end) = DateTime::parse_from_rfc3339(text.trim()) {
+        return Some(ParsedDeadline { label: String::new(), end_date: end.with_timezone(&Utc) });
+    }
+    let tokens = tokenize(text);
+    let local_now = now.with_timezone(&Local);
+    let today = local_now.date_naive();
+    let end_of_day = NaiveTime::from_hms_opt(23, 59, 0)?;
+
+    for index in 0..tokens.len() {
+        let rest = &tokens[index..];
+        let span = |used: usize| (tokens[index].start, tokens[index + used - 1].end);
+
+        if let Some((duration, used)) = match_relative(rest) {
+            let (start, end) = span(used);
+            return Some(ParsedDeadline { label: label_from(text, start, end), end_date: now + duration });
+        }
+
+        if let Some((date, used)) = match_date(rest, today) {
+            let (start, mut end) = span(used);
+            let mut time = None;
+            let after = &rest[used..];
+            let skip = usize::from(after.first().is_some_and(|token| token.text == "at"));
+            if let Some((found, time_used)) = after.get(skip..).and_then(match_time) {
+                time = Some(found);
+                end = after[skip + time_used - 1].end;
+            }
+            let end_date = to_utc(date, time.unwrap_or(end_of_day))?;
+            return Some(ParsedDeadline { label: label_from(text, start, end), end_date });
+        }
+
+        if let Some((time, used)) = match_time(rest) {
+            // "5pm tomorrow"
+            let (start, mut end) = span(used);
+            let mut date = today;
+            if let Some((found, date_used)) = match_date(&rest[used..], today) {
+                date = found;
+                end = rest[used + date_used - 1].end;
+            } else if time <= local_now.time() {
+                date = today.succ_opt()?;
+            }
+            let end_date = to_utc(date, time)?;
+            return Some(ParsedDeadline { label: label_from(text, start, end), end_date });
+        }
+    }
+    None
+}
@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::notifier::{Notification, Notifier};
+
+// A plugin that hasn't exited by then is killed, so a hung script can't pile up processes
+const PLUGIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Serialize, Clone)]
+pub struct PluginRun {
+    pub at: DateTime<Utc>,
+    // What went wrong, from the exit status and the last line the plugin wrote to stderr
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct PluginStatus {
+    pub name: String,
+    pub path: PathBuf,
+    pub last_run: Option<PluginRun>,
+}
+
+#[derive(Default)]
+pub struct PluginState {
+    dir: Arc<Mutex<Option<PathBuf>>>,
+    runs: Arc<Mutex<BTreeMap<String, PluginRun>>>,
+}
+
+impl PluginState {
+    fn runs(&self) -> Result<MutexGuard<'_, BTreeMap<String, PluginRun>>, String> {
+        self.runs.lock().map_err(|e| format!("Failed to lock plugin runs: {}", e))
+    }
+
+    // Creates the folder up front so users have somewhere to drop plugins
+    pub fn set_dir(&self, dir: PathBuf) -> Result<(), String> {
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create plugins folder: {}", e))?;
+        *self.dir.lock().map_err(|e| format!("Failed to lock plugins folder: {}", e))? = Some(dir);
+        Ok(())
+    }
+
+    fn dir(&self) -> Option<PathBuf> {
+        self.dir.lock().ok()?.clone()
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(windows)]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ["exe", "bat", "cmd"].contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+// Looked up on every notification, so plugins can be added or removed without a restart
+pub fn discover(app: &AppHandle) -> Vec<PathBuf> {
+    let Some(dir) = app.state::<PluginState>().dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    let mut plugins: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| is_executable(path))
+        .collect();
+    plugins.sort();
+    plugins
+}
+
+fn plugin_name(path: &Path) -> String {
+    path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+fn run(path: &Path, payload: &[u8]) -> Result<(), String> {
+    let mut command = Command::new(path);
+    command.stdin(Stdio::piped()).stdout(Stdio::null()).stderr(Stdio::piped());
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+    let mut child = command.spawn().map_err(|e| format!("Failed to start plugin: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A plugin that ignores its input closes the pipe early; that's not a failure
+        let _ = stdin.write_all(payload);
+    }
+    // Read while the plugin runs, as one that fills the pipe would otherwise block until the timeout
+    let stderr = child.stderr.take().map(|mut pipe| {
+        std::thread::spawn(move || {
+            let mut output = String::new();
+            let _ = pipe.read_to_string(&mut output);
+            output
+        })
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait().map_err(|e| format!("Failed to wait for plugin: {}", e))? {
+            Some(status) => break status,
+            None if started.elapsed() >= PLUGIN_TIMEOUT => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("Plugin didn't finish within {} seconds", PLUGIN_TIMEOUT.as_secs()));
+            }
+            None => std::thread::sleep(Duration::from_millis(100)),
+        }
+    };
+    if status.success() {
+        return Ok(());
+    }
+    let stderr = stderr.and_then(|reader| reader.join().ok()).unwrap_or_default();
+    match stderr.lines().rev().find(|line| !line.trim().is_empty()) {
+        Some(line) => Err(format!("Plugin exited with {}: {}", status, line.trim())),
+        None => Err(format!("Plugin exited with {}", status)),
+    }
+}
+
+// Runs an executable from the plugins folder with the notification as JSON on stdin
+pub struct PluginNotifier {
+    pub path: PathBuf,
+}
+
+impl Notifier for PluginNotifier {
+    fn deliver(&self, app: &AppHandle, notification: &Notification) -> Result<(), String> {
+        let payload = serde_json::to_vec(notification).map_err(|e| format!("Failed to serialize notification: {}", e))?;
+        let path = self.path.clone();
+        let app = app.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let result = run(&path, &payload);
+            if let Err(e) = &result {
+                eprintln!("Failed to run notifier plugin {}: {}", path.display(), e);
+            }
+            let state = app.state::<PluginState>();
+            match state.runs() {
+                Ok(mut runs) => {
+                    runs.insert(plugin_name(&path), PluginRun { at: Utc::now(), error: result.err() });
+                }
+                Err(e) => eprintln!("{}", e),
+            };
+        });
+        Ok(())
+    }
+}
+
+// Every plugin currently in the folder, with how its last run went
+pub fn status(app: &AppHandle) -> Vec<PluginStatus> {
+    let runs = app.state::<PluginState>().runs().map(|runs| runs.clone()).unwrap_or_default();
+    discover(app)
+        .into_iter()
+        .map(|path| {
+            let name = plugin_name(&path);
+            PluginStatus {
+                last_run: runs.get(&name).cloned(),
+                name,
+                path,
+            }
+        })
+        .collect()
+}
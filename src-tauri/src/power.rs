@@ -0,0 +1,181 @@
+use std::sync::{Mutex, MutexGuard};
+use chrono::Duration as ChronoDuration;
+use tauri::{AppHandle, Manager};
+use tokio::time::{interval, Duration};
+
+use crate::audio::AudioState;
+use crate::clock;
+use crate::i18n::t;
+use crate::settings::SettingsState;
+use crate::timers::TimerState;
+use crate::{main_window, primary_end_date, NotificationState};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum InhibitKind {
+    // Keep the machine from suspending
+    Sleep,
+    // Keep the screen on and the screensaver away
+    Display,
+}
+
+// An OS-level inhibition that lasts until this value is dropped
+pub struct Inhibitor {
+    #[cfg(windows)]
+    _release: std::sync::mpsc::Sender<()>,
+    #[cfg(not(windows))]
+    child: std::process::Child,
+}
+
+impl Inhibitor {
+    // The execution state belongs to the calling thread, so a dedicated thread holds it until
+    // the sender is dropped
+    #[cfg(windows)]
+    pub fn acquire(kind: InhibitKind, _reason: &str) -> Result<Self, String> {
+        use windows_sys::Win32::System::Power::{
+            SetThreadExecutionState, ES_CONTINUOUS, ES_DISPLAY_REQUIRED, ES_SYSTEM_REQUIRED,
+        };
+
+        let flags = match kind {
+            InhibitKind::Sleep => ES_CONTINUOUS | ES_SYSTEM_REQUIRED,
+            InhibitKind::Display => ES_CONTINUOUS | ES_SYSTEM_REQUIRED | ES_DISPLAY_REQUIRED,
+        };
+        let (release, released) = std::sync::mpsc::channel::<()>();
+        let (started, acquired) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            // SAFETY: plain flag arguments; the previous state is restored before the thread exits
+            let ok = unsafe { SetThreadExecutionState(flags) } != 0;
+            let _ = started.send(ok);
+            if ok {
+                let _ = released.recv();
+                unsafe { SetThreadExecutionState(ES_CONTINUOUS) };
+            }
+        });
+        match acquired.recv() {
+            Ok(true) => Ok(Self { _release: release }),
+            _ => Err("Failed to set thread execution state".to_string()),
+        }
+    }
+
+    // caffeinate exits on its own if we crash (-w)
+    #[cfg(target_os = "macos")]
+    pub fn acquire(kind: InhibitKind, _reason: &str) -> Result<Self, String> {
+        let flag = match kind {
+            InhibitKind::Sleep => "-i",
+            InhibitKind::Display => "-d",
+        };
+        let child = std::process::Command::new("caffeinate")
+            .args([flag, "-w", &std::process::id().to_string()])
+            .spawn()
+            .map_err(|e| format!("Failed to run caffeinate: {}", e))?;
+        Ok(Self { child })
+    }
+
+    // logind holds the lock for as long as the wrapped command runs; the command watches our
+    // PID so the lock can't outlive a crash
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn acquire(kind: InhibitKind, reason: &str) -> Result<Self, String> {
+        let what = match kind {
+            InhibitKind::Sleep => "--what=sleep",
+            InhibitKind::Display => "--what=idle:sleep",
+        };
+        let watch = format!("while kill -0 {} 2>/dev/null; do sleep 5; done", std::process::id());
+        let child = std::process::Command::new("systemd-inhibit")
+            .args([what, "--who=Hourglass", &format!("--why={}", reason), "--mode=block", "sh", "-c", &watch])
+            .spawn()
+            .map_err(|e| format!("Failed to run systemd-inhibit: {}", e))?;
+        Ok(Self { child })
+    }
+}
+
+#[cfg(not(windows))]
+impl Drop for Inhibitor {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[derive(Default)]
+pub struct PowerState {
+    sleep: Mutex<Option<Inhibitor>>,
+    display: Mutex<Option<Inhibitor>>,
+}
+
+impl PowerState {
+    fn slot(&self, kind: InhibitKind) -> Result<MutexGuard<'_, Option<Inhibitor>>, String> {
+        let slot = match kind {
+            InhibitKind::Sleep => &self.sleep,
+            InhibitKind::Display => &self.display,
+        };
+        slot.lock().map_err(|e| format!("Failed to lock power state: {}", e))
+    }
+
+    // Takes or releases the inhibition so it matches `wanted`; repeated calls are cheap
+    pub fn hold(&self, kind: InhibitKind, wanted: bool, reason: &str) -> Result<(), String> {
+        let mut slot = self.slot(kind)?;
+        match (wanted, slot.is_some()) {
+            (true, false) => *slot = Some(Inhibitor::acquire(kind, reason)?),
+            (false, true) => *slot = None,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    // Drops both inhibitions, for when the app exits
+    pub fn release_all(&self) {
+        for kind in [InhibitKind::Sleep, InhibitKind::Display] {
+            if let Ok(mut slot) = self.slot(kind) {
+                slot.take();
+            }
+        }
+    }
+}
+
+// The countdown is being presented rather than just open: full screen or pinned on top
+fn countdown_on_display(app: &AppHandle) -> bool {
+    main_window(app).is_some_and(|window| {
+        window.is_visible().unwrap_or(false)
+            && (window.is_fullscreen().unwrap_or(false) || window.is_always_on_top().unwrap_or(false))
+    })
+}
+
+// Keeps the machine awake through the final minutes of any countdown and for as long as the
+// expiry alarm rings, so it isn't slept through. Separately, keeps the screen on while the
+// countdown is presented during its final minutes, e.g. on a projector at a live event.
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut tick = interval(Duration::from_secs(30));
+        loop {
+            tick.tick().await;
+            let (keep_awake, keep_awake_minutes, keep_display_on, keep_display_on_minutes) =
+                match app.state::<SettingsState>().lock() {
+                    Ok(settings) => (
+                        settings.keep_awake,
+                        settings.keep_awake_minutes,
+                        settings.keep_display_on,
+                        settings.keep_display_on_minutes,
+                    ),
+                    Err(e) => {
+                        eprintln!("Failed to lock settings in keep-awake task: {}", e);
+                        continue;
+                    }
+                };
+            let now = clock::now();
+            let primary = primary_end_date(&app.state::<NotificationState>()).filter(|end| *end > now);
+            let timers = app.state::<TimerState>().lock().ok().and_then(|store| store.next_expiry(now));
+            let next_deadline = [primary, timers].into_iter().flatten().min();
+            let within = |minutes: u32| next_deadline.is_some_and(|end| end <= now + ChronoDuration::minutes(minutes.into()));
+            let power = app.state::<PowerState>();
+
+            let wanted = keep_awake && (within(keep_awake_minutes) || app.state::<AudioState>().is_ringing());
+            if let Err(e) = power.hold(InhibitKind::Sleep, wanted, &t("keep-awake-reason")) {
+                eprintln!("Failed to update sleep inhibition: {}", e);
+            }
+
+            let wanted = keep_display_on && within(keep_display_on_minutes) && countdown_on_display(&app);
+            if let Err(e) = power.hold(InhibitKind::Display, wanted, &t("keep-display-on-reason")) {
+                eprintln!("Failed to update screensaver inhibition: {}", e);
+            }
+        }
+    });
+}
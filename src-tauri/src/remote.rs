@@ -0,0 +1,142 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::i18n::{t, t_args};
+use crate::integrations::parse_due;
+use crate::notifier::EventKind;
+use crate::timers::{Timer, TimerState};
+use crate::{clock, format, http, import, notifier, send_notification};
+
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+// Keys a published JSON deadline may use for its end date
+const END_KEYS: [&str; 6] = ["end", "end_date", "endDate", "deadline", "due", "due_date"];
+
+// A URL publishing the authoritative end date of a timer, e.g. a team's shared deadline
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RemoteSubscription {
+    pub url: String,
+    pub last_checked: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+// A JSON document with the end date under one of END_KEYS, or a bare date string. An .ics
+// calendar counts from its first event's end.
+fn parse_remote_end(body: &str) -> Result<DateTime<Utc>, String> {
+    if body.trim_start().starts_with("BEGIN:VCALENDAR") {
+        return import::parse_ics(body)?
+            .first()
+            .map(|event| event.end_date)
+            .ok_or_else(|| t("error-no-events-in-file"));
+    }
+    let value: serde_json::Value = serde_json::from_str(body).map_err(|e| format!("Failed to parse remote deadline: {}", e))?;
+    let end = match &value {
+        serde_json::Value::String(end) => Some(end.as_str()),
+        serde_json::Value::Object(fields) => END_KEYS.iter().find_map(|key| fields.get(*key).and_then(|end| end.as_str())),
+        _ => None,
+    };
+    end.and_then(parse_due).ok_or_else(|| t("error-remote-no-end-date"))
+}
+
+async fn fetch_end(url: &str) -> Result<DateTime<Utc>, String> {
+    let response = http::client()?
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("{} answered {}", url, response.status()));
+    }
+    let body = response.text().await.map_err(|e| format!("Failed to read remote deadline: {}", e))?;
+    parse_remote_end(&body)
+}
+
+// Fetches one timer's remote deadline and moves the timer if it changed, telling the user
+async fn refresh(app: &AppHandle, id: u64, url: String) -> Result<Timer, String> {
+    let result = fetch_end(&url).await;
+    let now = clock::now();
+    let state = app.state::<TimerState>();
+    let mut moved = None;
+    let timer = state.lock()?.update(id, |timer| {
+        let Some(subscription) = timer.subscription.as_mut().filter(|subscription| subscription.url == url) else {
+            return Ok(());
+        };
+        subscription.last_checked = Some(now);
+        subscription.last_error = result.as_ref().err().cloned();
+        if let Ok(end) = result {
+            if end != timer.end_date {
+                moved = Some(timer.end_date);
+                if end <= timer.start_date {
+                    timer.start_date = now.min(end);
+                }
+                timer.reschedule(end, now);
+            }
+        }
+        Ok(())
+    })?;
+    if let Some(from) = moved {
+        let body = t_args(
+            "remote-deadline-moved",
+            &[("from", format::format_date_time(from).into()), ("to", format::format_date_time(timer.end_date).into())],
+        );
+        send_notification(app, EventKind::System, &timer.display_title(), &body);
+    }
+    Ok(timer)
+}
+
+// Checks every subscribed timer now and then
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let subscribed: Vec<(u64, String)> = app
+                .state::<TimerState>()
+                .lock()
+                .map(|store| {
+                    store
+                        .timers
+                        .iter()
+                        .filter(|timer| timer.is_active())
+                        .filter_map(|timer| timer.subscription.as_ref().map(|subscription| (timer.id, subscription.url.clone())))
+                        .collect()
+                })
+                .unwrap_or_default();
+            for (id, url) in subscribed {
+                if let Err(e) = refresh(&app, id, url).await {
+                    eprintln!("Failed to refresh remote deadline of timer {}: {}", id, e);
+                }
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+}
+
+// Follows `url` for the timer's end date from now on, or stops following with None. The first
+// check happens right away so a bad URL shows up immediately.
+#[tauri::command]
+pub async fn subscribe_timer(app: AppHandle, state: State<'_, TimerState>, id: u64, url: Option<String>) -> Result<Timer, String> {
+    let url = url.map(|url| url.trim().to_string()).filter(|url| !url.is_empty());
+    if let Some(url) = url.as_ref().filter(|url| !notifier::validate_webhook_url(url)) {
+        return Err(t_args("error-invalid-remote-url", &[("url", url.clone().into())]));
+    }
+    let timer = state.lock()?.update(id, |timer| {
+        timer.subscription = url.clone().map(|url| RemoteSubscription { url, last_checked: None, last_error: None });
+        Ok(())
+    })?;
+    match url {
+        Some(url) => refresh(&app, id, url).await,
+        None => Ok(timer),
+    }
+}
+
+#[tauri::command]
+pub async fn refresh_remote_deadline(app: AppHandle, state: State<'_, TimerState>, id: u64) -> Result<Timer, String> {
+    let url = state
+        .lock()?
+        .get(id)
+        .ok_or_else(|| t_args("error-timer-not-found", &[("id", id.into())]))?
+        .subscription
+        .as_ref()
+        .map(|subscription| subscription.url.clone())
+        .ok_or_else(|| t("error-timer-not-subscribed"))?;
+    refresh(&app, id, url).await
+}
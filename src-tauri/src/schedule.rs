@@ -0,0 +1,36 @@
+pub use hourglass_core::schedule::{CronSchedule, ReminderSchedule, DEFAULT_REMINDER_INTERVAL_SECS};
+
+use chrono::Duration;
+
+use crate::timers::{ScheduleSpec, Timer};
+
+fn parse_cron(timer: &Timer, expression: &str) -> Option<ReminderSchedule> {
+    match CronSchedule::parse(expression) {
+        Ok(cron) => Some(ReminderSchedule::Cron(cron)),
+        Err(e) => {
+            eprintln!("Ignoring schedule for timer {}: {}", timer.id, e);
+            None
+        }
+    }
+}
+
+// Expressions are validated when they're set, so a stored one that no longer parses falls back
+// to the default cadence rather than going silent. That cadence follows the timer's importance.
+pub fn for_timer(timer: &Timer) -> ReminderSchedule {
+    let default = ReminderSchedule::Interval(timer.importance.reminder_interval());
+    if !timer.reminder_schedules.is_empty() {
+        let schedules: Vec<ReminderSchedule> = timer
+            .reminder_schedules
+            .iter()
+            .filter_map(|spec| match spec {
+                ScheduleSpec::Interval { seconds } => Some(ReminderSchedule::Interval(Duration::seconds(*seconds))),
+                ScheduleSpec::Cron { expression } => parse_cron(timer, expression),
+            })
+            .collect();
+        return if schedules.is_empty() { default } else { ReminderSchedule::All(schedules) };
+    }
+    match &timer.reminder_cron {
+        Some(expression) => parse_cron(timer, expression).unwrap_or(default),
+        None => default,
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::sync::Mutex as AsyncMutex;
+
+pub use hourglass_core::queue::{EventQueue, EventSource};
+
+pub enum SchedulerMessage {
+    // Something about this source changed; recompute when it next needs attention
+    Replan(EventSource),
+    // The whole store was replaced (e.g. loaded from disk)
+    ReplanAll,
+}
+
+#[derive(Clone, Default)]
+pub struct SchedulerSender(Option<UnboundedSender<SchedulerMessage>>);
+
+impl SchedulerSender {
+    // The scheduler may not be running (notifications off); its queue is rebuilt from scratch
+    // when it starts, so dropped messages don't matter
+    pub fn send(&self, message: SchedulerMessage) {
+        if let Some(sender) = &self.0 {
+            let _ = sender.send(message);
+        }
+    }
+}
+
+pub struct SchedulerState {
+    pub sender: SchedulerSender,
+    // Lives outside the task so a restarted task picks up messages sent while it was stopped
+    pub receiver: Arc<AsyncMutex<UnboundedReceiver<SchedulerMessage>>>,
+}
+
+impl Default for SchedulerState {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        Self {
+            sender: SchedulerSender(Some(sender)),
+            receiver: Arc::new(AsyncMutex::new(receiver)),
+        }
+    }
+}
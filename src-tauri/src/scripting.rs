@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use chrono::{DateTime, Utc};
+use rhai::{CallFnOptions, Dynamic, Engine, EvalAltResult, Scope};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::notifier::{self, EventKind};
+use crate::settings::SettingsState;
+use crate::timers::TimerState;
+use crate::{http, send_notification};
+
+// Keeps a runaway loop from tying up a thread; well beyond what a hook needs
+const MAX_OPERATIONS: u64 = 500_000;
+
+#[derive(Clone, Copy)]
+pub enum ScriptEvent {
+    TimerCreated,
+    Milestone,
+    Expiry,
+}
+
+impl ScriptEvent {
+    // The function a script defines to handle the event
+    fn hook(self) -> &'static str {
+        match self {
+            ScriptEvent::TimerCreated => "on_timer_created",
+            ScriptEvent::Milestone => "on_milestone",
+            ScriptEvent::Expiry => "on_expiry",
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct ScriptRun {
+    pub at: DateTime<Utc>,
+    pub hook: String,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ScriptStatus {
+    pub name: String,
+    pub path: PathBuf,
+    pub last_run: Option<ScriptRun>,
+}
+
+#[derive(Default)]
+pub struct ScriptState {
+    dir: Arc<Mutex<Option<PathBuf>>>,
+    runs: Arc<Mutex<BTreeMap<String, ScriptRun>>>,
+}
+
+impl ScriptState {
+    fn runs(&self) -> Result<MutexGuard<'_, BTreeMap<String, ScriptRun>>, String> {
+        self.runs.lock().map_err(|e| format!("Failed to lock script runs: {}", e))
+    }
+
+    pub fn set_dir(&self, dir: PathBuf) -> Result<(), String> {
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create scripts folder: {}", e))?;
+        *self.dir.lock().map_err(|e| format!("Failed to lock scripts folder: {}", e))? = Some(dir);
+        Ok(())
+    }
+
+    fn dir(&self) -> Option<PathBuf> {
+        self.dir.lock().ok()?.clone()
+    }
+}
+
+// The .rhai files in the scripts folder, read again each time so edits apply right away
+fn discover(app: &AppHandle) -> Vec<PathBuf> {
+    let Some(dir) = app.state::<ScriptState>().dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    let mut scripts: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "rhai"))
+        .collect();
+    scripts.sort();
+    scripts
+}
+
+fn script_name(path: &Path) -> String {
+    path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+fn script_error(message: String) -> Box<EvalAltResult> {
+    message.into()
+}
+
+// Rhai has no file, process or network access of its own, so scripts can only reach what's
+// registered here: reading timers, sending a notification and calling a webhook
+fn engine(app: &AppHandle, name: String) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(64 * 1024);
+    engine.set_max_array_size(10_000);
+    engine.set_max_map_size(10_000);
+    engine.on_print(move |text| eprintln!("[{}] {}", name, text));
+
+    let handle = app.clone();
+    engine.register_fn("timers", move || -> Result<Dynamic, Box<EvalAltResult>> {
+        let timers: Vec<_> = handle
+            .state::<TimerState>()
+            .lock()
+            .map_err(script_error)?
+            .timers
+            .iter()
+            .filter(|timer| timer.is_active())
+            .cloned()
+            .collect();
+        rhai::serde::to_dynamic(timers)
+    });
+
+    let handle = app.clone();
+    engine.register_fn("notify", move |title: &str, body: &str| {
+        send_notification(&handle, EventKind::System, title, body);
+    });
+
+    engine.register_fn("webhook", move |url: &str, payload: Dynamic| -> Result<(), Box<EvalAltResult>> {
+        if !notifier::validate_webhook_url(url) {
+            return Err(script_error(format!("Not an http:// or https:// address: {}", url)));
+        }
+        let body: serde_json::Value = rhai::serde::from_dynamic(&payload)?;
+        let request = http::client().map_err(script_error)?.post(url).json(&body);
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = request.send().await {
+                eprintln!("Failed to call webhook from script: {}", e);
+            }
+        });
+        Ok(())
+    });
+    engine
+}
+
+fn run(app: &AppHandle, path: &Path, hook: &str, payload: &serde_json::Value) -> Result<bool, String> {
+    let source = std::fs::read_to_string(path).map_err(|e| format!("Failed to read script: {}", e))?;
+    let engine = engine(app, script_name(path));
+    let ast = engine.compile(&source).map_err(|e| format!("Failed to compile script: {}", e))?;
+    if !ast.iter_functions().any(|function| function.name == hook) {
+        return Ok(false);
+    }
+    let payload = rhai::serde::to_dynamic(payload).map_err(|e| format!("Failed to pass event to script: {}", e))?;
+    // Only the hook runs, not the script's top-level statements
+    engine
+        .call_fn_with_options::<Dynamic>(CallFnOptions::new().eval_ast(false), &mut Scope::new(), &ast, hook, (payload,))
+        .map(|_| true)
+        .map_err(|e| format!("Script failed: {}", e))
+}
+
+// Calls the event's hook in every script that defines it, in the background and only when
+// scripting is turned on
+pub fn dispatch(app: &AppHandle, event: ScriptEvent, payload: serde_json::Value) {
+    let enabled = app.state::<SettingsState>().lock().is_ok_and(|settings| settings.scripts_enabled);
+    if !enabled {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        for path in discover(&app) {
+            let result = run(&app, &path, event.hook(), &payload);
+            if let Err(e) = &result {
+                eprintln!("Failed to run script {}: {}", path.display(), e);
+            }
+            if matches!(result, Ok(false)) {
+                continue;
+            }
+            let state = app.state::<ScriptState>();
+            match state.runs() {
+                Ok(mut runs) => {
+                    let run = ScriptRun { at: Utc::now(), hook: event.hook().to_string(), error: result.err() };
+                    runs.insert(script_name(&path), run);
+                }
+                Err(e) => eprintln!("{}", e),
+            };
+        }
+    });
+}
+
+// Every script currently in the folder, with how its last hook went
+pub fn status(app: &AppHandle) -> Vec<ScriptStatus> {
+    let runs = app.state::<ScriptState>().runs().map(|runs| runs.clone()).unwrap_or_default();
+    discover(app)
+        .into_iter()
+        .map(|path| {
+            let name = script_name(&path);
+            ScriptStatus {
+                last_run: runs.get(&name).cloned(),
+                name,
+                path,
+            }
+        })
+        .collect()
+}
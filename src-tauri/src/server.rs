@@ -0,0 +1,501 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Mutex;
+use std::time::Instant;
+use std::time::Duration;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{ConnectInfo, Extension, Path, Query, Request, State};
+use axum::http::header::{self, HeaderValue};
+use axum::http::{HeaderMap, Method, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::oneshot;
+
+use crate::api_tokens::ApiTokenState;
+use crate::audit::{AuditEntry, AuditState};
+use crate::clock;
+use crate::embed::{self, EmbedState};
+use crate::kiosk::{self, KioskState};
+use crate::settings::SettingsState;
+use crate::share::{Countdown, CountdownSummary};
+use crate::snapshot::{self, AppSnapshot};
+use crate::NotificationState;
+
+const SETTINGS_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const PUSH_INTERVAL: Duration = Duration::from_secs(1);
+const SNOOZE_MINUTES: i64 = 15;
+const REMOTE_READABLE: [&str; 2] = ["/api/countdown", "/api/streamdeck"];
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+// What a hardware button shows for the main countdown
+#[derive(Serialize, Clone, PartialEq)]
+struct DeckState {
+    title: Option<String>,
+    text: String,
+    percent: Option<u8>,
+    expired: bool,
+    reminders_enabled: bool,
+    snoozed_until: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum DeckAction {
+    // Turn reminders on or off
+    Toggle,
+    // Hold routine reminders for a while
+    Snooze,
+}
+
+// Who a request came from, as worked out by the guard
+#[derive(Clone)]
+struct Caller {
+    client: Option<String>,
+    peer: SocketAddr,
+}
+
+impl Caller {
+    // What rate limits are counted against
+    fn key(&self) -> String {
+        self.client.clone().unwrap_or_else(|| self.peer.ip().to_string())
+    }
+}
+
+// Requests per client in the current minute. Clients are told apart by token, or by address
+// for requests without one.
+#[derive(Default)]
+pub struct RateLimits {
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl RateLimits {
+    fn allow(&self, key: String, limit: u32) -> bool {
+        if limit == 0 {
+            return true;
+        }
+        let Ok(mut windows) = self.windows.lock() else {
+            return true;
+        };
+        let now = Instant::now();
+        windows.retain(|_, (started, _)| now.duration_since(*started) < RATE_WINDOW);
+        let (_, count) = windows.entry(key).or_insert((now, 0));
+        *count += 1;
+        *count <= limit
+    }
+}
+
+#[derive(Deserialize)]
+struct CountdownQuery {
+    timer: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct ActionRequest {
+    action: DeckAction,
+}
+
+fn deck_state(app: &AppHandle) -> DeckState {
+    let now = clock::now();
+    let notifications = app.state::<NotificationState>();
+    let reminders_enabled = notifications.is_enabled.lock().map(|enabled| *enabled).unwrap_or(false);
+    let snoozed_until = notifications.snoozed_until.lock().ok().and_then(|until| *until).filter(|until| *until > now);
+    match Countdown::load(app, None) {
+        Ok(countdown) => {
+            let summary = countdown.summary(now);
+            DeckState {
+                title: summary.title,
+                text: summary.text,
+                percent: summary.percent,
+                expired: summary.expired,
+                reminders_enabled,
+                snoozed_until,
+            }
+        }
+        Err(_) => DeckState {
+            title: None,
+            text: String::new(),
+            percent: None,
+            expired: false,
+            reminders_enabled,
+            snoozed_until,
+        },
+    }
+}
+
+fn action_name(action: DeckAction) -> &'static str {
+    match action {
+        DeckAction::Toggle => "toggle reminders",
+        DeckAction::Snooze => "snooze reminders",
+    }
+}
+
+fn audit(app: &AppHandle, caller: &Caller, action: DeckAction, result: &Result<(), String>) {
+    app.state::<AuditState>().record(AuditEntry {
+        at: Utc::now(),
+        client: caller.client.clone(),
+        address: caller.peer.ip().to_string(),
+        action: action_name(action).to_string(),
+        error: result.as_ref().err().cloned(),
+    });
+}
+
+// Runs an action for a client and writes it to the audit log
+async fn perform_for(app: &AppHandle, caller: &Caller, action: DeckAction) -> Result<(), String> {
+    let result = perform(app, action).await;
+    audit(app, caller, action, &result);
+    result
+}
+
+async fn perform(app: &AppHandle, action: DeckAction) -> Result<(), String> {
+    // Both actions change what a locked display does, so they're refused like the app's own commands
+    kiosk::ensure_unlocked(app)?;
+    let state = app.state::<NotificationState>();
+    match action {
+        DeckAction::Toggle => {
+            let enabled = *state.is_enabled.lock().map_err(|e| format!("Failed to lock notification state: {}", e))?;
+            let running = state
+                .handle
+                .lock()
+                .map_err(|e| format!("Failed to lock task handle: {}", e))?
+                .as_ref()
+                .is_some_and(|task| !task.is_finished());
+            if enabled && running {
+                crate::stop_notifications(app.clone(), state).await
+            } else {
+                crate::start_notifications(app.clone(), state).await
+            }
+        }
+        DeckAction::Snooze => {
+            let mut until = state.snoozed_until.lock().map_err(|e| format!("Failed to lock snooze: {}", e))?;
+            *until = Some(clock::now() + chrono::Duration::minutes(SNOOZE_MINUTES));
+            Ok(())
+        }
+    }
+}
+
+async fn get_state(State(app): State<AppHandle>) -> Json<DeckState> {
+    Json(deck_state(&app))
+}
+
+// The main countdown, or one timer with ?timer=<id>
+async fn get_countdown(
+    State(app): State<AppHandle>,
+    Query(query): Query<CountdownQuery>,
+) -> Result<Json<CountdownSummary>, (StatusCode, String)> {
+    let countdown = Countdown::load(&app, query.timer).map_err(|e| (StatusCode::NOT_FOUND, e))?;
+    Ok(Json(countdown.summary(clock::now())))
+}
+
+// The same view the app's own window loads with; local clients and token holders only
+async fn get_snapshot(State(app): State<AppHandle>) -> Result<Json<AppSnapshot>, (StatusCode, String)> {
+    snapshot::take(&app).await.map(Json).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))
+}
+
+async fn get_embed_page(State(app): State<AppHandle>, Path(token): Path<String>) -> Result<Html<&'static str>, StatusCode> {
+    app.state::<EmbedState>().resolve(&token).map_err(|_| StatusCode::NOT_FOUND)?;
+    Ok(Html(embed::EMBED_PAGE))
+}
+
+// The countdown behind an embed link; the token decides which one, not the caller
+async fn get_embed_countdown(
+    State(app): State<AppHandle>,
+    Path(token): Path<String>,
+) -> Result<Json<CountdownSummary>, (StatusCode, String)> {
+    let timer = app.state::<EmbedState>().resolve(&token).map_err(|_| (StatusCode::NOT_FOUND, String::new()))?;
+    let countdown = Countdown::load(&app, timer).map_err(|e| (StatusCode::NOT_FOUND, e))?;
+    Ok(Json(countdown.summary(clock::now())))
+}
+
+async fn post_action(
+    State(app): State<AppHandle>,
+    Extension(caller): Extension<Caller>,
+    Json(request): Json<ActionRequest>,
+) -> Result<Json<DeckState>, (StatusCode, String)> {
+    perform_for(&app, &caller, request.action).await.map_err(|e| {
+        let status = if app.state::<KioskState>().is_active() { StatusCode::FORBIDDEN } else { StatusCode::INTERNAL_SERVER_ERROR };
+        (status, e)
+    })?;
+    Ok(Json(deck_state(&app)))
+}
+
+fn is_loopback_host(host: &str) -> bool {
+    host == "localhost" || host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+// Browsers don't apply CORS to WebSocket upgrades, so without this any website could open the
+// socket and press the buttons. Clients that send no Origin (like the Stream Deck plugin) and
+// non-web pages are let through; web pages only from the allowed origins or this machine.
+fn socket_origin_allowed(app: &AppHandle, headers: &HeaderMap) -> bool {
+    let Some(origin) = headers.get(header::ORIGIN) else {
+        return true;
+    };
+    let Ok(origin) = origin.to_str() else {
+        return false;
+    };
+    let allowed = app
+        .state::<SettingsState>()
+        .lock()
+        .is_ok_and(|settings| settings.local_api_cors_origins.iter().any(|allowed| allowed.trim_end_matches('/') == origin));
+    if allowed {
+        return true;
+    }
+    match tauri::Url::parse(origin) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => url.host_str().is_some_and(is_loopback_host),
+        Ok(_) => true,
+        // Includes "null", which sandboxed frames send
+        Err(_) => false,
+    }
+}
+
+async fn open_socket(
+    socket: WebSocketUpgrade,
+    State(app): State<AppHandle>,
+    Extension(caller): Extension<Caller>,
+    headers: HeaderMap,
+) -> Response {
+    if !socket_origin_allowed(&app, &headers) {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    socket.on_upgrade(move |socket| run_socket(socket, app, caller))
+}
+
+fn rate_limit(app: &AppHandle) -> u32 {
+    app.state::<SettingsState>().lock().map(|settings| settings.local_api_rate_limit).unwrap_or_default()
+}
+
+// Pushes the state whenever it changes and takes actions as {"action": "..."} messages
+async fn run_socket(mut socket: WebSocket, app: AppHandle, caller: Caller) {
+    let mut tick = tokio::time::interval(PUSH_INTERVAL);
+    let mut last_sent: Option<DeckState> = None;
+    loop {
+        tokio::select! {
+            _ = tick.tick() => {}
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<ActionRequest>(text.as_str()) {
+                    // Only the upgrade went through the guard, so each message counts on its own
+                    Ok(request) if !app.state::<RateLimits>().allow(caller.key(), rate_limit(&app)) => {
+                        audit(&app, &caller, request.action, &Err("Rate limited".to_string()));
+                    }
+                    Ok(request) => {
+                        if let Err(e) = perform_for(&app, &caller, request.action).await {
+                            eprintln!("Failed to run Stream Deck action: {}", e);
+                        }
+                    }
+                    Err(e) => eprintln!("Ignoring malformed Stream Deck message: {}", e),
+                },
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+        }
+        let state = deck_state(&app);
+        if last_sent.as_ref() == Some(&state) {
+            continue;
+        }
+        let Ok(json) = serde_json::to_string(&state) else {
+            continue;
+        };
+        if socket.send(Message::Text(json.into())).await.is_err() {
+            break;
+        }
+        last_sent = Some(state);
+    }
+}
+
+fn router(app: AppHandle) -> Router {
+    Router::new()
+        .route("/api/countdown", get(get_countdown))
+        .route("/api/snapshot", get(get_snapshot))
+        .route("/api/streamdeck", get(get_state))
+        .route("/api/streamdeck/action", post(post_action))
+        .route("/api/streamdeck/ws", get(open_socket))
+        .route("/embed/{token}", get(get_embed_page))
+        .route("/embed/{token}/countdown", get(get_embed_countdown))
+        .layer(middleware::from_fn_with_state(app.clone(), guard))
+        .with_state(app)
+}
+
+fn wanted_address(app: &AppHandle) -> Option<SocketAddr> {
+    app.state::<SettingsState>()
+        .lock()
+        .map(|settings| {
+            let default = if settings.local_api_allow_remote { Ipv4Addr::UNSPECIFIED } else { Ipv4Addr::LOCALHOST };
+            let ip = settings
+                .local_api_bind_address
+                .as_deref()
+                .and_then(|address| address.trim().parse::<IpAddr>().ok())
+                .unwrap_or(IpAddr::V4(default));
+            settings.local_api_enabled.then_some(SocketAddr::from((ip, settings.local_api_port)))
+        })
+        .unwrap_or(None)
+}
+
+// "https://example.com" with nothing after the host and port
+pub fn is_valid_origin(origin: &str) -> bool {
+    tauri::Url::parse(origin).is_ok_and(|url| {
+        matches!(url.scheme(), "http" | "https") && url.origin().ascii_serialization() == origin.trim_end_matches('/')
+    })
+}
+
+// Whether the request was addressed to this machine by a loopback name or the configured bind
+// address, port included
+fn is_local_host(app: &AppHandle, headers: &HeaderMap) -> bool {
+    let Some(host) = headers.get(header::HOST).and_then(|host| host.to_str().ok()) else {
+        return false;
+    };
+    let Some(address) = wanted_address(app) else {
+        return false;
+    };
+    let port = address.port();
+    let mut accepted = vec![
+        format!("localhost:{}", port),
+        SocketAddr::from((Ipv4Addr::LOCALHOST, port)).to_string(),
+        SocketAddr::from((std::net::Ipv6Addr::LOCALHOST, port)).to_string(),
+    ];
+    if !address.ip().is_unspecified() {
+        accepted.push(address.to_string());
+    }
+    // Browsers leave out the default port
+    if port == 80 {
+        accepted.extend(accepted.clone().into_iter().filter_map(|host| host.rsplit_once(':').map(|(name, _)| name.to_string())));
+    }
+    accepted.iter().any(|accepted| accepted.eq_ignore_ascii_case(host))
+}
+
+fn bearer_token(request: &Request) -> Option<&str> {
+    request
+        .headers()
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+fn with_cors(mut response: Response, origin: Option<&HeaderValue>) -> Response {
+    if let Some(origin) = origin {
+        let headers = response.headers_mut();
+        headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin.clone());
+        headers.insert(header::ACCESS_CONTROL_ALLOW_METHODS, HeaderValue::from_static("GET, POST, OPTIONS"));
+        headers.insert(header::ACCESS_CONTROL_ALLOW_HEADERS, HeaderValue::from_static("Authorization, Content-Type"));
+        headers.insert(header::VARY, HeaderValue::from_static("Origin"));
+    }
+    response
+}
+
+// Decides who may do what:
+// - Browsers may only call in from pages on the allowed origins, served by us or loaded locally,
+//   so a random website can't drive the API through someone's browser.
+// - A bearer token unlocks everything. Without one, other machines may only read the countdown,
+//   and nobody gets in at all when tokens are required.
+// - Embed pages carry their own token in the path and are always read-only.
+async fn guard(State(app): State<AppHandle>, ConnectInfo(peer): ConnectInfo<SocketAddr>, mut request: Request, next: Next) -> Response {
+    let (require_token, cors_origins, rate_limit, allow_remote) = match app.state::<SettingsState>().lock() {
+        Ok(settings) => (
+            settings.local_api_require_token,
+            settings.local_api_cors_origins.clone(),
+            settings.local_api_rate_limit,
+            settings.local_api_allow_remote,
+        ),
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+    let local_host = is_local_host(&app, request.headers());
+
+    let origin = request.headers().get(header::ORIGIN).cloned();
+    let allowed_origin = origin.clone().filter(|origin| {
+        origin.to_str().is_ok_and(|origin| cors_origins.iter().any(|allowed| allowed.trim_end_matches('/') == origin))
+    });
+    if let Some(origin) = origin.as_ref().filter(|_| allowed_origin.is_none()) {
+        let host = request.headers().get(header::HOST).and_then(|host| host.to_str().ok());
+        let (scheme, rest) = origin.to_str().ok().and_then(|origin| origin.split_once("://")).unwrap_or_default();
+        // Local plugins run from file:// and similar, which no website can pose as
+        let web = matches!(scheme, "http" | "https" | "");
+        if web && Some(rest) != host {
+            return StatusCode::FORBIDDEN.into_response();
+        }
+    }
+    if request.method() == Method::OPTIONS {
+        return with_cors(StatusCode::NO_CONTENT.into_response(), allowed_origin.as_ref());
+    }
+
+    let path = request.uri().path();
+    if !path.starts_with(embed::EMBED_PREFIX) {
+        let client = match bearer_token(&request) {
+            Some(token) => match app.state::<ApiTokenState>().authenticate(token) {
+                Some(client) => Some(client),
+                None => return with_cors(StatusCode::UNAUTHORIZED.into_response(), allowed_origin.as_ref()),
+            },
+            None => None,
+        };
+        let readable = request.method() == Method::GET && REMOTE_READABLE.contains(&path);
+        if client.is_none() && require_token {
+            return with_cors(StatusCode::UNAUTHORIZED.into_response(), allowed_origin.as_ref());
+        }
+        // A website whose name was re-pointed at 127.0.0.1 connects from loopback too, but keeps
+        // its own name in Host. Only reads other machines may already make are exempt.
+        if client.is_none() && !local_host && !(readable && allow_remote) {
+            return with_cors(StatusCode::FORBIDDEN.into_response(), allowed_origin.as_ref());
+        }
+        if client.is_none() && !peer.ip().is_loopback() && !readable {
+            return with_cors(StatusCode::FORBIDDEN.into_response(), allowed_origin.as_ref());
+        }
+        let caller = Caller { client, peer };
+        if !app.state::<RateLimits>().allow(caller.key(), rate_limit) {
+            return with_cors(StatusCode::TOO_MANY_REQUESTS.into_response(), allowed_origin.as_ref());
+        }
+        request.extensions_mut().insert(caller);
+    } else if request.method() != Method::GET {
+        return StatusCode::METHOD_NOT_ALLOWED.into_response();
+    }
+    with_cors(next.run(request).await, allowed_origin.as_ref())
+}
+
+// Serves the countdown to local clients such as a Stream Deck plugin, and to other machines
+// when allowed. Follows the settings: starts, stops or rebinds as they change.
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut running: Option<(SocketAddr, oneshot::Sender<()>)> = None;
+        loop {
+            let wanted = wanted_address(&app);
+            if running.as_ref().map(|(address, _)| *address) != wanted {
+                if let Some((_, shutdown)) = running.take() {
+                    let _ = shutdown.send(());
+                }
+                if let Some(address) = wanted {
+                    match tokio::net::TcpListener::bind(address).await {
+                        Ok(listener) => {
+                            let (shutdown, stopped) = oneshot::channel::<()>();
+                            let router = router(app.clone());
+                            tauri::async_runtime::spawn(async move {
+                                let served = axum::serve(listener, router.into_make_service_with_connect_info::<SocketAddr>())
+                                    .with_graceful_shutdown(async move {
+                                        let _ = stopped.await;
+                                    })
+                                    .await;
+                                if let Err(e) = served {
+                                    eprintln!("Local API server stopped: {}", e);
+                                }
+                            });
+                            running = Some((address, shutdown));
+                        }
+                        Err(e) => {
+                            let message = format!("Failed to listen on {}: {}", address, e);
+                            eprintln!("{}", message);
+                            let _ = app.emit("local-api-failed", message);
+                            // Wait for a settings change before trying again
+                            while wanted_address(&app) == wanted {
+                                tokio::time::sleep(SETTINGS_POLL_INTERVAL).await;
+                            }
+                            continue;
+                        }
+                    }
+                }
+            }
+            tokio::time::sleep(SETTINGS_POLL_INTERVAL).await;
+        }
+    });
+}
@@ -0,0 +1,515 @@
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::actions::SystemAction;
+use crate::focus::BlocklistMode;
+use crate::format::{self, ClockFormat, Rounding};
+use crate::notifier::{self, Channel, EventKind};
+use crate::timers::{self, TimerState};
+use crate::tray::{self, CloseBehavior, TrayMode};
+use crate::{clock, hosts, i18n, persistence, server, viewer};
+
+pub const ALL_DAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+const MAX_ANNOUNCEMENT_INTERVAL_MINUTES: u32 = 24 * 60;
+const MAX_IDLE_THRESHOLD_MINUTES: u32 = 4 * 60;
+const MAX_KEEP_AWAKE_MINUTES: u32 = 24 * 60;
+const MAX_BREAK_MINUTES: u32 = 120;
+const DEFAULT_LOCAL_API_PORT: u16 = 47600;
+// Below this are privileged ports on most systems
+const MIN_LOCAL_API_PORT: u16 = 1024;
+// Mail submission with STARTTLS
+const DEFAULT_SMTP_PORT: u16 = 587;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct Settings {
+    pub reminder_days: Vec<Weekday>,
+    pub remind_immediately: bool,
+    pub suppress_when_visible: bool,
+    pub expiry_alarm: bool,
+    // None follows the OS language
+    pub locale: Option<String>,
+    pub clock_format: ClockFormat,
+    // Whether time left drops or rounds up the part of the smallest unit shown
+    pub remaining_rounding: Rounding,
+    // Periodic screen-reader summaries of the countdown
+    pub announcements_enabled: bool,
+    pub announcement_interval_minutes: u32,
+    pub speak_announcements: bool,
+    // Hold routine reminders while there's no keyboard or mouse input
+    pub defer_when_idle: bool,
+    pub idle_threshold_minutes: u32,
+    // Stop the machine from sleeping through the final minutes of a countdown
+    pub keep_awake: bool,
+    pub keep_awake_minutes: u32,
+    // Keep the screen on while the countdown is shown full screen or on top near its end
+    pub keep_display_on: bool,
+    pub keep_display_on_minutes: u32,
+    // What to do to the computer when the main countdown runs out
+    pub expiry_action: Option<SystemAction>,
+    // Length of the enforced break after a work session
+    pub break_minutes: u32,
+    // Applications to keep away during a work session, matched by process name
+    pub app_blocklist: Vec<String>,
+    pub blocklist_mode: BlocklistMode,
+    // Sites cut off through the hosts file during a work session
+    pub block_sites: bool,
+    pub blocked_sites: Vec<String>,
+    // Record which application is in front while a countdown runs
+    pub track_active_app: bool,
+    // Draw the main countdown onto the desktop wallpaper
+    pub wallpaper_countdown: bool,
+    // Serve the countdown on localhost for a Stream Deck plugin
+    pub local_api_enabled: bool,
+    pub local_api_port: u16,
+    // Let other machines read the countdown, e.g. viewer instances; changes stay local-only
+    pub local_api_allow_remote: bool,
+    // Listen on this address instead, e.g. one network interface
+    pub local_api_bind_address: Option<String>,
+    // Every request must carry a token from create_api_token, even from this machine. Without
+    // it, other machines still need one to change anything.
+    pub local_api_require_token: bool,
+    // Web pages on these origins may call the API from a browser
+    pub local_api_cors_origins: Vec<String>,
+    // Requests each client may make per minute; 0 for no limit
+    pub local_api_rate_limit: u32,
+    // Whether closing the window hides it to the tray or minimizes it
+    pub tray_mode: TrayMode,
+    pub close_behavior: CloseBehavior,
+    pub confirm_quit_when_running: bool,
+    // Show another instance's countdown, read-only, from the address of its local API
+    pub viewer_source: Option<String>,
+    pub viewer_timer: Option<u64>,
+    // Opt-in counts of launches, reminders and completed timers, kept on this machine only
+    pub usage_stats: bool,
+    // Compare the system clock with an NTP server now and then, and warn when it's off
+    pub clock_drift_check: bool,
+    pub ntp_server: String,
+    pub clock_drift_threshold_seconds: u32,
+    // Which channels each kind of notification goes out on
+    pub notification_routes: BTreeMap<EventKind, Vec<Channel>>,
+    // Where the webhook channel POSTs notifications to
+    pub webhook_url: Option<String>,
+    // SMTP server the email channel sends through; its password is kept with the other
+    // credentials in integrations.json
+    pub smtp_host: Option<String>,
+    pub smtp_port: u16,
+    pub smtp_username: Option<String>,
+    // Sender and recipient for the email channel, e.g. "Hourglass <me@example.com>"
+    pub email_from: Option<String>,
+    pub email_to: Option<String>,
+    // Run the .rhai scripts in the scripts folder on timer events
+    pub scripts_enabled: bool,
+    // Move finished timers to the archive and put the next upcoming timer in the main
+    // countdown's place when it runs out
+    pub auto_archive_expired: bool,
+    // Month the company's fiscal year starts in, 1 for January
+    pub fiscal_year_start_month: u32,
+    // Add where the latest check-in's pace ends up to timer reminders
+    pub pace_in_reminders: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            reminder_days: ALL_DAYS.to_vec(),
+            remind_immediately: false,
+            suppress_when_visible: true,
+            expiry_alarm: true,
+            locale: None,
+            clock_format: ClockFormat::Auto,
+            remaining_rounding: Rounding::Truncate,
+            announcements_enabled: false,
+            announcement_interval_minutes: 15,
+            speak_announcements: false,
+            defer_when_idle: false,
+            idle_threshold_minutes: 5,
+            keep_awake: false,
+            keep_awake_minutes: 10,
+            keep_display_on: false,
+            keep_display_on_minutes: 15,
+            expiry_action: None,
+            break_minutes: 5,
+            app_blocklist: Vec::new(),
+            blocklist_mode: BlocklistMode::Off,
+            block_sites: false,
+            blocked_sites: Vec::new(),
+            track_active_app: false,
+            wallpaper_countdown: false,
+            local_api_enabled: false,
+            local_api_port: DEFAULT_LOCAL_API_PORT,
+            local_api_allow_remote: false,
+            local_api_bind_address: None,
+            local_api_require_token: false,
+            local_api_cors_origins: Vec::new(),
+            local_api_rate_limit: 120,
+            tray_mode: TrayMode::Auto,
+            close_behavior: CloseBehavior::Hide,
+            confirm_quit_when_running: true,
+            viewer_source: None,
+            viewer_timer: None,
+            usage_stats: false,
+            clock_drift_check: false,
+            ntp_server: "pool.ntp.org".to_string(),
+            clock_drift_threshold_seconds: 30,
+            notification_routes: notifier::default_routes(),
+            webhook_url: None,
+            smtp_host: None,
+            smtp_port: DEFAULT_SMTP_PORT,
+            smtp_username: None,
+            email_from: None,
+            email_to: None,
+            scripts_enabled: false,
+            auto_archive_expired: false,
+            fiscal_year_start_month: 1,
+            pace_in_reminders: false,
+        }
+    }
+}
+
+// One setting that can't be used as it is, with a value that would work
+#[derive(Serialize, Clone)]
+pub struct SettingsProblem {
+    pub field: String,
+    pub message: String,
+    pub example: String,
+}
+
+impl SettingsProblem {
+    fn new(field: &str, message: String, example: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            message,
+            example: example.to_string(),
+        }
+    }
+}
+
+impl Settings {
+    // Every invalid field, not just the first, so they can all be fixed in one go
+    pub fn problems(&self) -> Vec<SettingsProblem> {
+        let mut problems = Vec::new();
+        let mut check = |ok: bool, field: &str, message: &dyn Fn() -> String, example: &str| {
+            if !ok {
+                problems.push(SettingsProblem::new(field, message(), example));
+            }
+        };
+        check(!self.reminder_days.is_empty(), "reminder_days", &|| i18n::t("error-no-reminder-days"), r#"["mon", "tue", "wed", "thu", "fri"]"#);
+        if let Some(Err(e)) = self.locale.as_deref().map(i18n::validate) {
+            check(false, "locale", &|| e.clone(), r#""en""#);
+        }
+        check(
+            (1..=MAX_ANNOUNCEMENT_INTERVAL_MINUTES).contains(&self.announcement_interval_minutes),
+            "announcement_interval_minutes",
+            &|| i18n::t_args("error-announcement-interval", &[("max", MAX_ANNOUNCEMENT_INTERVAL_MINUTES.into())]),
+            "15",
+        );
+        check(
+            (1..=MAX_IDLE_THRESHOLD_MINUTES).contains(&self.idle_threshold_minutes),
+            "idle_threshold_minutes",
+            &|| i18n::t_args("error-idle-threshold", &[("max", MAX_IDLE_THRESHOLD_MINUTES.into())]),
+            "5",
+        );
+        check(
+            (1..=MAX_KEEP_AWAKE_MINUTES).contains(&self.keep_awake_minutes),
+            "keep_awake_minutes",
+            &|| i18n::t_args("error-keep-awake-minutes", &[("max", MAX_KEEP_AWAKE_MINUTES.into())]),
+            "10",
+        );
+        check(
+            (1..=MAX_KEEP_AWAKE_MINUTES).contains(&self.keep_display_on_minutes),
+            "keep_display_on_minutes",
+            &|| i18n::t_args("error-keep-display-on-minutes", &[("max", MAX_KEEP_AWAKE_MINUTES.into())]),
+            "15",
+        );
+        check(
+            (1..=MAX_BREAK_MINUTES).contains(&self.break_minutes),
+            "break_minutes",
+            &|| i18n::t_args("error-break-minutes", &[("max", MAX_BREAK_MINUTES.into())]),
+            "5",
+        );
+        check(
+            !self.app_blocklist.iter().any(|name| name.trim().is_empty()),
+            "app_blocklist",
+            &|| i18n::t("error-empty-blocklist-entry"),
+            r#"["steam", "discord"]"#,
+        );
+        if let Some(site) = self.blocked_sites.iter().find(|site| hosts::normalize_site(site).is_none()) {
+            check(false, "blocked_sites", &|| i18n::t_args("error-invalid-blocked-site", &[("site", site.clone().into())]), r#"["youtube.com"]"#);
+        }
+        check(
+            self.local_api_port >= MIN_LOCAL_API_PORT,
+            "local_api_port",
+            &|| i18n::t_args("error-local-api-port", &[("min", MIN_LOCAL_API_PORT.into())]),
+            "47600",
+        );
+        if let Some(address) = self.local_api_bind_address.as_ref().filter(|address| address.trim().parse::<IpAddr>().is_err()) {
+            check(
+                false,
+                "local_api_bind_address",
+                &|| i18n::t_args("error-invalid-bind-address", &[("address", address.clone().into())]),
+                r#""192.168.1.20""#,
+            );
+        }
+        if let Some(origin) = self.local_api_cors_origins.iter().find(|origin| !server::is_valid_origin(origin)) {
+            check(
+                false,
+                "local_api_cors_origins",
+                &|| i18n::t_args("error-invalid-cors-origin", &[("origin", origin.clone().into())]),
+                r#"["https://example.com"]"#,
+            );
+        }
+        check(!self.ntp_server.trim().is_empty(), "ntp_server", &|| i18n::t("error-ntp-server"), r#""pool.ntp.org""#);
+        check(
+            self.clock_drift_threshold_seconds > 0,
+            "clock_drift_threshold_seconds",
+            &|| i18n::t("error-clock-drift-threshold"),
+            "30",
+        );
+        check(
+            (1..=12).contains(&self.fiscal_year_start_month),
+            "fiscal_year_start_month",
+            &|| i18n::t("error-fiscal-year-start"),
+            "10",
+        );
+        if let Some(source) = self.viewer_source.as_ref().filter(|source| !viewer::validate_source(source)) {
+            check(
+                false,
+                "viewer_source",
+                &|| i18n::t_args("error-invalid-viewer-source", &[("source", source.clone().into())]),
+                r#""http://192.168.1.20:47600""#,
+            );
+        }
+        if let Some(url) = self.webhook_url.as_ref().filter(|url| !notifier::validate_webhook_url(url)) {
+            check(
+                false,
+                "webhook_url",
+                &|| i18n::t_args("error-invalid-webhook-url", &[("url", url.clone().into())]),
+                r#""https://example.com/hooks/hourglass""#,
+            );
+        }
+        if let Some(host) = self.smtp_host.as_ref().filter(|host| host.trim().is_empty() || host.contains(char::is_whitespace)) {
+            check(false, "smtp_host", &|| i18n::t_args("error-invalid-smtp-host", &[("host", host.clone().into())]), r#""smtp.example.com""#);
+        }
+        check(self.smtp_port > 0, "smtp_port", &|| i18n::t("error-smtp-port"), "587");
+        for (field, address) in [("email_from", &self.email_from), ("email_to", &self.email_to)] {
+            if let Some(address) = address.as_ref().filter(|address| !notifier::validate_mailbox(address)) {
+                check(
+                    false,
+                    field,
+                    &|| i18n::t_args("error-invalid-email-address", &[("address", address.clone().into())]),
+                    r#""Hourglass <me@example.com>""#,
+                );
+            }
+        }
+        // Otherwise those notifications would be dropped at send time with nowhere to go
+        let routed = |channel: Channel| self.notification_routes.values().any(|channels| channels.contains(&channel));
+        check(
+            self.channel_configured(Channel::Webhook) || !routed(Channel::Webhook),
+            "notification_routes",
+            &|| i18n::t("error-webhook-route-without-url"),
+            r#"{"reminder": ["desktop", "sound", "plugins"]}"#,
+        );
+        check(
+            self.channel_configured(Channel::Email) || !routed(Channel::Email),
+            "notification_routes",
+            &|| i18n::t("error-email-route-without-smtp"),
+            r#"{"reminder": ["desktop", "sound", "plugins"]}"#,
+        );
+        problems
+    }
+
+    // Whether a channel has what it needs to send anything
+    fn channel_configured(&self, channel: Channel) -> bool {
+        let set = |value: &Option<String>| value.as_deref().is_some_and(|value| !value.trim().is_empty());
+        match channel {
+            Channel::Webhook => set(&self.webhook_url),
+            Channel::Email => {
+                set(&self.smtp_host)
+                    && [&self.email_from, &self.email_to]
+                        .iter()
+                        .all(|address| address.as_deref().is_some_and(notifier::validate_mailbox))
+            }
+            Channel::Desktop | Channel::Sound | Channel::Plugins => true,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let problems = self.problems();
+        if problems.is_empty() {
+            return Ok(());
+        }
+        Err(problems.iter().map(describe).collect::<Vec<_>>().join("\n"))
+    }
+
+    // Puts the fields with problems back to their defaults, keeping everything else
+    fn repair(&mut self, problems: &[SettingsProblem]) {
+        let defaults = Settings::default();
+        for problem in problems {
+            match problem.field.as_str() {
+                "reminder_days" => self.reminder_days = defaults.reminder_days.clone(),
+                "locale" => self.locale = None,
+                "announcement_interval_minutes" => self.announcement_interval_minutes = defaults.announcement_interval_minutes,
+                "idle_threshold_minutes" => self.idle_threshold_minutes = defaults.idle_threshold_minutes,
+                "keep_awake_minutes" => self.keep_awake_minutes = defaults.keep_awake_minutes,
+                "keep_display_on_minutes" => self.keep_display_on_minutes = defaults.keep_display_on_minutes,
+                "break_minutes" => self.break_minutes = defaults.break_minutes,
+                "app_blocklist" => self.app_blocklist.retain(|name| !name.trim().is_empty()),
+                "blocked_sites" => self.blocked_sites.retain(|site| hosts::normalize_site(site).is_some()),
+                "local_api_port" => self.local_api_port = defaults.local_api_port,
+                "local_api_bind_address" => self.local_api_bind_address = None,
+                "local_api_cors_origins" => self.local_api_cors_origins.retain(|origin| server::is_valid_origin(origin)),
+                "ntp_server" => self.ntp_server = defaults.ntp_server.clone(),
+                "clock_drift_threshold_seconds" => self.clock_drift_threshold_seconds = defaults.clock_drift_threshold_seconds,
+                "viewer_source" => self.viewer_source = None,
+                "fiscal_year_start_month" => self.fiscal_year_start_month = defaults.fiscal_year_start_month,
+                "webhook_url" => self.webhook_url = None,
+                "smtp_host" => self.smtp_host = None,
+                "smtp_port" => self.smtp_port = defaults.smtp_port,
+                "email_from" => self.email_from = None,
+                "email_to" => self.email_to = None,
+                "notification_routes" => {
+                    let configured: Vec<Channel> = [Channel::Webhook, Channel::Email]
+                        .into_iter()
+                        .filter(|channel| self.channel_configured(*channel))
+                        .collect();
+                    for channels in self.notification_routes.values_mut() {
+                        channels.retain(|channel| !matches!(channel, Channel::Webhook | Channel::Email) || configured.contains(channel));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn describe(problem: &SettingsProblem) -> String {
+    i18n::t_args(
+        "error-invalid-setting",
+        &[
+            ("field", problem.field.clone().into()),
+            ("message", problem.message.clone().into()),
+            ("example", problem.example.clone().into()),
+        ],
+    )
+}
+
+#[derive(Default)]
+pub struct SettingsState {
+    pub settings: Arc<Mutex<Settings>>,
+    pub data_path: Arc<Mutex<Option<PathBuf>>>,
+    // What was wrong with settings.json when it was loaded
+    pub load_problems: Arc<Mutex<Vec<SettingsProblem>>>,
+}
+
+impl SettingsState {
+    pub fn lock(&self) -> Result<MutexGuard<'_, Settings>, String> {
+        self.settings.lock().map_err(|e| format!("Failed to lock settings: {}", e))
+    }
+
+    // A hand-edited file with a mistake in it keeps the rest of its values: fields that are
+    // invalid go back to their defaults and the problems are kept for the settings screen.
+    // Only a file that isn't valid JSON at all is replaced wholesale.
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let (mut loaded, mut problems) = match std::fs::read_to_string(&path) {
+            Ok(contents) => match serde_json::from_str::<Settings>(&contents) {
+                Ok(settings) => (settings, Vec::new()),
+                Err(e) => {
+                    eprintln!("Failed to parse {}: {}", path.display(), e);
+                    let problem = SettingsProblem::new(
+                        "settings.json",
+                        i18n::t_args("error-unreadable-settings", &[("reason", e.to_string().into())]),
+                        r#"{ "reminder_days": ["mon"], "break_minutes": 5 }"#,
+                    );
+                    (Settings::default(), vec![problem])
+                }
+            },
+            Err(_) => (persistence::load_json(&path), Vec::new()),
+        };
+        let invalid = loaded.problems();
+        loaded.repair(&invalid);
+        for problem in &invalid {
+            eprintln!("Invalid setting in {}: {}", path.display(), describe(problem));
+        }
+        problems.extend(invalid);
+        *self.lock()? = loaded;
+        *self.load_problems.lock().map_err(|e| format!("Failed to lock settings problems: {}", e))? = problems;
+        *self.data_path.lock().map_err(|e| format!("Failed to lock settings path: {}", e))? = Some(path);
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = self.data_path.lock().map_err(|e| format!("Failed to lock settings path: {}", e))?.clone();
+        match path {
+            Some(path) => persistence::save_json(&path, &*self.lock()?),
+            None => Ok(()),
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_settings(state: State<'_, SettingsState>) -> Result<Settings, String> {
+    Ok(state.lock()?.clone())
+}
+
+// Checks settings without saving them, so a form can flag each field
+#[tauri::command]
+pub async fn validate_settings(settings: Settings) -> Result<Vec<SettingsProblem>, String> {
+    Ok(settings.problems())
+}
+
+// Problems found in settings.json at startup, and fixed by falling back to defaults
+#[tauri::command]
+pub async fn get_settings_problems(state: State<'_, SettingsState>) -> Result<Vec<SettingsProblem>, String> {
+    Ok(state.load_problems.lock().map_err(|e| format!("Failed to lock settings problems: {}", e))?.clone())
+}
+
+#[tauri::command]
+pub async fn update_settings(
+    app: AppHandle,
+    state: State<'_, SettingsState>,
+    settings: Settings,
+) -> Result<Settings, String> {
+    settings.validate()?;
+    let (locale_changed, clock_changed, fiscal_changed) = {
+        let mut current = state.lock()?;
+        let changed = (
+            current.locale != settings.locale,
+            current.clock_format != settings.clock_format || current.remaining_rounding != settings.remaining_rounding,
+            current.fiscal_year_start_month != settings.fiscal_year_start_month,
+        );
+        *current = settings.clone();
+        changed
+    };
+    state.save()?;
+    if locale_changed {
+        i18n::apply(settings.locale.as_deref())?;
+    }
+    if clock_changed {
+        format::set_clock_format(settings.clock_format);
+        format::set_rounding(settings.remaining_rounding);
+    }
+    if locale_changed || clock_changed {
+        i18n::locale_changed(&app);
+    }
+    if fiscal_changed {
+        timers::set_fiscal_year_start(settings.fiscal_year_start_month);
+        app.state::<TimerState>().lock()?.realign_fiscal_periods(clock::now())?;
+    }
+    tray::apply_mode(&app);
+    Ok(settings)
+}
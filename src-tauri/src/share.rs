@@ -0,0 +1,398 @@
+use std::fs;
+use chrono::{DateTime, FixedOffset, Local, Utc};
+use chrono_tz::Tz;
+use fontdue::{Font, FontSettings};
+use qrcode::{Color, QrCode};
+use serde::{Deserialize, Serialize};
+use tauri::ipc::Response;
+use hourglass_core::countdown::{self, CalendarSpan};
+use tauri::{AppHandle, Manager, State, Url};
+
+use crate::i18n::{t, t_args};
+use crate::timers::{Timer, TimerState};
+use crate::{clock, format, primary_end_date, primary_start_date, NotificationState};
+
+const QR_MODULE_PIXELS: usize = 8;
+// Scanners need a margin of at least four light modules around the code
+const QR_QUIET_ZONE: usize = 4;
+// The usual link-preview card size, so chat apps show it uncropped
+const CARD_WIDTH: u32 = 1200;
+const CARD_HEIGHT: u32 = 630;
+
+#[cfg(windows)]
+const FONT_PATHS: [&str; 3] = ["C:\\Windows\\Fonts\\segoeui.ttf", "C:\\Windows\\Fonts\\arial.ttf", "C:\\Windows\\Fonts\\tahoma.ttf"];
+#[cfg(target_os = "macos")]
+const FONT_PATHS: [&str; 3] = [
+    "/System/Library/Fonts/Supplemental/Arial.ttf",
+    "/Library/Fonts/Arial.ttf",
+    "/System/Library/Fonts/Helvetica.ttc",
+];
+#[cfg(all(unix, not(target_os = "macos")))]
+const FONT_PATHS: [&str; 5] = [
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/TTF/DejaVuSans.ttf",
+    "/usr/share/fonts/dejavu-sans-fonts/DejaVuSans.ttf",
+    "/usr/share/fonts/truetype/liberation/LiberationSans-Regular.ttf",
+    "/usr/share/fonts/noto/NotoSans-Regular.ttf",
+];
+
+// What a rendered or copied countdown shows. Without a timer id this is the main countdown.
+pub struct Countdown {
+    pub title: Option<String>,
+    pub start: Option<DateTime<Utc>>,
+    pub end: DateTime<Utc>,
+}
+
+impl Countdown {
+    pub fn load(app: &AppHandle, timer_id: Option<u64>) -> Result<Self, String> {
+        match timer_id {
+            Some(id) => {
+                let state = app.state::<TimerState>();
+                let store = state.lock()?;
+                let timer = store.get(id).ok_or_else(|| t_args("error-timer-not-found", &[("id", id.into())]))?;
+                Ok(Self::from_timer(timer))
+            }
+            None => {
+                let state = app.state::<NotificationState>();
+                let end = primary_end_date(&state).ok_or_else(|| t("error-timer-dates-not-set"))?;
+                Ok(Self {
+                    title: None,
+                    start: primary_start_date(&state),
+                    end,
+                })
+            }
+        }
+    }
+
+    pub fn from_timer(timer: &Timer) -> Self {
+        Self {
+            title: (!timer.label.is_empty()).then(|| timer.label.clone()),
+            start: Some(timer.start_date),
+            end: timer.end_date,
+        }
+    }
+
+    pub fn remaining_ms(&self, now: DateTime<Utc>) -> i64 {
+        (self.end - now).num_milliseconds()
+    }
+
+    // Share of the countdown already behind us, from 0 to 1; unknown without a start date
+    pub fn progress(&self, now: DateTime<Utc>) -> Option<f64> {
+        countdown::progress(self.start?, self.end, now)
+    }
+
+    // Time left counted on the local calendar rather than in fixed-length days
+    pub fn breakdown(&self, now: DateTime<Utc>) -> CountdownBreakdown {
+        let (local_now, end) = (now.with_timezone(&Local).naive_local(), self.end.with_timezone(&Local).naive_local());
+        let calendar = countdown::calendar_span(local_now, end);
+        let (iso_year, iso_week) = countdown::iso_week(end.date());
+        CountdownBreakdown {
+            title: self.title.clone(),
+            end: self.end,
+            remaining_ms: self.remaining_ms(now).max(0),
+            text: format::format_calendar_span(&calendar),
+            calendar,
+            iso_year,
+            iso_week,
+            iso_week_label: t_args("iso-week", &[("week", iso_week.into())]),
+            calendar_weeks: countdown::calendar_weeks_between(local_now.date(), end.date()),
+        }
+    }
+
+    pub fn summary(&self, now: DateTime<Utc>) -> CountdownSummary {
+        let remaining_ms = self.remaining_ms(now);
+        CountdownSummary {
+            title: self.title.clone(),
+            start: self.start,
+            end: self.end,
+            remaining_ms: remaining_ms.max(0),
+            text: format::format_duration_short(remaining_ms),
+            percent: self.progress(now).map(|progress| (progress * 100.0).round() as u8),
+            expired: remaining_ms <= 0,
+        }
+    }
+}
+
+// The countdown as reported to scripts, launchers and status bars
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct CountdownSummary {
+    pub title: Option<String>,
+    pub start: Option<DateTime<Utc>>,
+    pub end: DateTime<Utc>,
+    pub remaining_ms: i64,
+    pub text: String,
+    pub percent: Option<u8>,
+    pub expired: bool,
+}
+
+#[derive(Serialize)]
+pub struct CountdownBreakdown {
+    pub title: Option<String>,
+    pub end: DateTime<Utc>,
+    pub remaining_ms: i64,
+    pub calendar: CalendarSpan,
+    // "3 months, 2 days"
+    pub text: String,
+    // The deadline's ISO week, which belongs to `iso_year` and not always the calendar year
+    pub iso_year: i32,
+    pub iso_week: u32,
+    // "W42"
+    pub iso_week_label: String,
+    // Monday-to-Sunday weeks still to start before the deadline's week
+    pub calendar_weeks: i64,
+}
+
+// Encodes 8-bit pixels as a PNG; `rgba` picks between color and grayscale data
+pub fn encode_png(pixels: &[u8], width: u32, height: u32, rgba: bool) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, width, height);
+    encoder.set_color(if rgba { png::ColorType::Rgba } else { png::ColorType::Grayscale });
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| format!("Failed to encode image: {}", e))?;
+    writer.write_image_data(pixels).map_err(|e| format!("Failed to encode image: {}", e))?;
+    writer.finish().map_err(|e| format!("Failed to encode image: {}", e))?;
+    Ok(bytes)
+}
+
+fn render_qr(data: &str) -> Result<Vec<u8>, String> {
+    let code = QrCode::new(data.as_bytes()).map_err(|e| format!("Failed to create QR code: {}", e))?;
+    let modules = code.width();
+    let colors = code.to_colors();
+    let side = (modules + 2 * QR_QUIET_ZONE) * QR_MODULE_PIXELS;
+    let mut pixels = vec![255u8; side * side];
+    for (index, color) in colors.iter().enumerate() {
+        if *color != Color::Dark {
+            continue;
+        }
+        let x = (index % modules + QR_QUIET_ZONE) * QR_MODULE_PIXELS;
+        let y = (index / modules + QR_QUIET_ZONE) * QR_MODULE_PIXELS;
+        for row in y..y + QR_MODULE_PIXELS {
+            pixels[row * side + x..row * side + x + QR_MODULE_PIXELS].fill(0);
+        }
+    }
+    encode_png(&pixels, side as u32, side as u32, false)
+}
+
+// hourglass://timer?label=...&start=...&end=... carries everything needed to recreate the
+// countdown on the scanning device
+pub fn timer_link(label: &str, start: &str, end: &str) -> Result<Url, String> {
+    Url::parse_with_params("hourglass://timer", &[("label", label), ("start", start), ("end", end)])
+        .map_err(|e| format!("Failed to build timer link: {}", e))
+}
+
+// Returns the PNG bytes, which the frontend receives as an ArrayBuffer
+#[tauri::command]
+pub async fn get_timer_qr(state: State<'_, TimerState>, id: u64) -> Result<Response, String> {
+    let link = {
+        let store = state.lock()?;
+        let timer = store.get(id).ok_or_else(|| t_args("error-timer-not-found", &[("id", id.into())]))?;
+        timer_link(&timer.label, &timer.start_date.to_rfc3339(), &timer.end_date.to_rfc3339())?
+    };
+    Ok(Response::new(render_qr(link.as_str())?))
+}
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageStyle {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl ImageStyle {
+    // Background, text, muted text and accent colors
+    fn palette(self) -> [[u8; 3]; 4] {
+        match self {
+            ImageStyle::Light => [[0xfa, 0xf7, 0xf0], [0x1f, 0x1f, 0x1f], [0x6b, 0x6b, 0x6b], [0xf5, 0x9e, 0x0b]],
+            ImageStyle::Dark => [[0x17, 0x17, 0x1c], [0xf5, 0xf5, 0xf5], [0xa3, 0xa3, 0xa3], [0xf5, 0x9e, 0x0b]],
+        }
+    }
+}
+
+// The first of the usual UI fonts that this machine has
+pub fn load_font() -> Result<Font, String> {
+    let bytes = FONT_PATHS
+        .iter()
+        .find_map(|path| fs::read(path).ok())
+        .ok_or_else(|| "Failed to find a font to render with".to_string())?;
+    Font::from_bytes(bytes, FontSettings::default()).map_err(|e| format!("Failed to load font: {}", e))
+}
+
+// A plain RGBA pixel buffer with just enough drawing for text cards
+pub struct Canvas {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Canvas {
+    pub fn new(width: u32, height: u32, background: [u8; 3]) -> Self {
+        let pixels = background.iter().copied().chain([255]).cycle().take((width * height * 4) as usize).collect();
+        Self { width, height, pixels }
+    }
+
+    // Mixes `color` over the pixel with the given coverage (0-255)
+    pub fn blend(&mut self, x: i64, y: i64, color: [u8; 3], coverage: u8) {
+        if x < 0 || y < 0 || x >= i64::from(self.width) || y >= i64::from(self.height) || coverage == 0 {
+            return;
+        }
+        let index = ((y as u32 * self.width + x as u32) * 4) as usize;
+        let alpha = u32::from(coverage);
+        for (channel, value) in color.iter().enumerate() {
+            let current = u32::from(self.pixels[index + channel]);
+            self.pixels[index + channel] = ((u32::from(*value) * alpha + current * (255 - alpha)) / 255) as u8;
+        }
+    }
+
+    pub fn from_rgba(width: u32, height: u32, pixels: Vec<u8>) -> Self {
+        Self { width, height, pixels }
+    }
+
+    pub fn fill_rect(&mut self, x: i64, y: i64, width: i64, height: i64, color: [u8; 3]) {
+        self.shade_rect(x, y, width, height, color, 255);
+    }
+
+    pub fn shade_rect(&mut self, x: i64, y: i64, width: i64, height: i64, color: [u8; 3], alpha: u8) {
+        for row in y..y + height {
+            for column in x..x + width {
+                self.blend(column, row, color, alpha);
+            }
+        }
+    }
+
+    pub fn text_width(font: &Font, text: &str, size: f32) -> f32 {
+        text.chars().map(|c| font.metrics(c, size).advance_width).sum()
+    }
+
+    // Draws one line starting at `x` on the `baseline`, shrinking it until it fits `max_width`
+    pub fn draw_text(&mut self, font: &Font, text: &str, (x, baseline): (f32, f32), size: f32, max_width: f32, color: [u8; 3]) {
+        let mut size = size;
+        while size > 8.0 && Self::text_width(font, text, size) > max_width {
+            size *= 0.9;
+        }
+        let mut pen = x;
+        for c in text.chars() {
+            let (metrics, coverage) = font.rasterize(c, size);
+            let left = (pen + metrics.xmin as f32).round() as i64;
+            let top = (baseline - metrics.ymin as f32 - metrics.height as f32).round() as i64;
+            for row in 0..metrics.height {
+                for column in 0..metrics.width {
+                    self.blend(left + column as i64, top + row as i64, color, coverage[row * metrics.width + column]);
+                }
+            }
+            pen += metrics.advance_width;
+        }
+    }
+
+    pub fn to_png(&self) -> Result<Vec<u8>, String> {
+        encode_png(&self.pixels, self.width, self.height, true)
+    }
+}
+
+// Title, time left and a progress bar on a panel covering `area` (x, y, width, height).
+// A panel alpha below 255 lets whatever is underneath show through.
+pub fn draw_countdown(canvas: &mut Canvas, font: &Font, countdown: &Countdown, style: ImageStyle, area: (f32, f32, f32, f32), panel_alpha: u8) {
+    let [background, text, muted, accent] = style.palette();
+    let (x, y, w, h) = area;
+    canvas.shade_rect(x as i64, y as i64, w as i64, h as i64, background, panel_alpha);
+    let now = Utc::now();
+    let left = x + w * 0.07;
+    let max_width = w * 0.86;
+
+    let title = countdown.title.clone().unwrap_or_else(|| t("primary-countdown-label"));
+    canvas.draw_text(font, &title, (left, y + h * 0.28), h * 0.11, max_width, muted);
+    let remaining = countdown.remaining_ms(now);
+    let headline = if remaining > 0 { format::format_duration(remaining) } else { t("share-sentence-ended-untitled") };
+    canvas.draw_text(font, &headline, (left, y + h * 0.52), h * 0.16, max_width, text);
+
+    if let Some(progress) = countdown.progress(now) {
+        let bar_top = (y + h * 0.66) as i64;
+        let bar_height = (h * 0.045).max(4.0) as i64;
+        canvas.fill_rect(left as i64, bar_top, max_width as i64, bar_height, muted);
+        canvas.fill_rect(left as i64, bar_top, (max_width * progress as f32) as i64, bar_height, accent);
+        let percent = format!("{}%", (progress * 100.0).round());
+        canvas.draw_text(font, &percent, (left, y + h * 0.80), h * 0.06, max_width, muted);
+    }
+    canvas.draw_text(font, &t("tray-title"), (left, y + h * 0.92), h * 0.05, max_width, accent);
+}
+
+pub fn render_countdown(countdown: &Countdown, style: ImageStyle, width: u32, height: u32) -> Result<Canvas, String> {
+    let font = load_font()?;
+    let mut canvas = Canvas::new(width, height, style.palette()[0]);
+    draw_countdown(&mut canvas, &font, countdown, style, (0.0, 0.0, width as f32, height as f32), 255);
+    Ok(canvas)
+}
+
+#[tauri::command]
+pub async fn export_countdown_image(
+    app: AppHandle,
+    path: String,
+    style: Option<ImageStyle>,
+    timer_id: Option<u64>,
+) -> Result<(), String> {
+    let countdown = Countdown::load(&app, timer_id)?;
+    let png = tauri::async_runtime::spawn_blocking(move || {
+        render_countdown(&countdown, style.unwrap_or_default(), CARD_WIDTH, CARD_HEIGHT)?.to_png()
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    fs::write(&path, png).map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+#[derive(Serialize)]
+pub struct ZonedDeadline {
+    pub zone: String,
+    // What the zone is called at that moment, e.g. CET or EST
+    pub abbreviation: String,
+    pub local: DateTime<FixedOffset>,
+    pub time: String,
+}
+
+#[derive(Serialize)]
+pub struct DeadlineInZones {
+    pub end: DateTime<Utc>,
+    pub zones: Vec<ZonedDeadline>,
+    // "17:00 CET / 11:00 EST / 01:00 JST (+1 day)"; days are relative to the first zone
+    pub summary: String,
+}
+
+// The end instant as it reads in each IANA zone, e.g. "Europe/Berlin", for teams sharing one
+// countdown across time zones
+#[tauri::command]
+pub async fn get_deadline_in_zones(app: AppHandle, zones: Vec<String>, timer_id: Option<u64>) -> Result<DeadlineInZones, String> {
+    let end = Countdown::load(&app, timer_id)?.end;
+    let mut zoned = Vec::new();
+    let mut parts = Vec::new();
+    let mut first_date = None;
+    for zone in zones.iter().map(|zone| zone.trim()) {
+        let tz: Tz = zone.parse().map_err(|_| t_args("error-unknown-timezone", &[("zone", zone.to_string().into())]))?;
+        let local = end.with_timezone(&tz);
+        let time = format::format_clock_time(&local);
+        let abbreviation = local.format("%Z").to_string();
+        let days_apart = (local.date_naive() - *first_date.get_or_insert(local.date_naive())).num_days();
+        parts.push(match days_apart {
+            0 => format!("{} {}", time, abbreviation),
+            days => t_args(
+                "zone-time-other-day",
+                &[
+                    ("time", format!("{} {}", time, abbreviation).into()),
+                    ("sign", if days > 0 { "+" } else { "-" }.into()),
+                    ("count", days.abs().into()),
+                ],
+            ),
+        });
+        zoned.push(ZonedDeadline {
+            zone: tz.name().to_string(),
+            abbreviation,
+            local: local.fixed_offset(),
+            time,
+        });
+    }
+    Ok(DeadlineInZones { end, zones: zoned, summary: parts.join(" / ") })
+}
+
+// How long is left in years, months and days as the calendar counts them
+#[tauri::command]
+pub async fn get_countdown_breakdown(app: AppHandle, timer_id: Option<u64>) -> Result<CountdownBreakdown, String> {
+    Ok(Countdown::load(&app, timer_id)?.breakdown(clock::now()))
+}
@@ -0,0 +1,62 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Manager};
+
+use crate::activity::ActivityState;
+use crate::audio::AudioState;
+use crate::layout::LayoutState;
+use crate::power::PowerState;
+use crate::settings::SettingsState;
+use crate::stats::StatsState;
+use crate::timers::TimerState;
+use crate::{hosts, kiosk, wallpaper, NotificationState};
+
+static SHUT_DOWN: AtomicBool = AtomicBool::new(false);
+
+// Leaves the system as it was before the app started and writes out anything held in memory.
+// Process teardown would skip all of it: an inhibitor could outlive us, the hosts file would
+// keep blocking sites and the countdown would stay painted on the wallpaper.
+pub fn run(app: &AppHandle) {
+    if SHUT_DOWN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let notifications = app.state::<NotificationState>();
+    if let Ok(mut handle) = notifications.handle.lock() {
+        if let Some(task) = handle.take() {
+            task.abort();
+        }
+    }
+    if let Err(e) = app.state::<AudioState>().stop_alarm() {
+        eprintln!("Failed to stop alarm on exit: {}", e);
+    }
+
+    let flushed = [
+        ("timers", app.state::<TimerState>().lock().and_then(|store| store.save())),
+        ("settings", app.state::<SettingsState>().save()),
+        ("statistics", app.state::<StatsState>().save()),
+        ("activity", app.state::<ActivityState>().save()),
+        ("window layout", app.state::<LayoutState>().save()),
+    ];
+    for (what, result) in flushed {
+        if let Err(e) = result {
+            eprintln!("Failed to save {} on exit: {}", what, e);
+        }
+    }
+
+    app.state::<PowerState>().release_all();
+    if let Err(e) = hosts::clear() {
+        eprintln!("Failed to lift site block on exit: {}", e);
+    }
+    if let Err(e) = wallpaper::restore(app) {
+        eprintln!("Failed to restore wallpaper on exit: {}", e);
+    }
+}
+
+// Relaunches so settings read only at startup, like the local API port or the locale, take
+// effect. Everything is written out first since the new process starts from what's on disk.
+#[tauri::command]
+pub async fn restart_app(app: AppHandle) -> Result<(), String> {
+    kiosk::ensure_unlocked(&app)?;
+    run(&app);
+    app.restart()
+}
@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::clock;
+use crate::health::{self, NotificationHealth};
+use crate::notifications;
+use crate::settings::{Settings, SettingsState};
+use crate::share::{Countdown, CountdownSummary};
+use crate::timers::{Timer, TimerState};
+use crate::NotificationState;
+
+#[derive(Serialize)]
+pub struct TimerSnapshot {
+    #[serde(flatten)]
+    pub timer: Timer,
+    pub countdown: CountdownSummary,
+}
+
+#[derive(Serialize)]
+pub struct NotificationSnapshot {
+    pub enabled: bool,
+    pub snoozed_until: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub health: NotificationHealth,
+}
+
+// Everything the main window shows on load, read at one instant
+#[derive(Serialize)]
+pub struct AppSnapshot {
+    pub taken_at: DateTime<Utc>,
+    // Non-zero while set_time_offset is simulating another time
+    pub time_offset_seconds: i64,
+    // None until the main countdown's dates are set
+    pub primary: Option<CountdownSummary>,
+    pub timers: Vec<TimerSnapshot>,
+    pub settings: Settings,
+    pub notifications: NotificationSnapshot,
+}
+
+// Active timers, the main countdown, settings and notification status, all measured against
+// the same "now"
+pub async fn take(app: &AppHandle) -> Result<AppSnapshot, String> {
+    let checked = app.clone();
+    let permission = tauri::async_runtime::spawn_blocking(move || notifications::permission(&checked))
+        .await
+        .map_err(|e| format!("Failed to check notification permission: {}", e))?;
+
+    let now = clock::now();
+    let timers = {
+        let state = app.state::<TimerState>();
+        let store = state.lock()?;
+        store
+            .timers
+            .iter()
+            .filter(|timer| timer.is_active())
+            .map(|timer| TimerSnapshot {
+                countdown: Countdown::from_timer(timer).summary(now),
+                timer: timer.clone(),
+            })
+            .collect()
+    };
+    let settings = app.state::<SettingsState>().lock()?.clone();
+    let state = app.state::<NotificationState>();
+    let enabled = *state.is_enabled.lock().map_err(|e| format!("Failed to lock notification state: {}", e))?;
+    let snoozed_until = state
+        .snoozed_until
+        .lock()
+        .map_err(|e| format!("Failed to lock snooze: {}", e))?
+        .filter(|until| *until > now);
+
+    Ok(AppSnapshot {
+        taken_at: now,
+        time_offset_seconds: clock::offset_seconds(),
+        primary: Countdown::load(app, None).ok().map(|countdown| countdown.summary(now)),
+        timers,
+        settings,
+        notifications: NotificationSnapshot {
+            enabled,
+            snoozed_until,
+            health: health::notification_health(app, permission),
+        },
+    })
+}
+
+#[tauri::command]
+pub async fn get_app_snapshot(app: AppHandle) -> Result<AppSnapshot, String> {
+    take(&app).await
+}
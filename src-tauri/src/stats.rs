@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use hourglass_core::countdown::{fiscal_year, percent_elapsed, period_bounds, CalendarPeriod};
+
+use crate::timers::{self, TimerState, TimerStatus};
+use crate::{i18n, persistence};
+
+// Things history can't reconstruct on its own
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct StatsLog {
+    pub notifications: BTreeMap<NaiveDate, u32>,
+}
+
+#[derive(Default)]
+pub struct StatsState {
+    pub log: Arc<Mutex<StatsLog>>,
+    pub data_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl StatsState {
+    pub fn lock(&self) -> Result<MutexGuard<'_, StatsLog>, String> {
+        self.log.lock().map_err(|e| format!("Failed to lock statistics: {}", e))
+    }
+
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let loaded: StatsLog = persistence::load_json(&path);
+        *self.lock()? = loaded;
+        *self.data_path.lock().map_err(|e| format!("Failed to lock statistics path: {}", e))? = Some(path);
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = self.data_path.lock().map_err(|e| format!("Failed to lock statistics path: {}", e))?.clone();
+        match path {
+            Some(path) => persistence::save_json(&path, &*self.lock()?),
+            None => Ok(()),
+        }
+    }
+
+    pub fn record_notification(&self) -> Result<(), String> {
+        *self.lock()?.notifications.entry(Local::now().date_naive()).or_default() += 1;
+        self.save()
+    }
+}
+
+// Trailing windows ending today
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsRange {
+    Week,
+    #[default]
+    Month,
+    Quarter,
+    Year,
+}
+
+impl StatsRange {
+    fn days(self) -> u64 {
+        match self {
+            StatsRange::Week => 7,
+            StatsRange::Month => 30,
+            StatsRange::Quarter => 90,
+            StatsRange::Year => 365,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DayStats {
+    pub date: NaiveDate,
+    // Countdown time that passed on this day, summed over every countdown running then
+    pub elapsed_seconds: i64,
+    pub notifications: u32,
+}
+
+#[derive(Serialize)]
+pub struct WeekStats {
+    // Monday of the week
+    pub week_start: NaiveDate,
+    pub completed: u32,
+}
+
+#[derive(Serialize)]
+pub struct FiscalYearStats {
+    pub label: String,
+    pub start: NaiveDate,
+    // The last day of the fiscal year
+    pub end: NaiveDate,
+    pub percent_elapsed: i64,
+}
+
+#[derive(Serialize)]
+pub struct Statistics {
+    pub days: Vec<DayStats>,
+    pub weeks: Vec<WeekStats>,
+    pub fiscal_year: FiscalYearStats,
+}
+
+// How far into the fiscal year today is, going by the fiscal year start setting
+fn fiscal_year_stats(today: NaiveDate, now: DateTime<Utc>) -> FiscalYearStats {
+    let start_month = timers::fiscal_year_start();
+    let (start, end) = period_bounds(CalendarPeriod::FiscalYear, today, start_month);
+    FiscalYearStats {
+        label: i18n::t_args("fiscal-year-label", &[("year", fiscal_year(today, start_month).to_string().into())]),
+        start,
+        end: end - Days::new(1),
+        percent_elapsed: percent_elapsed(local_midnight(start), local_midnight(end), now).unwrap_or(0),
+    }
+}
+
+fn local_midnight(date: NaiveDate) -> DateTime<Utc> {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    // Midnight can be skipped by a DST change; fall back to reading it as UTC
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map_or_else(|| midnight.and_utc(), |at| at.with_timezone(&Utc))
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Days::new(date.weekday().num_days_from_monday().into())
+}
+
+#[tauri::command]
+pub async fn get_statistics(
+    timer_state: State<'_, TimerState>,
+    stats_state: State<'_, StatsState>,
+    range: Option<StatsRange>,
+) -> Result<Statistics, String> {
+    let now = Utc::now();
+    let today = now.with_timezone(&Local).date_naive();
+    let first_day = today - Days::new(range.unwrap_or_default().days() - 1);
+    let dates: Vec<NaiveDate> = first_day.iter_days().take_while(|date| *date <= today).collect();
+
+    let store = timer_state.lock()?;
+    let timers: Vec<_> = store.timers.iter().filter(|t| t.status != TimerStatus::Trashed).collect();
+    let log = stats_state.lock()?;
+
+    let days = dates
+        .iter()
+        .map(|date| {
+            let day_start = local_midnight(*date);
+            let day_end = local_midnight(*date + Days::new(1)).min(now);
+            let elapsed_seconds = timers
+                .iter()
+                .map(|t| (t.end_date.min(day_end) - t.start_date.max(day_start)).num_seconds().max(0))
+                .sum();
+            DayStats {
+                date: *date,
+                elapsed_seconds,
+                notifications: log.notifications.get(date).copied().unwrap_or(0),
+            }
+        })
+        .collect();
+
+    let mut weeks: BTreeMap<NaiveDate, u32> = dates.iter().map(|date| (week_start(*date), 0)).collect();
+    for timer in timers.iter().filter(|t| t.end_date <= now) {
+        let ended = timer.end_date.with_timezone(&Local).date_naive();
+        if ended >= first_day {
+            *weeks.entry(week_start(ended)).or_default() += 1;
+        }
+    }
+    let weeks = weeks.into_iter().map(|(week_start, completed)| WeekStats { week_start, completed }).collect();
+
+    Ok(Statistics { days, weeks, fiscal_year: fiscal_year_stats(today, now) })
+}
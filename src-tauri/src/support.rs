@@ -0,0 +1,158 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use chrono::{Local, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::audit::AuditState;
+use crate::health;
+use crate::settings::SettingsState;
+use crate::timers::{TimerState, TimerStatus};
+use crate::updates;
+
+const REDACTED: &str = "<redacted>";
+// Settings that say where the user goes or what they run, rather than how the app behaves
+const PRIVATE_SETTINGS: [&str; 5] = [
+    "app_blocklist",
+    "blocked_sites",
+    "local_api_bind_address",
+    "local_api_cors_origins",
+    "viewer_source",
+];
+
+// Keeps the shape of a private value, so "three sites blocked" still shows, but not its content
+fn redact(value: &mut Value) {
+    match value {
+        Value::Null => {}
+        Value::Array(items) => items.iter_mut().for_each(|item| *item = Value::from(REDACTED)),
+        _ => *value = Value::from(REDACTED),
+    }
+}
+
+fn anonymized_settings(app: &AppHandle) -> Result<Value, String> {
+    let state = app.state::<SettingsState>();
+    let mut settings = serde_json::to_value(&*state.lock()?).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    for field in PRIVATE_SETTINGS {
+        if let Some(value) = settings.get_mut(field) {
+            redact(value);
+        }
+    }
+    let problems = state.load_problems.lock().map_err(|e| format!("Failed to lock settings problems: {}", e))?.clone();
+    Ok(json!({ "settings": settings, "problems_on_load": problems }))
+}
+
+// Counts only; labels and notes stay out of the bundle
+fn timer_summary(app: &AppHandle) -> Result<Value, String> {
+    let store = app.state::<TimerState>();
+    let store = store.lock()?;
+    let count = |status: TimerStatus| store.timers.iter().filter(|t| t.status == status).count();
+    Ok(json!({
+        "active": count(TimerStatus::Active),
+        "archived": count(TimerStatus::Archived),
+        "trashed": count(TimerStatus::Trashed),
+        "recurring": store.timers.iter().filter(|t| t.recurrence.is_some()).count(),
+    }))
+}
+
+// The local API log, without who made each request or from where
+fn api_log(app: &AppHandle) -> Result<Value, String> {
+    let entries = app.state::<AuditState>().lock()?.clone();
+    Ok(entries
+        .into_iter()
+        .map(|entry| {
+            json!({
+                "at": entry.at,
+                "client": entry.client.map(|_| REDACTED),
+                "action": entry.action,
+                "error": entry.error,
+            })
+        })
+        .collect())
+}
+
+fn system_info() -> Value {
+    json!({
+        "version": updates::current_version(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "os_version": sysinfo::System::long_os_version(),
+        "created_at": Utc::now(),
+    })
+}
+
+fn write_bundle(path: &Path, files: &[(&str, Value)]) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, value) in files {
+        let contents = serde_json::to_vec_pretty(value).map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+        zip.start_file(*name, options).map_err(|e| format!("Failed to add {} to the bundle: {}", name, e))?;
+        zip.write_all(&contents).map_err(|e| format!("Failed to add {} to the bundle: {}", name, e))?;
+    }
+    zip.finish().map_err(|e| format!("Failed to finish the bundle: {}", e))?;
+    Ok(())
+}
+
+// Shows the file selected in the file manager where the platform can, or its folder otherwise
+#[cfg(windows)]
+fn reveal(path: &Path) -> Result<(), String> {
+    Command::new("explorer")
+        .arg(format!("/select,{}", path.display()))
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open the file manager: {}", e))
+}
+
+#[cfg(target_os = "macos")]
+fn reveal(path: &Path) -> Result<(), String> {
+    Command::new("open")
+        .arg("-R")
+        .arg(path)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open the file manager: {}", e))
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn reveal(path: &Path) -> Result<(), String> {
+    Command::new("xdg-open")
+        .arg(path.parent().unwrap_or(path))
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open the file manager: {}", e))
+}
+
+#[derive(Serialize)]
+pub struct SupportBundle {
+    pub path: PathBuf,
+}
+
+// Collects diagnostics into a zip in the downloads folder and shows it, ready to attach to a
+// bug report. There's no log file, so the local API log stands in for one.
+#[tauri::command]
+pub async fn create_support_bundle(app: AppHandle) -> Result<SupportBundle, String> {
+    let health = health::get_health(app.clone()).await?;
+    let files = [
+        ("system.json", system_info()),
+        ("health.json", serde_json::to_value(&health).map_err(|e| format!("Failed to serialize health checks: {}", e))?),
+        ("settings.json", anonymized_settings(&app)?),
+        ("timers.json", timer_summary(&app)?),
+        ("api_log.json", api_log(&app)?),
+    ];
+
+    let folder = match dirs::download_dir() {
+        Some(folder) => folder,
+        None => app.path().app_data_dir().map_err(|e| format!("Failed to find a folder for the bundle: {}", e))?,
+    };
+    let path = folder.join(format!("hourglass-support-{}.zip", Local::now().format("%Y%m%d-%H%M%S")));
+    write_bundle(&path, &files)?;
+    if let Err(e) = reveal(&path) {
+        eprintln!("Failed to reveal support bundle: {}", e);
+    }
+    Ok(SupportBundle { path })
+}
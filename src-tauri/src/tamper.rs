@@ -0,0 +1,172 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::time::interval;
+
+use crate::edit_lock::EditLockState;
+use crate::{passcode, persistence};
+
+const WATCH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+// Smaller corrections are the OS keeping time, not someone winding the clock back
+const CLOCK_TOLERANCE_SECONDS: i64 = 120;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TamperKind {
+    ClockMovedBack { seconds: i64 },
+    EndDateChanged { from: DateTime<Utc>, to: DateTime<Utc> },
+    // The debug time offset was used while the lock was on
+    TimeOffset { seconds: i64 },
+}
+
+// Each entry's hash covers the one before it, so changing or removing an earlier entry in the
+// file breaks the chain from there on
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TamperEntry {
+    pub at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub kind: TamperKind,
+    pub hash: String,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct TamperLog {
+    pub entries: Vec<TamperEntry>,
+    // The latest wall-clock time the app saw, to notice the clock going back while it was closed
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+fn chain_hash(previous: &str, at: DateTime<Utc>, kind: &TamperKind) -> String {
+    let kind = serde_json::to_string(kind).unwrap_or_default();
+    passcode::token_hash(&format!("{}|{}|{}", previous, at.to_rfc3339(), kind))
+}
+
+impl TamperLog {
+    fn intact(&self) -> bool {
+        let mut previous = String::new();
+        self.entries.iter().all(|entry| {
+            let expected = chain_hash(&previous, entry.at, &entry.kind);
+            previous = entry.hash.clone();
+            passcode::secrets_match(&expected, &entry.hash)
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct TamperState {
+    pub log: Arc<Mutex<TamperLog>>,
+    pub data_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl TamperState {
+    pub fn lock(&self) -> Result<MutexGuard<'_, TamperLog>, String> {
+        self.log.lock().map_err(|e| format!("Failed to lock tamper log: {}", e))
+    }
+
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let loaded: TamperLog = persistence::load_json(&path);
+        *self.lock()? = loaded;
+        *self.data_path.lock().map_err(|e| format!("Failed to lock tamper log path: {}", e))? = Some(path);
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = self.data_path.lock().map_err(|e| format!("Failed to lock tamper log path: {}", e))?.clone();
+        match path {
+            Some(path) => persistence::save_json(&path, &*self.lock()?),
+            None => Ok(()),
+        }
+    }
+}
+
+fn locked(app: &AppHandle) -> bool {
+    app.state::<EditLockState>().lock().is_ok_and(|lock| lock.passcode.is_some())
+}
+
+// Appends to the trail if the countdown is locked, and tells the UI
+pub fn record(app: &AppHandle, kind: TamperKind) {
+    if !locked(app) {
+        return;
+    }
+    let state = app.state::<TamperState>();
+    let entry = state.lock().map(|mut log| {
+        let at = Utc::now();
+        let previous = log.entries.last().map(|entry| entry.hash.clone()).unwrap_or_default();
+        let hash = chain_hash(&previous, at, &kind);
+        let entry = TamperEntry { at, kind, hash };
+        log.entries.push(entry.clone());
+        entry
+    });
+    match entry.and_then(|entry| state.save().map(|_| entry)) {
+        Ok(entry) => {
+            if let Err(e) = app.emit("countdown-tampered", entry) {
+                eprintln!("Failed to emit tamper event: {}", e);
+            }
+        }
+        Err(e) => eprintln!("Failed to record tampering: {}", e),
+    }
+}
+
+fn note_time(app: &AppHandle, now: DateTime<Utc>) {
+    let state = app.state::<TamperState>();
+    if let Ok(mut log) = state.lock() {
+        log.last_seen = Some(now);
+    }
+    if let Err(e) = state.save() {
+        eprintln!("Failed to save tamper log: {}", e);
+    }
+}
+
+// Watches for the system clock being set back, which would stretch a locked countdown. The
+// monotonic clock says how much time really passed between checks; across restarts, the last
+// time seen stands in for it.
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let now = Utc::now();
+        let last_seen = app.state::<TamperState>().lock().ok().and_then(|log| log.last_seen);
+        if let Some(seen) = last_seen.filter(|seen| (*seen - now).num_seconds() > CLOCK_TOLERANCE_SECONDS) {
+            record(&app, TamperKind::ClockMovedBack { seconds: (seen - now).num_seconds() });
+        }
+        note_time(&app, now);
+
+        let mut baseline = (Instant::now(), now);
+        let mut tick = interval(WATCH_INTERVAL);
+        loop {
+            tick.tick().await;
+            let (started, wall) = baseline;
+            let expected = wall + Duration::from_std(started.elapsed()).unwrap_or_default();
+            let now = Utc::now();
+            let behind = (expected - now).num_seconds();
+            if behind > CLOCK_TOLERANCE_SECONDS {
+                record(&app, TamperKind::ClockMovedBack { seconds: behind });
+            }
+            baseline = (Instant::now(), now);
+            note_time(&app, now);
+        }
+    });
+}
+
+#[derive(Serialize)]
+pub struct TamperStatus {
+    // Something was recorded while the current lock has been on, or the trail was edited
+    pub tampered: bool,
+    pub intact: bool,
+    pub entries: Vec<TamperEntry>,
+}
+
+#[tauri::command]
+pub async fn get_tamper_status(state: State<'_, TamperState>, edit_lock: State<'_, EditLockState>) -> Result<TamperStatus, String> {
+    let locked_at = edit_lock.lock()?.locked_at;
+    let log = state.lock()?;
+    let intact = log.intact();
+    let recorded = locked_at.is_some_and(|since| log.entries.iter().any(|entry| entry.at >= since));
+    Ok(TamperStatus {
+        tampered: recorded || !intact,
+        intact,
+        entries: log.entries.clone(),
+    })
+}
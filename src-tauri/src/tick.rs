@@ -0,0 +1,68 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::Notify;
+use tokio::time::{sleep, Duration};
+
+use crate::{compute_time_remaining, NotificationState};
+
+const DEFAULT_TICK_RATE_MS: u64 = 1000;
+// 10 Hz is plenty for the sand animation; a minute is the slowest the countdown can go without
+// showing stale minutes
+const MIN_TICK_RATE_MS: u64 = 100;
+const MAX_TICK_RATE_MS: u64 = 60_000;
+
+pub struct TickState {
+    rate_ms: Arc<AtomicU64>,
+    rate_changed: Arc<Notify>,
+}
+
+impl Default for TickState {
+    fn default() -> Self {
+        Self {
+            rate_ms: Arc::new(AtomicU64::new(DEFAULT_TICK_RATE_MS)),
+            rate_changed: Arc::new(Notify::new()),
+        }
+    }
+}
+
+// Pushes "countdown-tick" with the same payload as get_time_remaining, so the frontend doesn't
+// have to poll. Nothing is emitted while no countdown is set.
+pub fn spawn(app: AppHandle) {
+    let state = app.state::<TickState>();
+    let rate_ms = state.rate_ms.clone();
+    let rate_changed = state.rate_changed.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Ok(remaining) = compute_time_remaining(&app.state::<NotificationState>()) {
+                if let Err(e) = app.emit("countdown-tick", remaining) {
+                    eprintln!("Failed to emit countdown tick: {}", e);
+                }
+            }
+            // A new rate takes effect immediately instead of after a long sleep
+            let delay = Duration::from_millis(rate_ms.load(Ordering::SeqCst));
+            tokio::select! {
+                _ = sleep(delay) => {}
+                _ = rate_changed.notified() => {}
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_tick_rate(state: State<'_, TickState>) -> Result<u64, String> {
+    Ok(state.rate_ms.load(Ordering::SeqCst))
+}
+
+#[tauri::command]
+pub async fn set_tick_rate(state: State<'_, TickState>, ms: u64) -> Result<u64, String> {
+    if !(MIN_TICK_RATE_MS..=MAX_TICK_RATE_MS).contains(&ms) {
+        return Err(format!(
+            "Tick rate must be between {} and {} ms",
+            MIN_TICK_RATE_MS, MAX_TICK_RATE_MS
+        ));
+    }
+    state.rate_ms.store(ms, Ordering::SeqCst);
+    state.rate_changed.notify_one();
+    Ok(ms)
+}
@@ -0,0 +1,1563 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use hourglass_core::countdown::{percent_elapsed, period_bounds, CalendarPeriod};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::actions::SystemAction;
+use crate::clock;
+use crate::edit_lock::EditLockState;
+use crate::events;
+use crate::focus::FocusEnforcement;
+use crate::remote::RemoteSubscription;
+use crate::notifier::EventKind;
+use crate::{i18n, persistence, send_notification};
+use crate::schedule::{self, CronSchedule, ReminderSchedule, DEFAULT_REMINDER_INTERVAL_SECS};
+use crate::scheduler::{EventSource, SchedulerMessage, SchedulerSender};
+use crate::scripting::{self, ScriptEvent};
+
+// Streak lengths worth a notification of their own
+const STREAK_MILESTONES: [u32; 8] = [3, 7, 14, 30, 50, 100, 200, 365];
+
+// Default "T-minus" reminders: one week, one day and one hour before the end date
+pub const DEFAULT_RELATIVE_REMINDERS: [i64; 3] = [7 * 24 * 60 * 60, 24 * 60 * 60, 60 * 60];
+
+const MIN_SCHEDULE_INTERVAL_SECS: i64 = 60;
+// Reminders for one timer closer together than this are sent as one
+const REMINDER_DEDUP_SECS: i64 = 5 * 60;
+
+// How many percentage points of work may lag the time used up before a check-in warns
+const BEHIND_PACE_POINTS: i64 = 10;
+
+// Month the fiscal year starts in, from the settings
+static FISCAL_YEAR_START: AtomicU32 = AtomicU32::new(1);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TimerStatus {
+    #[default]
+    Active,
+    Archived,
+    Trashed,
+}
+
+// How often a timer starts over once it runs out
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Recurrence {
+    Daily,
+    Weekly,
+}
+
+impl Recurrence {
+    fn period(self) -> Duration {
+        match self {
+            Recurrence::Daily => Duration::days(1),
+            Recurrence::Weekly => Duration::weeks(1),
+        }
+    }
+}
+
+// How much a timer matters, which decides how often it reminds and what may hold it back
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Importance {
+    Low,
+    #[default]
+    Normal,
+    High,
+    Critical,
+}
+
+impl Importance {
+    // Gap between routine reminders for timers without a schedule of their own
+    pub fn reminder_interval(self) -> Duration {
+        let default = Duration::seconds(DEFAULT_REMINDER_INTERVAL_SECS);
+        match self {
+            Importance::Low => default * 2,
+            Importance::Normal => default,
+            Importance::High => default / 3,
+            Importance::Critical => default / 6,
+        }
+    }
+
+    // Whether reminder days, idle deferral, snoozing and hiding reminders while the
+    // countdown is on screen apply
+    pub fn can_be_held_back(self) -> bool {
+        matches!(self, Importance::Low | Importance::Normal)
+    }
+}
+
+// One of several routine schedules a timer can combine, e.g. every 6 hours plus a 09:00 digest
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScheduleSpec {
+    Interval { seconds: i64 },
+    Cron { expression: String },
+}
+
+// How reminders put the time left: exact figures, or rounded the way a person would say it
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ReminderWording {
+    #[default]
+    Exact,
+    // "about 2 weeks left", "just over a day left"
+    Casual,
+}
+
+// The task, milestone or sprint a timer mirrors in another service, so syncing updates it
+// instead of adding a duplicate
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ExternalSource {
+    pub provider: String,
+    pub id: String,
+}
+
+// Consecutive occurrences of a recurring timer the user marked as done
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Streak {
+    pub current: u32,
+    pub best: u32,
+    pub completed_current: bool,
+    pub last_completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Reminder {
+    pub at: DateTime<Utc>,
+    pub fired: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RelativeReminder {
+    pub seconds_before: i64,
+    pub fired: bool,
+}
+
+// A named intermediate deadline inside a timer, e.g. "draft due", with its own T-minus reminders
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Milestone {
+    pub id: u64,
+    pub name: String,
+    pub due: DateTime<Utc>,
+    pub reminders: Vec<RelativeReminder>,
+    pub due_notified: bool,
+    pub done: bool,
+}
+
+impl Milestone {
+    fn reminder_at(&self, reminder: &RelativeReminder) -> DateTime<Utc> {
+        self.due - Duration::seconds(reminder.seconds_before)
+    }
+
+    fn next_event_at(&self) -> Option<DateTime<Utc>> {
+        if self.done || self.due_notified {
+            return None;
+        }
+        let reminders = self.reminders.iter().filter(|r| !r.fired).map(|r| self.reminder_at(r));
+        reminders.chain(Some(self.due)).min()
+    }
+
+    // Moves the milestone to `due`, re-arming the reminders that now lie ahead
+    fn set_due(&mut self, due: DateTime<Utc>, now: DateTime<Utc>) {
+        self.due = due;
+        self.due_notified = due <= now;
+        for reminder in &mut self.reminders {
+            reminder.fired = due - Duration::seconds(reminder.seconds_before) <= now;
+        }
+    }
+}
+
+// Work to get done each day before the local time `reset_at`
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DailyGoal {
+    pub target_minutes: u32,
+    pub reset_at: NaiveTime,
+    // Logged since the last reset
+    pub progress_minutes: u32,
+}
+
+// How far along the work behind a timer was at some point, as reported by the user
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CheckIn {
+    pub at: DateTime<Utc>,
+    pub percent_done: u8,
+    pub note: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Timer {
+    pub id: u64,
+    pub label: String,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub include_note_in_expiry: bool,
+    #[serde(default)]
+    pub status: TimerStatus,
+    #[serde(default)]
+    pub archived_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub reminders: Vec<Reminder>,
+    #[serde(default)]
+    pub relative_reminders: Vec<RelativeReminder>,
+    #[serde(default)]
+    pub reminder_cron: Option<String>,
+    #[serde(default)]
+    pub next_reminder_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub expiry_notified: bool,
+    #[serde(default)]
+    pub sound_path: Option<String>,
+    #[serde(default)]
+    pub expiry_action: Option<SystemAction>,
+    #[serde(default)]
+    pub focus_enforcement: Option<FocusEnforcement>,
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+    #[serde(default)]
+    pub streak: Streak,
+    #[serde(default)]
+    pub source: Option<ExternalSource>,
+    #[serde(default)]
+    pub importance: Importance,
+    #[serde(default)]
+    pub wording: ReminderWording,
+    // Combined routine schedules; when empty, `reminder_cron` or the default cadence applies
+    #[serde(default)]
+    pub reminder_schedules: Vec<ScheduleSpec>,
+    // When this timer last reminded, so reminders from different schedules that land close
+    // together go out once
+    #[serde(default)]
+    pub last_reminded_at: Option<DateTime<Utc>>,
+    // Counts down to the end of the current month, quarter or year, moving on to the next one
+    // once it passes
+    #[serde(default)]
+    pub period: Option<CalendarPeriod>,
+    #[serde(default)]
+    pub milestones: Vec<Milestone>,
+    #[serde(default)]
+    pub checkins: Vec<CheckIn>,
+    // Makes this a daily goal that resets every day instead of a one-shot countdown
+    #[serde(default)]
+    pub goal: Option<DailyGoal>,
+    // Where the authoritative end date is published, if it's kept elsewhere
+    #[serde(default)]
+    pub subscription: Option<RemoteSubscription>,
+    // Archived because its dates were moved into the main countdown
+    #[serde(default)]
+    pub promoted_to_primary: bool,
+}
+
+impl Timer {
+    pub fn new(id: u64, label: String, start_date: DateTime<Utc>, end_date: DateTime<Utc>) -> Self {
+        Self {
+            id,
+            label,
+            start_date,
+            end_date,
+            tags: Vec::new(),
+            color: None,
+            icon: None,
+            note: None,
+            include_note_in_expiry: false,
+            status: TimerStatus::Active,
+            archived_at: None,
+            deleted_at: None,
+            reminders: Vec::new(),
+            relative_reminders: Vec::new(),
+            reminder_cron: None,
+            next_reminder_at: None,
+            expiry_notified: false,
+            sound_path: None,
+            expiry_action: None,
+            focus_enforcement: None,
+            recurrence: None,
+            streak: Streak::default(),
+            source: None,
+            importance: Importance::default(),
+            wording: ReminderWording::default(),
+            reminder_schedules: Vec::new(),
+            last_reminded_at: None,
+            period: None,
+            milestones: Vec::new(),
+            checkins: Vec::new(),
+            goal: None,
+            subscription: None,
+            promoted_to_primary: false,
+        }
+    }
+
+    // Whether the timer starts over once it runs out, instead of staying finished
+    pub fn repeats(&self) -> bool {
+        self.recurrence.is_some() || self.period.is_some() || self.goal.is_some()
+    }
+
+    // Counts the current occurrence as done, once. True when the streak just reached a length
+    // worth a notification.
+    fn complete_current(&mut self, now: DateTime<Utc>) -> bool {
+        if self.streak.completed_current {
+            return false;
+        }
+        self.streak.current += 1;
+        self.streak.best = self.streak.best.max(self.streak.current);
+        self.streak.completed_current = true;
+        self.streak.last_completed_at = Some(now);
+        STREAK_MILESTONES.contains(&self.streak.current)
+    }
+
+    // Moves a recurring timer on to the occurrence that contains `now`, re-arming its
+    // reminders. Every occurrence that ends without being marked done breaks the streak.
+    pub fn roll_forward(&mut self, now: DateTime<Utc>) {
+        if let Some(reset_at) = self.goal.as_ref().map(|goal| goal.reset_at) {
+            while self.end_date <= now {
+                if !self.streak.completed_current {
+                    self.streak.current = 0;
+                }
+                self.streak.completed_current = false;
+                let Some(next) = next_local_time(self.end_date, reset_at) else {
+                    break;
+                };
+                self.start_date = self.end_date;
+                self.end_date = next;
+            }
+            if let Some(goal) = self.goal.as_mut() {
+                goal.progress_minutes = 0;
+            }
+            let end_date = self.end_date;
+            for reminder in &mut self.relative_reminders {
+                reminder.fired = end_date - Duration::seconds(reminder.seconds_before) <= now;
+            }
+            self.expiry_notified = false;
+            return;
+        }
+        if let Some(period) = self.period {
+            if self.end_date <= now {
+                if let Some((start, end)) = period_dates(period, now) {
+                    self.start_date = start;
+                    self.reschedule(end, now);
+                }
+            }
+            return;
+        }
+        let Some(recurrence) = self.recurrence else {
+            return;
+        };
+        let period = recurrence.period();
+        while self.end_date <= now {
+            if !self.streak.completed_current {
+                self.streak.current = 0;
+            }
+            self.streak.completed_current = false;
+            self.start_date += period;
+            self.end_date += period;
+            for reminder in &mut self.reminders {
+                reminder.at += period;
+                reminder.fired = reminder.at <= now;
+            }
+            for milestone in &mut self.milestones {
+                milestone.done = false;
+                milestone.set_due(milestone.due + period, now);
+            }
+        }
+        let end_date = self.end_date;
+        for reminder in &mut self.relative_reminders {
+            reminder.fired = end_date - Duration::seconds(reminder.seconds_before) <= now;
+        }
+        self.expiry_notified = false;
+    }
+
+    // Moves the deadline, re-arming reminders that now lie ahead again
+    pub fn reschedule(&mut self, end_date: DateTime<Utc>, now: DateTime<Utc>) {
+        if self.end_date == end_date {
+            return;
+        }
+        self.end_date = end_date;
+        let offsets: Vec<i64> = self.relative_reminders.iter().map(|r| r.seconds_before).collect();
+        self.relative_reminders.clear();
+        self.set_relative_reminders(&offsets, now);
+        self.expiry_notified = end_date <= now;
+    }
+
+    // Replaces the T-minus offsets while keeping the fired state of offsets that are kept.
+    // Offsets whose instant has already passed count as fired so configuring them doesn't
+    // trigger a burst of stale reminders.
+    pub fn set_relative_reminders(&mut self, seconds_before: &[i64], now: DateTime<Utc>) {
+        let mut reminders: Vec<RelativeReminder> = Vec::new();
+        for &offset in seconds_before {
+            if reminders.iter().any(|r| r.seconds_before == offset) {
+                continue;
+            }
+            let previously_fired = self
+                .relative_reminders
+                .iter()
+                .any(|r| r.seconds_before == offset && r.fired);
+            let already_passed = self.end_date - Duration::seconds(offset) <= now;
+            reminders.push(RelativeReminder {
+                seconds_before: offset,
+                fired: previously_fired || already_passed,
+            });
+        }
+        reminders.sort_by_key(|r| std::cmp::Reverse(r.seconds_before));
+        self.relative_reminders = reminders;
+    }
+
+    // The earliest instant at which this timer has something to fire: an explicit or T-minus
+    // reminder, its recurring schedule, a milestone or its expiry
+    pub fn next_event_at(&self) -> Option<DateTime<Utc>> {
+        if !self.is_active() {
+            return None;
+        }
+        let reminders = self.reminders.iter().filter(|r| !r.fired).map(|r| r.at);
+        let relative = self
+            .relative_reminders
+            .iter()
+            .filter(|r| !r.fired)
+            .map(|r| self.end_date - Duration::seconds(r.seconds_before));
+        let expiry = (!self.expiry_notified).then_some(self.end_date);
+        let milestones = self.milestones.iter().filter_map(Milestone::next_event_at);
+        reminders.chain(relative).chain(self.next_reminder_at).chain(expiry).chain(milestones).min()
+    }
+
+    fn reminded_recently(&self, now: DateTime<Utc>) -> bool {
+        self.last_reminded_at.is_some_and(|at| now - at < Duration::seconds(REMINDER_DEDUP_SECS))
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == TimerStatus::Active
+    }
+
+    // A work session with enforcement that's currently running
+    pub fn is_focus_session(&self, now: DateTime<Utc>) -> bool {
+        self.is_active() && self.focus_enforcement.is_some() && self.start_date <= now && now < self.end_date
+    }
+
+    // Title used for notifications so several countdowns are told apart at a glance
+    pub fn display_title(&self) -> String {
+        let label = if self.label.is_empty() { i18n::t("reminder-title") } else { self.label.clone() };
+        match &self.icon {
+            Some(icon) => format!("{} {}", icon, label),
+            None => label,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TagDefaults {
+    pub notifications_enabled: bool,
+    #[serde(default)]
+    pub reminder_days: Option<Vec<Weekday>>,
+}
+
+impl Default for TagDefaults {
+    fn default() -> Self {
+        Self {
+            notifications_enabled: true,
+            reminder_days: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct TimerStore {
+    pub timers: Vec<Timer>,
+    pub next_id: u64,
+    pub tag_defaults: HashMap<String, TagDefaults>,
+    #[serde(skip)]
+    pub data_path: Option<PathBuf>,
+    // Tells the scheduler task to re-plan around whatever just changed
+    #[serde(skip)]
+    pub scheduler: SchedulerSender,
+    // Where lifecycle events go; unset until the app is up
+    #[serde(skip)]
+    pub events: Option<AppHandle>,
+}
+
+impl TimerStore {
+    pub fn add(&mut self, label: String, start_date: DateTime<Utc>, end_date: DateTime<Utc>) -> &mut Timer {
+        self.next_id += 1;
+        let mut timer = Timer::new(self.next_id, label, start_date, end_date);
+        timer.set_relative_reminders(&DEFAULT_RELATIVE_REMINDERS, clock::now());
+        timer.next_reminder_at = ReminderSchedule::default().next_after(clock::now());
+        self.timers.push(timer);
+        self.scheduler.send(SchedulerMessage::Replan(EventSource::Timer(self.next_id)));
+        self.timers.last_mut().expect("timer was just pushed")
+    }
+
+    // Applies a change to one timer and persists the store, returning the updated timer
+    pub fn update<F>(&mut self, id: u64, change: F) -> Result<Timer, String>
+    where
+        F: FnOnce(&mut Timer) -> Result<(), String>,
+    {
+        let timer = self.get_mut(id).ok_or_else(|| i18n::t_args("error-timer-not-found", &[("id", id.into())]))?;
+        change(timer)?;
+        let updated = timer.clone();
+        self.save()?;
+        self.scheduler.send(SchedulerMessage::Replan(EventSource::Timer(id)));
+        self.emit(events::TIMER_UPDATED, &updated);
+        Ok(updated)
+    }
+
+    pub fn emit(&self, event: &str, timer: &Timer) {
+        if let Some(app) = &self.events {
+            events::timer(app, event, timer);
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        match &self.data_path {
+            Some(path) => persistence::save_json(path, self),
+            None => Ok(()),
+        }
+    }
+
+    pub fn get(&self, id: u64) -> Option<&Timer> {
+        self.timers.iter().find(|t| t.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut Timer> {
+        self.timers.iter_mut().find(|t| t.id == id)
+    }
+
+    // Marks every due reminder as fired and returns the timers that need a notification,
+    // one entry per timer even if several of its reminders came due at once. Reminders of a
+    // timer muted by its tags are marked fired too, so they don't all go off once it's unmuted.
+    pub fn take_due_reminders(&mut self, now: DateTime<Utc>) -> Vec<Timer> {
+        let allowed: Vec<bool> = self.timers.iter().map(|t| self.notifications_enabled_for(t)).collect();
+        let mut due = Vec::new();
+        for (timer, allowed) in self.timers.iter_mut().zip(allowed).filter(|(t, _)| t.is_active()) {
+            let mut fired_any = false;
+            for reminder in timer.reminders.iter_mut().filter(|r| !r.fired && r.at <= now) {
+                reminder.fired = true;
+                fired_any = true;
+            }
+            // T-minus reminders missed while the app was closed still fire once on the next check
+            let end_date = timer.end_date;
+            for reminder in timer
+                .relative_reminders
+                .iter_mut()
+                .filter(|r| !r.fired && end_date - Duration::seconds(r.seconds_before) <= now)
+            {
+                reminder.fired = true;
+                fired_any = true;
+            }
+            if fired_any && allowed && !timer.reminded_recently(now) {
+                timer.last_reminded_at = Some(now);
+                due.push(timer.clone());
+            }
+        }
+        due
+    }
+
+    // Moves timers that follow a fiscal period onto the dates the current fiscal year start
+    // gives them, after that setting changed
+    pub fn realign_fiscal_periods(&mut self, now: DateTime<Utc>) -> Result<(), String> {
+        let mut changed = Vec::new();
+        for timer in self.timers.iter_mut().filter(|t| t.is_active() && t.period.is_some_and(CalendarPeriod::is_fiscal)) {
+            let Some((start, end)) = timer.period.and_then(|period| period_dates(period, now)) else {
+                continue;
+            };
+            if (start, end) != (timer.start_date, timer.end_date) {
+                timer.start_date = start;
+                timer.reschedule(end, now);
+                changed.push(timer.clone());
+            }
+        }
+        if changed.is_empty() {
+            return Ok(());
+        }
+        self.save()?;
+        for timer in &changed {
+            self.scheduler.send(SchedulerMessage::Replan(EventSource::Timer(timer.id)));
+            self.emit(events::TIMER_UPDATED, timer);
+        }
+        Ok(())
+    }
+
+    // Milestones that came due or reached one of their reminders, each reported once. The flag
+    // says whether it's the milestone itself rather than a reminder ahead of it.
+    pub fn take_due_milestones(&mut self, now: DateTime<Utc>) -> Vec<(Timer, Milestone, bool)> {
+        let mut due = Vec::new();
+        for timer in self.timers.iter_mut().filter(|t| t.is_active()) {
+            let mut fired = Vec::new();
+            for milestone in timer.milestones.iter_mut().filter(|m| !m.done && !m.due_notified) {
+                if milestone.due <= now {
+                    milestone.due_notified = true;
+                    milestone.reminders.iter_mut().for_each(|r| r.fired = true);
+                    fired.push((milestone.clone(), true));
+                    continue;
+                }
+                let mut reminded = false;
+                for reminder in milestone.reminders.iter_mut().filter(|r| !r.fired) {
+                    if milestone.due - Duration::seconds(reminder.seconds_before) <= now {
+                        reminder.fired = true;
+                        reminded = true;
+                    }
+                }
+                if reminded {
+                    fired.push((milestone.clone(), false));
+                }
+            }
+            due.extend(fired.into_iter().map(|(milestone, is_due)| (timer.clone(), milestone, is_due)));
+        }
+        due
+    }
+
+    // When the next active timer runs out
+    pub fn next_expiry(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.timers
+            .iter()
+            .filter(|t| t.is_active() && t.end_date > now)
+            .map(|t| t.end_date)
+            .min()
+    }
+
+    // Active timers that ran out since the last check; each one is only reported once. Recurring
+    // ones move straight on to their next occurrence.
+    pub fn take_newly_expired(&mut self, now: DateTime<Utc>) -> Vec<Timer> {
+        let mut expired = Vec::new();
+        for timer in self.timers.iter_mut().filter(|t| t.is_active()) {
+            if !timer.expiry_notified && timer.end_date <= now {
+                timer.expiry_notified = true;
+                expired.push(timer.clone());
+                timer.roll_forward(now);
+            }
+        }
+        expired
+    }
+
+    // Advances each timer's recurring schedule and returns the timers whose reminder is due.
+    // The next fire time is stored on the timer so a restart continues the same cadence.
+    // Reminders that land on a day the timer doesn't remind on are skipped, not postponed.
+    pub fn take_due_scheduled(&mut self, now: DateTime<Utc>, default_days: &[Weekday]) -> Vec<Timer> {
+        let today = now.with_timezone(&Local).weekday();
+        let allowed: Vec<bool> = self
+            .timers
+            .iter()
+            .map(|t| {
+                self.notifications_enabled_for(t)
+                    && (!t.importance.can_be_held_back() || self.reminder_days_for(t, default_days).contains(&today))
+            })
+            .collect();
+        let mut due = Vec::new();
+        for (timer, allowed) in self.timers.iter_mut().zip(allowed) {
+            if !timer.is_active() {
+                continue;
+            }
+            let schedule = schedule::for_timer(timer);
+            match timer.next_reminder_at {
+                Some(next) if next <= now => {
+                    if allowed && !timer.reminded_recently(now) {
+                        timer.last_reminded_at = Some(now);
+                        due.push(timer.clone());
+                    }
+                    timer.next_reminder_at = schedule.next_after(now);
+                }
+                Some(_) => {}
+                // Timers saved before schedules existed pick up their cadence from now on
+                None => timer.next_reminder_at = schedule.next_after(now),
+            }
+        }
+        due
+    }
+
+    // Days on which routine reminders go out for this timer: the days shared by every tag that
+    // restricts them, or the global setting when none of its tags does
+    pub fn reminder_days_for(&self, timer: &Timer, default_days: &[Weekday]) -> Vec<Weekday> {
+        let mut days: Option<Vec<Weekday>> = None;
+        for tag_days in timer
+            .tags
+            .iter()
+            .filter_map(|tag| self.tag_defaults.get(tag))
+            .filter_map(|d| d.reminder_days.as_ref())
+        {
+            days = Some(match days {
+                Some(days) => days.into_iter().filter(|d| tag_days.contains(d)).collect(),
+                None => tag_days.clone(),
+            });
+        }
+        days.unwrap_or_else(|| default_days.to_vec())
+    }
+
+    // A timer is muted as soon as any of its tags has notifications turned off
+    pub fn notifications_enabled_for(&self, timer: &Timer) -> bool {
+        timer.tags.iter().all(|tag| {
+            self.tag_defaults
+                .get(tag)
+                .map(|d| d.notifications_enabled)
+                .unwrap_or(true)
+        })
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct TimerQuery {
+    pub text: Option<String>,
+    pub tags: Vec<String>,
+    pub ends_after: Option<DateTime<Utc>>,
+    pub ends_before: Option<DateTime<Utc>>,
+    pub expiring_within_days: Option<i64>,
+    pub include_archived: bool,
+}
+
+impl TimerQuery {
+    pub fn matches(&self, timer: &Timer, now: DateTime<Utc>) -> bool {
+        let visible = timer.is_active() || (self.include_archived && timer.status == TimerStatus::Archived);
+        if !visible {
+            return false;
+        }
+
+        if let Some(text) = self.text.as_ref().map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty()) {
+            let in_label = timer.label.to_lowercase().contains(&text);
+            let in_note = timer.note.as_ref().is_some_and(|n| n.to_lowercase().contains(&text));
+            if !in_label && !in_note {
+                return false;
+            }
+        }
+
+        let tags = normalize_tags(self.tags.clone());
+        if !tags.iter().all(|tag| timer.tags.contains(tag)) {
+            return false;
+        }
+
+        if self.ends_after.is_some_and(|after| timer.end_date < after) {
+            return false;
+        }
+        if self.ends_before.is_some_and(|before| timer.end_date > before) {
+            return false;
+        }
+
+        // "Expiring within N days" only covers timers that haven't run out yet
+        if let Some(days) = self.expiring_within_days {
+            if timer.end_date <= now || timer.end_date > now + Duration::days(days) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+#[derive(Default)]
+pub struct TimerState {
+    pub store: Arc<Mutex<TimerStore>>,
+}
+
+impl TimerState {
+    pub fn lock(&self) -> Result<MutexGuard<'_, TimerStore>, String> {
+        self.store.lock().map_err(|e| format!("Failed to lock timer store: {}", e))
+    }
+
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let mut loaded: TimerStore = persistence::load_json(&path);
+        loaded.data_path = Some(path);
+        let mut store = self.lock()?;
+        loaded.scheduler = store.scheduler.clone();
+        loaded.events = store.events.clone();
+        *store = loaded;
+        store.scheduler.send(SchedulerMessage::ReplanAll);
+        Ok(())
+    }
+}
+
+// The first time after `after` that the local clock shows `at`. A time skipped by a DST change
+// falls back to the same time the next day.
+fn next_local_time(after: DateTime<Utc>, at: NaiveTime) -> Option<DateTime<Utc>> {
+    let local = after.with_timezone(&Local);
+    (0..3)
+        .filter_map(|days| local.date_naive().checked_add_days(chrono::Days::new(days)))
+        .filter_map(|date| Local.from_local_datetime(&date.and_time(at)).earliest())
+        .map(|moment| moment.with_timezone(&Utc))
+        .find(|moment| *moment > after)
+}
+
+pub fn set_fiscal_year_start(month: u32) {
+    FISCAL_YEAR_START.store(month, Ordering::Relaxed);
+}
+
+pub fn fiscal_year_start() -> u32 {
+    FISCAL_YEAR_START.load(Ordering::Relaxed)
+}
+
+// The local calendar period containing `now`
+pub fn period_dates(period: CalendarPeriod, now: DateTime<Utc>) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let (start, end) = period_bounds(period, now.with_timezone(&Local).date_naive(), fiscal_year_start());
+    let midnight = |date: NaiveDate| Local.from_local_datetime(&date.and_time(NaiveTime::MIN)).earliest().map(|at| at.with_timezone(&Utc));
+    Some((midnight(start)?, midnight(end)?))
+}
+
+// `field` is the translation key naming the field in error messages
+pub fn parse_date(value: &str, field: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|d| d.with_timezone(&Utc))
+        .map_err(|e| i18n::t_args("error-invalid-date", &[("field", i18n::t(field).into()), ("reason", e.to_string().into())]))
+}
+
+// Accepts CSS-style hex colors (#RGB or #RRGGBB) and stores them lowercased
+pub fn normalize_color(color: &str) -> Result<String, String> {
+    let color = color.trim().to_lowercase();
+    let hex = color.strip_prefix('#').unwrap_or("");
+    if (hex.len() == 3 || hex.len() == 6) && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(color)
+    } else {
+        Err(i18n::t_args("error-invalid-color", &[("color", color.into())]))
+    }
+}
+
+// Icons are a single emoji or short symbol; anything longer belongs in the label
+pub fn normalize_icon(icon: &str) -> Result<String, String> {
+    let icon = icon.trim();
+    if icon.chars().count() > 8 || icon.chars().any(char::is_whitespace) {
+        return Err(i18n::t_args("error-invalid-icon", &[("icon", icon.into())]));
+    }
+    Ok(icon.to_string())
+}
+
+const MAX_NOTE_LENGTH: usize = 10_000;
+
+// Tags are free-form but compared case-insensitively, so "Work" and "work" are the same tag
+pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+#[tauri::command]
+pub async fn create_timer(
+    app: AppHandle,
+    state: State<'_, TimerState>,
+    label: String,
+    start_date: String,
+    end_date: String,
+    tags: Option<Vec<String>>,
+) -> Result<Timer, String> {
+    let start = parse_date(&start_date, "field-start-date")?;
+    let end = parse_date(&end_date, "field-end-date")?;
+    if end <= start {
+        return Err(i18n::t("error-end-before-start"));
+    }
+
+    let mut store = state.lock()?;
+    let timer = store.add(label.trim().to_string(), start, end);
+    timer.tags = normalize_tags(tags.unwrap_or_default());
+    let timer = timer.clone();
+    store.save()?;
+    store.emit(events::TIMER_CREATED, &timer);
+    scripting::dispatch(&app, ScriptEvent::TimerCreated, serde_json::json!({ "timer": timer }));
+    Ok(timer)
+}
+
+// A timer for the end of the current month, quarter or year that rolls over on its own
+#[tauri::command]
+pub async fn create_preset_timer(
+    app: AppHandle,
+    state: State<'_, TimerState>,
+    period: CalendarPeriod,
+    label: Option<String>,
+) -> Result<Timer, String> {
+    let (start, end) = period_dates(period, clock::now()).ok_or_else(|| i18n::t("error-period-dates"))?;
+    let label = label.map(|label| label.trim().to_string()).filter(|label| !label.is_empty()).unwrap_or_else(|| {
+        i18n::t(match period {
+            CalendarPeriod::Month => "preset-end-of-month",
+            CalendarPeriod::Quarter => "preset-end-of-quarter",
+            CalendarPeriod::Year => "preset-end-of-year",
+            CalendarPeriod::FiscalQuarter => "preset-end-of-fiscal-quarter",
+            CalendarPeriod::FiscalYear => "preset-end-of-fiscal-year",
+        })
+    });
+
+    let mut store = state.lock()?;
+    let timer = store.add(label, start, end);
+    timer.period = Some(period);
+    let timer = timer.clone();
+    store.save()?;
+    store.emit(events::TIMER_CREATED, &timer);
+    scripting::dispatch(&app, ScriptEvent::TimerCreated, serde_json::json!({ "timer": timer }));
+    Ok(timer)
+}
+
+fn milestone_reminders(seconds_before: &[i64], due: DateTime<Utc>, now: DateTime<Utc>) -> Vec<RelativeReminder> {
+    let mut offsets: Vec<i64> = seconds_before.iter().copied().filter(|offset| *offset > 0).collect();
+    offsets.sort_unstable_by_key(|offset| std::cmp::Reverse(*offset));
+    offsets.dedup();
+    offsets
+        .into_iter()
+        .map(|seconds_before| RelativeReminder { seconds_before, fired: due - Duration::seconds(seconds_before) <= now })
+        .collect()
+}
+
+// Adds a named intermediate deadline, reminded `reminders` seconds ahead (one day by default)
+#[tauri::command]
+pub async fn add_timer_milestone(
+    state: State<'_, TimerState>,
+    id: u64,
+    name: String,
+    due: String,
+    reminders: Option<Vec<i64>>,
+) -> Result<Timer, String> {
+    let due = parse_date(&due, "field-milestone-date")?;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(i18n::t("error-milestone-name"));
+    }
+    let now = clock::now();
+    let reminders = milestone_reminders(&reminders.unwrap_or_else(|| vec![24 * 60 * 60]), due, now);
+
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        if due > timer.end_date {
+            return Err(i18n::t("error-milestone-after-end"));
+        }
+        let milestone_id = timer.milestones.iter().map(|m| m.id).max().unwrap_or(0) + 1;
+        timer.milestones.push(Milestone {
+            id: milestone_id,
+            name,
+            due,
+            reminders,
+            due_notified: due <= now,
+            done: false,
+        });
+        timer.milestones.sort_by_key(|m| m.due);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn remove_timer_milestone(state: State<'_, TimerState>, id: u64, milestone_id: u64) -> Result<Timer, String> {
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        let before = timer.milestones.len();
+        timer.milestones.retain(|m| m.id != milestone_id);
+        if timer.milestones.len() == before {
+            return Err(i18n::t_args("error-milestone-not-found", &[("id", milestone_id.into())]));
+        }
+        Ok(())
+    })
+}
+
+// A milestone marked done stops reminding
+#[tauri::command]
+pub async fn set_milestone_done(state: State<'_, TimerState>, id: u64, milestone_id: u64, done: bool) -> Result<Timer, String> {
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        let milestone = timer
+            .milestones
+            .iter_mut()
+            .find(|m| m.id == milestone_id)
+            .ok_or_else(|| i18n::t_args("error-milestone-not-found", &[("id", milestone_id.into())]))?;
+        milestone.done = done;
+        Ok(())
+    })
+}
+
+#[derive(Serialize)]
+pub struct CheckInReport {
+    pub timer: Timer,
+    pub percent_elapsed: Option<i64>,
+    // Percentage points of work behind the share of time used up; 0 when on or ahead of pace
+    pub behind_by: i64,
+}
+
+// Records progress on the timer's work. Falling well behind the time already used up gets a
+// warning right away rather than at the next reminder.
+#[tauri::command]
+pub async fn log_checkin(
+    app: AppHandle,
+    state: State<'_, TimerState>,
+    timer_id: u64,
+    percent_done: u8,
+    note: Option<String>,
+) -> Result<CheckInReport, String> {
+    if percent_done > 100 {
+        return Err(i18n::t("error-checkin-percent"));
+    }
+    let now = clock::now();
+    let note = note.map(|note| note.trim().to_string()).filter(|note| !note.is_empty());
+    let timer = state.lock()?.update(timer_id, |timer| {
+        timer.checkins.push(CheckIn { at: now, percent_done, note });
+        Ok(())
+    })?;
+
+    let percent_elapsed = percent_elapsed(timer.start_date, timer.end_date, now);
+    let behind_by = percent_elapsed.map_or(0, |elapsed| (elapsed - i64::from(percent_done)).max(0));
+    if behind_by >= BEHIND_PACE_POINTS {
+        let body = i18n::t_args(
+            "checkin-behind-pace",
+            &[("done", percent_done.into()), ("elapsed", percent_elapsed.unwrap_or_default().into())],
+        );
+        send_notification(&app, EventKind::Reminder, &timer.display_title(), &body);
+    }
+    Ok(CheckInReport { timer, percent_elapsed, behind_by })
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineKind {
+    Start,
+    Milestone,
+    End,
+}
+
+#[derive(Serialize)]
+pub struct TimelineEntry {
+    pub kind: TimelineKind,
+    pub milestone_id: Option<u64>,
+    pub name: String,
+    pub at: DateTime<Utc>,
+    pub done: bool,
+    pub passed: bool,
+    // Negative once it has passed
+    pub remaining_ms: i64,
+}
+
+// The timer's start, its milestones and its end, in date order
+#[tauri::command]
+pub async fn get_timer_timeline(state: State<'_, TimerState>, id: u64) -> Result<Vec<TimelineEntry>, String> {
+    let store = state.lock()?;
+    let timer = store.get(id).ok_or_else(|| i18n::t_args("error-timer-not-found", &[("id", id.into())]))?;
+    let now = clock::now();
+    let entry = |kind: TimelineKind, milestone_id: Option<u64>, name: String, at: DateTime<Utc>, done: bool| TimelineEntry {
+        kind,
+        milestone_id,
+        name,
+        at,
+        done,
+        passed: at <= now,
+        remaining_ms: (at - now).num_milliseconds(),
+    };
+    let mut timeline = vec![entry(TimelineKind::Start, None, i18n::t("timeline-start"), timer.start_date, timer.start_date <= now)];
+    timeline.extend(
+        timer
+            .milestones
+            .iter()
+            .map(|m| entry(TimelineKind::Milestone, Some(m.id), m.name.clone(), m.due, m.done)),
+    );
+    timeline.push(entry(TimelineKind::End, None, timer.display_title(), timer.end_date, timer.end_date <= now));
+    timeline.sort_by_key(|entry| entry.at);
+    Ok(timeline)
+}
+
+#[tauri::command]
+pub async fn list_timers(
+    state: State<'_, TimerState>,
+    filter_by_tag: Option<String>,
+    include_archived: Option<bool>,
+) -> Result<Vec<Timer>, String> {
+    let store = state.lock()?;
+    let tag = filter_by_tag.map(|t| t.trim().to_lowercase());
+    let include_archived = include_archived.unwrap_or(false);
+    Ok(store
+        .timers
+        .iter()
+        .filter(|t| t.is_active() || (include_archived && t.status == TimerStatus::Archived))
+        .filter(|t| tag.as_ref().is_none_or(|tag| t.tags.contains(tag)))
+        .cloned()
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_timer(state: State<'_, TimerState>, id: u64) -> Result<Timer, String> {
+    let store = state.lock()?;
+    store.get(id).cloned().ok_or_else(|| i18n::t_args("error-timer-not-found", &[("id", id.into())]))
+}
+
+// Deleting only moves the timer to the trash; it stays restorable until the trash is emptied
+#[tauri::command]
+pub async fn delete_timer(
+    state: State<'_, TimerState>,
+    edit_lock: State<'_, EditLockState>,
+    id: u64,
+    passcode: Option<String>,
+) -> Result<(), String> {
+    edit_lock.check(passcode.as_deref())?;
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        if timer.status == TimerStatus::Trashed {
+            return Err(i18n::t_args("error-timer-already-trashed", &[("id", id.into())]));
+        }
+        timer.status = TimerStatus::Trashed;
+        timer.deleted_at = Some(clock::now());
+        Ok(())
+    })?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn archive_timer(state: State<'_, TimerState>, id: u64) -> Result<Timer, String> {
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        if timer.status != TimerStatus::Active {
+            return Err(i18n::t_args("error-timer-not-active", &[("id", id.into())]));
+        }
+        timer.status = TimerStatus::Archived;
+        timer.archived_at = Some(clock::now());
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn unarchive_timer(state: State<'_, TimerState>, id: u64) -> Result<Timer, String> {
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        if timer.status != TimerStatus::Archived {
+            return Err(i18n::t_args("error-timer-not-archived", &[("id", id.into())]));
+        }
+        timer.status = TimerStatus::Active;
+        timer.archived_at = None;
+        timer.promoted_to_primary = false;
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn list_trash(state: State<'_, TimerState>) -> Result<Vec<Timer>, String> {
+    let store = state.lock()?;
+    Ok(store
+        .timers
+        .iter()
+        .filter(|t| t.status == TimerStatus::Trashed)
+        .cloned()
+        .collect())
+}
+
+// Restored timers go back to where they were before deletion: the archive or the active list
+#[tauri::command]
+pub async fn restore_timer(state: State<'_, TimerState>, id: u64) -> Result<Timer, String> {
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        if timer.status != TimerStatus::Trashed {
+            return Err(i18n::t_args("error-timer-not-trashed", &[("id", id.into())]));
+        }
+        timer.status = if timer.archived_at.is_some() { TimerStatus::Archived } else { TimerStatus::Active };
+        timer.deleted_at = None;
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn empty_trash(
+    state: State<'_, TimerState>,
+    edit_lock: State<'_, EditLockState>,
+    passcode: Option<String>,
+) -> Result<usize, String> {
+    edit_lock.check(passcode.as_deref())?;
+    let mut store = state.lock()?;
+    let before = store.timers.len();
+    store.timers.retain(|t| t.status != TimerStatus::Trashed);
+    store.save()?;
+    Ok(before - store.timers.len())
+}
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HistorySort {
+    #[default]
+    EndDate,
+    Duration,
+}
+
+#[derive(Serialize)]
+pub struct HistoryPage {
+    pub items: Vec<Timer>,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+const DEFAULT_HISTORY_PAGE_SIZE: usize = 50;
+const MAX_HISTORY_PAGE_SIZE: usize = 500;
+
+// History is every archived timer, most recently finished first unless asked otherwise
+#[tauri::command]
+pub async fn get_timer_history(
+    state: State<'_, TimerState>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    sort_by: Option<HistorySort>,
+    ascending: Option<bool>,
+) -> Result<HistoryPage, String> {
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(DEFAULT_HISTORY_PAGE_SIZE).clamp(1, MAX_HISTORY_PAGE_SIZE);
+
+    let store = state.lock()?;
+    let mut history: Vec<&Timer> = store
+        .timers
+        .iter()
+        .filter(|t| t.status == TimerStatus::Archived)
+        .collect();
+
+    match sort_by.unwrap_or_default() {
+        HistorySort::EndDate => history.sort_by_key(|t| t.end_date),
+        HistorySort::Duration => history.sort_by_key(|t| t.end_date - t.start_date),
+    }
+    if !ascending.unwrap_or(false) {
+        history.reverse();
+    }
+
+    let total = history.len();
+    let items = history.into_iter().skip(offset).take(limit).cloned().collect();
+    Ok(HistoryPage { items, total, offset, limit })
+}
+
+#[tauri::command]
+pub async fn set_timer_tags(
+    state: State<'_, TimerState>,
+    id: u64,
+    tags: Vec<String>,
+) -> Result<Timer, String> {
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        timer.tags = normalize_tags(tags);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn get_tag_defaults(
+    state: State<'_, TimerState>,
+) -> Result<HashMap<String, TagDefaults>, String> {
+    let store = state.lock()?;
+    Ok(store.tag_defaults.clone())
+}
+
+#[tauri::command]
+pub async fn set_tag_defaults(
+    state: State<'_, TimerState>,
+    tag: String,
+    defaults: TagDefaults,
+) -> Result<(), String> {
+    let tag = tag.trim().to_lowercase();
+    if tag.is_empty() {
+        return Err(i18n::t("error-empty-tag"));
+    }
+    if defaults.reminder_days.as_ref().is_some_and(|days| days.is_empty()) {
+        return Err(i18n::t("error-tag-no-reminder-days"));
+    }
+    let mut store = state.lock()?;
+    store.tag_defaults.insert(tag, defaults);
+    store.save()
+}
+
+#[tauri::command]
+pub async fn set_timer_appearance(
+    state: State<'_, TimerState>,
+    id: u64,
+    color: Option<String>,
+    icon: Option<String>,
+) -> Result<Timer, String> {
+    // Empty strings clear the value so the UI can reset to the default look
+    let color = color.filter(|c| !c.trim().is_empty()).map(|c| normalize_color(&c)).transpose()?;
+    let icon = icon.filter(|i| !i.trim().is_empty()).map(|i| normalize_icon(&i)).transpose()?;
+
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        timer.color = color;
+        timer.icon = icon;
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn set_timer_note(
+    state: State<'_, TimerState>,
+    id: u64,
+    note: Option<String>,
+    include_in_expiry: bool,
+) -> Result<Timer, String> {
+    // Notes are Markdown and kept verbatim apart from surrounding whitespace
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if let Some(note) = &note {
+        if note.chars().count() > MAX_NOTE_LENGTH {
+            return Err(i18n::t_args("error-note-too-long", &[("max", MAX_NOTE_LENGTH.into())]));
+        }
+    }
+
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        timer.note = note;
+        timer.include_note_in_expiry = include_in_expiry;
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn search_timers(
+    state: State<'_, TimerState>,
+    query: TimerQuery,
+) -> Result<Vec<Timer>, String> {
+    if query.expiring_within_days.is_some_and(|days| days < 0) {
+        return Err(i18n::t("error-negative-expiring-within"));
+    }
+
+    let store = state.lock()?;
+    let now = clock::now();
+    let mut results: Vec<Timer> = store
+        .timers
+        .iter()
+        .filter(|t| query.matches(t, now))
+        .cloned()
+        .collect();
+    results.sort_by_key(|t| t.end_date);
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn add_timer_reminder(
+    state: State<'_, TimerState>,
+    id: u64,
+    at: String,
+) -> Result<Timer, String> {
+    let at = parse_date(&at, "field-reminder-date")?;
+    if at <= clock::now() {
+        return Err(i18n::t("error-reminder-in-past"));
+    }
+
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        if timer.reminders.iter().any(|r| r.at == at) {
+            return Err(i18n::t("error-reminder-exists"));
+        }
+        timer.reminders.push(Reminder { at, fired: false });
+        timer.reminders.sort_by_key(|r| r.at);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn remove_timer_reminder(
+    state: State<'_, TimerState>,
+    id: u64,
+    at: String,
+) -> Result<Timer, String> {
+    let at = parse_date(&at, "field-reminder-date")?;
+
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        let before = timer.reminders.len();
+        timer.reminders.retain(|r| r.at != at);
+        if timer.reminders.len() == before {
+            return Err(i18n::t("error-reminder-not-found"));
+        }
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn set_relative_reminders(
+    state: State<'_, TimerState>,
+    id: u64,
+    seconds_before: Vec<i64>,
+) -> Result<Timer, String> {
+    if seconds_before.iter().any(|&s| s <= 0) {
+        return Err(i18n::t("error-relative-reminder-not-positive"));
+    }
+
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        timer.set_relative_reminders(&seconds_before, clock::now());
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn set_timer_schedule(
+    state: State<'_, TimerState>,
+    id: u64,
+    cron: Option<String>,
+) -> Result<Timer, String> {
+    let cron = cron.map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+    if let Some(expression) = &cron {
+        CronSchedule::parse(expression)?;
+    }
+
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        timer.reminder_cron = cron;
+        timer.next_reminder_at = schedule::for_timer(timer).next_after(clock::now());
+        Ok(())
+    })
+}
+
+// Changing importance changes the default cadence, so the next reminder is replanned too
+#[tauri::command]
+pub async fn set_timer_importance(state: State<'_, TimerState>, id: u64, importance: Importance) -> Result<Timer, String> {
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        timer.importance = importance;
+        timer.next_reminder_at = schedule::for_timer(timer).next_after(clock::now());
+        Ok(())
+    })
+}
+
+// Replaces the timer's routine schedules with all of `schedules` at once. An interval counts
+// from the timer's last reminder, whichever schedule sent it.
+#[tauri::command]
+pub async fn set_timer_schedules(state: State<'_, TimerState>, id: u64, schedules: Vec<ScheduleSpec>) -> Result<Timer, String> {
+    let mut cleaned = Vec::new();
+    for spec in schedules {
+        let spec = match spec {
+            ScheduleSpec::Interval { seconds } if seconds < MIN_SCHEDULE_INTERVAL_SECS => {
+                return Err(i18n::t_args("error-schedule-interval-too-short", &[("min", MIN_SCHEDULE_INTERVAL_SECS.into())]));
+            }
+            ScheduleSpec::Cron { expression } => {
+                let expression = expression.trim().to_string();
+                CronSchedule::parse(&expression)?;
+                ScheduleSpec::Cron { expression }
+            }
+            spec => spec,
+        };
+        if !cleaned.contains(&spec) {
+            cleaned.push(spec);
+        }
+    }
+
+    state.lock()?.update(id, |timer| {
+        timer.reminder_schedules = cleaned;
+        timer.next_reminder_at = schedule::for_timer(timer).next_after(clock::now());
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn set_timer_wording(state: State<'_, TimerState>, id: u64, wording: ReminderWording) -> Result<Timer, String> {
+    state.lock()?.update(id, |timer| {
+        timer.wording = wording;
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn preview_reminder_schedule(
+    cron: String,
+    count: Option<usize>,
+) -> Result<Vec<DateTime<Utc>>, String> {
+    let schedule = CronSchedule::parse(cron.trim())?;
+    let mut upcoming = Vec::new();
+    let mut after = clock::now();
+    for _ in 0..count.unwrap_or(5).min(50) {
+        match schedule.next_after(after) {
+            Some(next) => {
+                upcoming.push(next);
+                after = next;
+            }
+            None => break,
+        }
+    }
+    Ok(upcoming)
+}
+
+#[tauri::command]
+pub async fn set_timer_recurrence(
+    state: State<'_, TimerState>,
+    id: u64,
+    recurrence: Option<Recurrence>,
+) -> Result<Timer, String> {
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        timer.recurrence = recurrence;
+        if recurrence.is_none() {
+            timer.streak = Streak::default();
+        }
+        timer.roll_forward(clock::now());
+        Ok(())
+    })
+}
+
+// Marks the current occurrence of a recurring timer as done. Doing so twice in one occurrence
+// doesn't count twice.
+#[tauri::command]
+pub async fn complete_timer_occurrence(
+    app: AppHandle,
+    state: State<'_, TimerState>,
+    id: u64,
+) -> Result<Timer, String> {
+    let mut store = state.lock()?;
+    let timer = store.update(id, |timer| {
+        let recurrence = timer.recurrence.ok_or_else(|| i18n::t("error-timer-not-recurring"))?;
+        if timer.complete_current(clock::now()) {
+            let key = match recurrence {
+                Recurrence::Daily => "streak-milestone-days",
+                Recurrence::Weekly => "streak-milestone-weeks",
+            };
+            celebrate_streak(&app, timer, key);
+        }
+        Ok(())
+    })?;
+    Ok(timer)
+}
+
+fn celebrate_streak(app: &AppHandle, timer: &Timer, key: &str) {
+    send_notification(app, EventKind::Milestone, &timer.display_title(), &i18n::t_args(key, &[("count", timer.streak.current.into())]));
+    scripting::dispatch(app, ScriptEvent::Milestone, serde_json::json!({ "timer": timer, "streak": timer.streak.current }));
+}
+
+// A goal timer counting down to today's reset time, e.g. 6 hours of deep work before 18:00.
+// At the reset the day's progress is cleared and a missed goal breaks the streak.
+#[tauri::command]
+pub async fn create_goal_timer(
+    app: AppHandle,
+    state: State<'_, TimerState>,
+    label: String,
+    target_minutes: u32,
+    reset_at: String,
+) -> Result<Timer, String> {
+    if target_minutes == 0 || target_minutes > 24 * 60 {
+        return Err(i18n::t("error-goal-target"));
+    }
+    let reset_at = NaiveTime::parse_from_str(reset_at.trim(), "%H:%M").map_err(|_| i18n::t("error-goal-reset-time"))?;
+    let now = clock::now();
+    let end = next_local_time(now, reset_at).ok_or_else(|| i18n::t("error-goal-reset-time"))?;
+    let start = end - Duration::days(1);
+
+    let mut store = state.lock()?;
+    let timer = store.add(label.trim().to_string(), start, end);
+    timer.goal = Some(DailyGoal { target_minutes, reset_at, progress_minutes: 0 });
+    let timer = timer.clone();
+    store.save()?;
+    store.emit(events::TIMER_CREATED, &timer);
+    scripting::dispatch(&app, ScriptEvent::TimerCreated, serde_json::json!({ "timer": timer }));
+    Ok(timer)
+}
+
+// Adds time worked towards today's goal; reaching the target counts the day as done
+#[tauri::command]
+pub async fn log_goal_progress(app: AppHandle, state: State<'_, TimerState>, id: u64, minutes: u32) -> Result<Timer, String> {
+    let mut store = state.lock()?;
+    store.update(id, |timer| {
+        let goal = timer.goal.as_mut().ok_or_else(|| i18n::t("error-timer-not-goal"))?;
+        goal.progress_minutes = goal.progress_minutes.saturating_add(minutes);
+        if goal.progress_minutes >= goal.target_minutes && timer.complete_current(clock::now()) {
+            celebrate_streak(&app, timer, "streak-milestone-days");
+        }
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn get_timer_streak(state: State<'_, TimerState>, id: u64) -> Result<Streak, String> {
+    let store = state.lock()?;
+    let timer = store.get(id).ok_or_else(|| i18n::t_args("error-timer-not-found", &[("id", id.into())]))?;
+    Ok(timer.streak.clone())
+}
@@ -0,0 +1,129 @@
+use std::time::Duration;
+use serde::Deserialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::http;
+use crate::integrations::{self, ExternalDeadline, IntegrationState, SyncReport, TodoistConfig};
+
+const PROVIDER: &str = "todoist";
+const TASKS_URL: &str = "https://api.todoist.com/api/v1/tasks";
+const REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Deserialize)]
+struct Due {
+    date: String,
+}
+
+#[derive(Deserialize)]
+struct Task {
+    id: String,
+    content: String,
+    due: Option<Due>,
+}
+
+#[derive(Deserialize)]
+struct TaskPage {
+    results: Vec<Task>,
+    next_cursor: Option<String>,
+}
+
+// Every open task with a due date, following the API's pages
+async fn fetch_deadlines(config: &TodoistConfig) -> Result<Vec<ExternalDeadline>, String> {
+    let client = http::client()?;
+    let mut deadlines = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut query: Vec<(&str, &str)> = Vec::new();
+        if let Some(project_id) = &config.project_id {
+            query.push(("project_id", project_id));
+        }
+        if let Some(cursor) = &cursor {
+            query.push(("cursor", cursor));
+        }
+        let url = tauri::Url::parse_with_params(TASKS_URL, &query).map_err(|e| format!("Failed to build Todoist URL: {}", e))?;
+        let response = client
+            .get(url)
+            .bearer_auth(&config.token)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach Todoist: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Todoist answered {}", response.status()));
+        }
+        let page: TaskPage = response.json().await.map_err(|e| format!("Failed to read Todoist tasks: {}", e))?;
+        deadlines.extend(page.results.into_iter().filter_map(|task| {
+            let due = integrations::parse_due(&task.due?.date)?;
+            Some(ExternalDeadline {
+                id: task.id,
+                title: task.content,
+                due,
+            })
+        }));
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    Ok(deadlines)
+}
+
+async fn sync(app: &AppHandle, config: &TodoistConfig) -> Result<SyncReport, String> {
+    let deadlines = fetch_deadlines(config).await?;
+    // Open tasks are all Todoist returns, so one that's missing was completed or deleted
+    let report = integrations::sync_deadlines(app, PROVIDER, &deadlines, true)?;
+    if let Err(e) = app.emit("timers-synced", PROVIDER) {
+        eprintln!("Failed to emit Todoist sync: {}", e);
+    }
+    Ok(report)
+}
+
+fn config(app: &AppHandle) -> Option<TodoistConfig> {
+    app.state::<IntegrationState>().lock().ok().and_then(|config| config.todoist.clone())
+}
+
+// Keeps timers in step with Todoist while connected
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut tick = tokio::time::interval(REFRESH_INTERVAL);
+        loop {
+            tick.tick().await;
+            let Some(config) = config(&app) else {
+                continue;
+            };
+            if let Err(e) = sync(&app, &config).await {
+                eprintln!("Failed to sync Todoist: {}", e);
+            }
+        }
+    });
+}
+
+// Checks the token with a first sync before saving it
+#[tauri::command]
+pub async fn connect_todoist(
+    app: AppHandle,
+    state: State<'_, IntegrationState>,
+    token: String,
+    project_id: Option<String>,
+) -> Result<SyncReport, String> {
+    let config = TodoistConfig {
+        token: token.trim().to_string(),
+        project_id: project_id.filter(|id| !id.trim().is_empty()),
+    };
+    let report = sync(&app, &config).await?;
+    state.lock()?.todoist = Some(config);
+    state.save()?;
+    Ok(report)
+}
+
+// Timers already imported stay; they just stop being updated
+#[tauri::command]
+pub async fn disconnect_todoist(state: State<'_, IntegrationState>) -> Result<(), String> {
+    state.lock()?.todoist = None;
+    state.save()
+}
+
+#[tauri::command]
+pub async fn sync_todoist(app: AppHandle) -> Result<SyncReport, String> {
+    let config = config(&app).ok_or_else(|| "Todoist isn't connected".to_string())?;
+    sync(&app, &config).await
+}
@@ -0,0 +1,340 @@
+use tauri::image::Image;
+use tauri::menu::{Menu, MenuItem};
+use tauri::tray::{TrayIconBuilder, TrayIconEvent};
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Arc, Mutex};
+use serde::{Deserialize, Serialize};
+use tauri::{App, AppHandle, Emitter, Manager, State, Theme, Wry};
+
+#[cfg(all(unix, not(target_os = "macos")))]
+use crate::dbus;
+use crate::i18n::{t, t_args};
+use crate::settings::SettingsState;
+use crate::timers::TimerState;
+use crate::{clipboard, clock, format, kiosk, main_window, primary_end_date, NotificationState};
+
+pub const TRAY_ID: &str = "main";
+const MENU_TIMER_FROM_CLIPBOARD: &str = "timer-from-clipboard";
+const MENU_QUIT: &str = "quit";
+// How close a deadline has to be for quitting to ask first
+const QUIT_WARNING_WINDOW_MINUTES: i64 = 60;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayMode {
+    // Use the tray when the desktop shows one
+    #[default]
+    Auto,
+    Tray,
+    // Never rely on the tray: closing minimizes, so the taskbar or dock is the way back in
+    NoTray,
+}
+
+// What the main window's close button does
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CloseBehavior {
+    // Keep running in the tray, or minimized when there's no tray
+    #[default]
+    Hide,
+    Quit,
+    // Let the frontend ask, through the "close-requested" event
+    Ask,
+}
+
+// The countdown that quitting would leave without its reminders
+#[derive(Serialize, Clone)]
+pub struct QuitWarning {
+    // None for the main countdown
+    pub label: Option<String>,
+    pub ends_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TrayStatus {
+    Available,
+    // Shown through the old X11 system tray protocol, which some panels only half support
+    Legacy,
+    // Nothing on the desktop displays tray icons
+    Unavailable,
+    // The icon couldn't be created at all
+    Missing,
+    Unknown,
+}
+
+// Tray icons are drawn as a single-color silhouette of the app icon so they stay legible on
+// both light and dark taskbars/menu bars
+fn themed_icon(base: &Image<'_>, theme: Theme) -> Image<'static> {
+    let (r, g, b) = if theme == Theme::Dark { (0xf5, 0xf5, 0xf5) } else { (0x1f, 0x1f, 0x1f) };
+    let rgba = base
+        .rgba()
+        .chunks_exact(4)
+        .flat_map(|pixel| [r, g, b, pixel[3]])
+        .collect();
+    Image::new_owned(rgba, base.width(), base.height())
+}
+
+fn current_theme(app: &AppHandle) -> Theme {
+    main_window(app)
+        .and_then(|window| window.theme().ok())
+        .unwrap_or(Theme::Light)
+}
+
+// Shows when the countdown ends while it is still running
+fn tooltip(app: &AppHandle) -> String {
+    match primary_end_date(&app.state::<NotificationState>()) {
+        Some(end) if end > clock::now() => t_args("tray-tooltip-ends", &[("date", format::format_date_time(end).into())]),
+        _ => t("tray-tooltip"),
+    }
+}
+
+fn menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let from_clipboard = MenuItem::with_id(app, MENU_TIMER_FROM_CLIPBOARD, t("tray-timer-from-clipboard"), true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, MENU_QUIT, t("tray-quit"), true, None::<&str>)?;
+    Menu::with_items(app, &[&from_clipboard, &quit])
+}
+
+pub fn setup(app: &App) -> tauri::Result<()> {
+    // Setup system tray only if we have a default icon
+    if let Some(icon) = app.default_window_icon() {
+        let tray = TrayIconBuilder::with_id(TRAY_ID)
+            .icon(themed_icon(icon, current_theme(app.handle())))
+            .title(t("tray-title"))
+            .tooltip(tooltip(app.handle()))
+            .menu(&menu(app.handle())?)
+            // Left click keeps opening the window; the menu is on right click
+            .show_menu_on_left_click(false)
+            .on_menu_event(|app, event| {
+                match event.id().as_ref() {
+                    MENU_TIMER_FROM_CLIPBOARD => clipboard::propose_from_tray(app),
+                    MENU_QUIT => quit_from_tray(app),
+                    _ => {}
+                }
+            })
+            .on_tray_icon_event(|tray, event| {
+                if let TrayIconEvent::Click { .. } = event {
+                    let app = tray.app_handle();
+                    if let Some(window) = main_window(app) {
+                        let _ = window.show();
+                        let _ = window.set_focus();
+                    }
+                }
+            })
+            .build(app);
+        // Missing tray support shouldn't keep the app from starting; get_tray_status reports it
+        if let Err(e) = tray {
+            eprintln!("Failed to create tray icon: {}", e);
+        }
+    }
+    Ok(())
+}
+
+// On Linux the tray icon is published over StatusNotifierItem (AppIndicator), which needs a
+// watcher on the session bus. Without one, X11 sessions fall back to the legacy tray and
+// Wayland sessions, e.g. stock GNOME, show nothing.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn host_status() -> TrayStatus {
+    let x11 = std::env::var("XDG_SESSION_TYPE").map(|session| session == "x11").unwrap_or_else(|_| {
+        std::env::var_os("DISPLAY").is_some() && std::env::var_os("WAYLAND_DISPLAY").is_none()
+    });
+    match dbus::name_has_owner("org.kde.StatusNotifierWatcher") {
+        Some(true) => TrayStatus::Available,
+        _ if x11 => TrayStatus::Legacy,
+        Some(false) => TrayStatus::Unavailable,
+        None => TrayStatus::Unknown,
+    }
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn host_status() -> TrayStatus {
+    TrayStatus::Available
+}
+
+// The last detected status, since checking can mean a round trip over D-Bus
+#[derive(Default)]
+pub struct TrayState {
+    pub status: Arc<Mutex<Option<TrayStatus>>>,
+}
+
+pub fn status(app: &AppHandle) -> TrayStatus {
+    let status = if app.tray_by_id(TRAY_ID).is_none() { TrayStatus::Missing } else { host_status() };
+    if let Ok(mut cached) = app.state::<TrayState>().status.lock() {
+        *cached = Some(status);
+    }
+    status
+}
+
+// Whether hiding the window is safe, i.e. the tray icon is there to bring it back
+pub fn uses_tray(app: &AppHandle) -> bool {
+    let mode = app.state::<SettingsState>().lock().map(|settings| settings.tray_mode).unwrap_or_default();
+    match mode {
+        TrayMode::Tray => true,
+        TrayMode::NoTray => false,
+        // Until detection finishes, keep the behavior the app always had
+        TrayMode::Auto => app
+            .state::<TrayState>()
+            .status
+            .lock()
+            .ok()
+            .and_then(|status| *status)
+            .is_none_or(|status| matches!(status, TrayStatus::Available | TrayStatus::Legacy | TrayStatus::Unknown)),
+    }
+}
+
+// Shows or hides the icon to match the mode. A window already hidden to the tray comes back
+// minimized when there's no longer a tray to restore it from.
+pub fn apply_mode(app: &AppHandle) {
+    let uses_tray = uses_tray(app);
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        if let Err(e) = tray.set_visible(uses_tray) {
+            eprintln!("Failed to update tray visibility: {}", e);
+        }
+    }
+    if let Some(window) = main_window(app).filter(|window| !uses_tray && !window.is_visible().unwrap_or(true)) {
+        let _ = window.minimize();
+        let _ = window.show();
+    }
+}
+
+fn hide_main_window(app: &AppHandle) {
+    let Some(window) = main_window(app) else {
+        return;
+    };
+    if uses_tray(app) {
+        let _ = window.hide();
+    } else {
+        let _ = window.minimize();
+    }
+}
+
+// The close button, as the setting says. Read each time so a change applies straight away.
+pub fn close_requested(app: &AppHandle) {
+    if kiosk::ensure_unlocked(app).is_err() {
+        return;
+    }
+    let behavior = app.state::<SettingsState>().lock().map(|settings| settings.close_behavior).unwrap_or_default();
+    match behavior {
+        CloseBehavior::Hide => hide_main_window(app),
+        CloseBehavior::Quit => app.exit(0),
+        CloseBehavior::Ask => {
+            if let Err(e) = app.emit("close-requested", ()) {
+                eprintln!("Failed to emit close request: {}", e);
+                hide_main_window(app);
+            }
+        }
+    }
+}
+
+// The user's answer to the close prompt, optionally kept as the setting so it isn't asked again
+#[tauri::command]
+pub async fn answer_close_prompt(app: AppHandle, state: State<'_, SettingsState>, quit: bool, remember: bool) -> Result<(), String> {
+    kiosk::ensure_unlocked(&app)?;
+    if remember {
+        state.lock()?.close_behavior = if quit { CloseBehavior::Quit } else { CloseBehavior::Hide };
+        state.save()?;
+    }
+    if quit {
+        app.exit(0);
+    } else {
+        hide_main_window(&app);
+    }
+    Ok(())
+}
+
+// The soonest countdown ending within the warning window, if the user wants to be asked
+fn quit_warning(app: &AppHandle) -> Option<QuitWarning> {
+    let confirm = app.state::<SettingsState>().lock().map(|settings| settings.confirm_quit_when_running).unwrap_or(true);
+    if !confirm {
+        return None;
+    }
+    let now = clock::now();
+    let soon = |end: &DateTime<Utc>| *end > now && *end <= now + Duration::minutes(QUIT_WARNING_WINDOW_MINUTES);
+    let primary = primary_end_date(&app.state::<NotificationState>())
+        .filter(soon)
+        .map(|ends_at| QuitWarning { label: None, ends_at });
+    let timer = app.state::<TimerState>().lock().ok().and_then(|store| {
+        store
+            .timers
+            .iter()
+            .filter(|timer| timer.is_active() && soon(&timer.end_date))
+            .min_by_key(|timer| timer.end_date)
+            .map(|timer| QuitWarning { label: Some(timer.label.clone()), ends_at: timer.end_date })
+    });
+    [primary, timer].into_iter().flatten().min_by_key(|warning| warning.ends_at)
+}
+
+// Quitting stops every reminder, so a countdown about to end is handed to the frontend to
+// confirm through the "quit-warning" event instead
+fn quit_from_tray(app: &AppHandle) {
+    if let Err(e) = kiosk::ensure_unlocked(app) {
+        eprintln!("Ignoring Quit from the tray: {}", e);
+        return;
+    }
+    let Some(warning) = quit_warning(app) else {
+        app.exit(0);
+        return;
+    };
+    if let Some(window) = main_window(app) {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    if let Err(e) = app.emit("quit-warning", warning) {
+        eprintln!("Failed to emit quit warning: {}", e);
+        app.exit(0);
+    }
+}
+
+// Exits unless a countdown ends within the hour and `force` isn't set; the warning is returned
+// for the UI to confirm, after which it calls again with `force`
+#[tauri::command]
+pub async fn quit_app(app: AppHandle, force: bool) -> Result<Option<QuitWarning>, String> {
+    kiosk::ensure_unlocked(&app)?;
+    if !force {
+        if let Some(warning) = quit_warning(&app) {
+            return Ok(Some(warning));
+        }
+    }
+    app.exit(0);
+    Ok(None)
+}
+
+// Detects the tray host in the background so startup doesn't wait on it
+pub fn detect(app: AppHandle) {
+    tauri::async_runtime::spawn_blocking(move || {
+        status(&app);
+        apply_mode(&app);
+    });
+}
+
+// Lets the UI offer another way back in when the icon can't be seen
+#[tauri::command]
+pub async fn get_tray_status(app: AppHandle) -> Result<TrayStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || status(&app))
+        .await
+        .map_err(|e| format!("Failed to check tray status: {}", e))
+}
+
+pub fn apply_theme(app: &AppHandle, theme: Theme) {
+    let (Some(tray), Some(icon)) = (app.tray_by_id(TRAY_ID), app.default_window_icon()) else {
+        return;
+    };
+    if let Err(e) = tray.set_icon(Some(themed_icon(icon, theme))) {
+        eprintln!("Failed to update tray icon for theme change: {}", e);
+    }
+}
+
+pub fn refresh_labels(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    if let Err(e) = tray.set_title(Some(t("tray-title"))) {
+        eprintln!("Failed to update tray title: {}", e);
+    }
+    if let Err(e) = tray.set_tooltip(Some(tooltip(app))) {
+        eprintln!("Failed to update tray tooltip: {}", e);
+    }
+    if let Err(e) = menu(app).and_then(|menu| tray.set_menu(Some(menu))) {
+        eprintln!("Failed to update tray menu: {}", e);
+    }
+}
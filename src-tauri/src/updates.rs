@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::http;
+use crate::persistence;
+
+const RELEASES_API: &str = "https://api.github.com/repos/jonit-dev/hourglass-time/releases";
+// Notes for a given version don't change, but which version is the latest does
+const LATEST_REFRESH_HOURS: i64 = 6;
+
+#[derive(Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    pub body: Option<String>,
+    pub html_url: String,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+pub struct UpdateCheck {
+    pub current_version: String,
+    pub latest_version: String,
+    pub update_available: bool,
+    pub notes: Option<String>,
+    pub url: String,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReleaseNotes {
+    pub version: String,
+    pub notes: Option<String>,
+    pub url: String,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+impl From<Release> for ReleaseNotes {
+    fn from(release: Release) -> Self {
+        Self {
+            version: release.tag_name.trim_start_matches('v').to_string(),
+            notes: release.body.filter(|notes| !notes.trim().is_empty()),
+            url: release.html_url,
+            published_at: release.published_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Changelog {
+    pub current_version: String,
+    // None when there's no published release for this build, e.g. in development
+    pub current: Option<ReleaseNotes>,
+    pub latest: Option<ReleaseNotes>,
+    pub update_available: bool,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ChangelogCache {
+    pub releases: BTreeMap<String, ReleaseNotes>,
+    pub latest: Option<String>,
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Default)]
+pub struct ChangelogState {
+    pub cache: Arc<Mutex<ChangelogCache>>,
+    pub data_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl ChangelogState {
+    pub fn lock(&self) -> Result<MutexGuard<'_, ChangelogCache>, String> {
+        self.cache.lock().map_err(|e| format!("Failed to lock changelog: {}", e))
+    }
+
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let loaded: ChangelogCache = persistence::load_json(&path);
+        *self.lock()? = loaded;
+        *self.data_path.lock().map_err(|e| format!("Failed to lock changelog path: {}", e))? = Some(path);
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = self.data_path.lock().map_err(|e| format!("Failed to lock changelog path: {}", e))?.clone();
+        match path {
+            Some(path) => persistence::save_json(&path, &*self.lock()?),
+            None => Ok(()),
+        }
+    }
+
+    fn cached(&self, version: &str) -> Option<ReleaseNotes> {
+        self.lock().ok()?.releases.get(version).cloned()
+    }
+
+    fn remember(&self, notes: &ReleaseNotes, latest: bool) -> Result<(), String> {
+        {
+            let mut cache = self.lock()?;
+            cache.releases.insert(notes.version.clone(), notes.clone());
+            if latest {
+                cache.latest = Some(notes.version.clone());
+                cache.checked_at = Some(Utc::now());
+            }
+        }
+        self.save()
+    }
+}
+
+pub fn current_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+// "v1.10.0-beta.2" becomes [1, 10, 0]; pre-release suffixes don't take part in the comparison
+fn version_parts(version: &str) -> Vec<u64> {
+    let version = version.trim().trim_start_matches('v');
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    core.split('.').map(|part| part.parse().unwrap_or(0)).collect()
+}
+
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    let (mut candidate, mut current) = (version_parts(candidate), version_parts(current));
+    let len = candidate.len().max(current.len());
+    candidate.resize(len, 0);
+    current.resize(len, 0);
+    candidate > current
+}
+
+// `path` is relative to the releases endpoint, e.g. "latest" or "tags/v1.2.0"
+pub async fn fetch_release(path: &str) -> Result<Release, String> {
+    let response = http::client()?
+        .get(format!("{}/{}", RELEASES_API, path))
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach GitHub: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("GitHub answered {}", response.status()));
+    }
+    response.json().await.map_err(|e| format!("Failed to read release: {}", e))
+}
+
+// Asks for the latest published release right away, whatever the background updater is doing,
+// so the UI can offer it
+#[tauri::command]
+pub async fn check_for_updates_now() -> Result<UpdateCheck, String> {
+    let release = ReleaseNotes::from(fetch_release("latest").await?);
+    Ok(UpdateCheck {
+        current_version: current_version().to_string(),
+        update_available: is_newer(&release.version, current_version()),
+        latest_version: release.version,
+        notes: release.notes,
+        url: release.url,
+        published_at: release.published_at,
+    })
+}
+
+// Release notes for the running version and the latest one, for a "what's new" view. Both are
+// cached on disk; a failed lookup falls back to the cache rather than failing the whole call.
+#[tauri::command]
+pub async fn get_changelog(state: State<'_, ChangelogState>) -> Result<Changelog, String> {
+    let version = current_version();
+    let mut failure = None;
+
+    let current = match state.cached(version) {
+        Some(notes) => Some(notes),
+        None => match fetch_release(&format!("tags/v{}", version)).await {
+            Ok(release) => {
+                let notes = ReleaseNotes::from(release);
+                state.remember(&notes, false)?;
+                Some(notes)
+            }
+            Err(e) => {
+                failure = Some(e);
+                None
+            }
+        },
+    };
+
+    let (cached_latest, checked_at) = {
+        let cache = state.lock()?;
+        let latest = cache.latest.as_ref().and_then(|latest| cache.releases.get(latest).cloned());
+        (latest, cache.checked_at)
+    };
+    let fresh = checked_at.is_some_and(|at| Utc::now() - at < Duration::hours(LATEST_REFRESH_HOURS));
+    let latest = match cached_latest {
+        Some(notes) if fresh => Some(notes),
+        cached_latest => match fetch_release("latest").await {
+            Ok(release) => {
+                let notes = ReleaseNotes::from(release);
+                state.remember(&notes, true)?;
+                Some(notes)
+            }
+            Err(e) => {
+                failure = Some(e);
+                cached_latest
+            }
+        },
+    };
+
+    if current.is_none() && latest.is_none() {
+        if let Some(e) = failure {
+            return Err(e);
+        }
+    }
+    Ok(Changelog {
+        current_version: version.to_string(),
+        update_available: latest.as_ref().is_some_and(|latest| is_newer(&latest.version, version)),
+        current,
+        latest,
+    })
+}
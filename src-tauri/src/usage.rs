@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::persistence;
+use crate::settings::SettingsState;
+
+#[derive(Clone, Copy)]
+pub enum UsageEvent {
+    Launch,
+    Reminder,
+    Completed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct UsageCounts {
+    pub launches: u32,
+    pub reminders: u32,
+    pub completed: u32,
+}
+
+impl UsageCounts {
+    fn add(&mut self, event: UsageEvent, count: u32) {
+        let counter = match event {
+            UsageEvent::Launch => &mut self.launches,
+            UsageEvent::Reminder => &mut self.reminders,
+            UsageEvent::Completed => &mut self.completed,
+        };
+        *counter = counter.saturating_add(count);
+    }
+}
+
+// Per-day counts kept in usage.json; only written while the user has opted in, and never sent
+// anywhere
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct UsageLog {
+    pub days: BTreeMap<NaiveDate, UsageCounts>,
+}
+
+#[derive(Default)]
+pub struct UsageState {
+    pub log: Arc<Mutex<UsageLog>>,
+    pub data_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl UsageState {
+    pub fn lock(&self) -> Result<MutexGuard<'_, UsageLog>, String> {
+        self.log.lock().map_err(|e| format!("Failed to lock usage statistics: {}", e))
+    }
+
+    pub fn load(&self, path: PathBuf) -> Result<(), String> {
+        let loaded: UsageLog = persistence::load_json(&path);
+        *self.lock()? = loaded;
+        *self.data_path.lock().map_err(|e| format!("Failed to lock usage statistics path: {}", e))? = Some(path);
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = self.data_path.lock().map_err(|e| format!("Failed to lock usage statistics path: {}", e))?.clone();
+        match path {
+            Some(path) => persistence::save_json(&path, &*self.lock()?),
+            None => Ok(()),
+        }
+    }
+}
+
+// Counts the event for today, if usage statistics are turned on
+pub fn record(app: &AppHandle, event: UsageEvent, count: u32) {
+    let enabled = app.state::<SettingsState>().lock().map(|settings| settings.usage_stats).unwrap_or(false);
+    if !enabled || count == 0 {
+        return;
+    }
+    let state = app.state::<UsageState>();
+    let recorded = state.lock().map(|mut log| log.days.entry(Local::now().date_naive()).or_default().add(event, count));
+    if let Err(e) = recorded.and_then(|_| state.save()) {
+        eprintln!("Failed to record usage: {}", e);
+    }
+}
+
+#[derive(Serialize)]
+pub struct UsageDay {
+    pub date: NaiveDate,
+    #[serde(flatten)]
+    pub counts: UsageCounts,
+}
+
+#[derive(Serialize)]
+pub struct UsageStats {
+    pub enabled: bool,
+    // The first day anything was counted
+    pub since: Option<NaiveDate>,
+    pub totals: UsageCounts,
+    pub days: Vec<UsageDay>,
+}
+
+#[tauri::command]
+pub async fn get_usage_stats(
+    settings_state: State<'_, SettingsState>,
+    usage_state: State<'_, UsageState>,
+) -> Result<UsageStats, String> {
+    let enabled = settings_state.lock()?.usage_stats;
+    let log = usage_state.lock()?;
+    let mut totals = UsageCounts::default();
+    for counts in log.days.values() {
+        totals.add(UsageEvent::Launch, counts.launches);
+        totals.add(UsageEvent::Reminder, counts.reminders);
+        totals.add(UsageEvent::Completed, counts.completed);
+    }
+    Ok(UsageStats {
+        enabled,
+        since: log.days.keys().next().copied(),
+        totals,
+        days: log.days.iter().map(|(date, counts)| UsageDay { date: *date, counts: *counts }).collect(),
+    })
+}
+
+// Turning the setting off keeps what was collected; this deletes it
+#[tauri::command]
+pub async fn clear_usage_stats(state: State<'_, UsageState>) -> Result<(), String> {
+    state.lock()?.days.clear();
+    state.save()
+}
@@ -0,0 +1,124 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::http;
+use crate::settings::SettingsState;
+use crate::share::CountdownSummary;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+// The remote countdown as last seen. A failed fetch keeps the last good countdown so the
+// display doesn't blank out over a network hiccup.
+#[derive(Serialize, Clone, Default)]
+pub struct ViewerStatus {
+    pub source: Option<String>,
+    pub countdown: Option<CountdownSummary>,
+    pub error: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Default)]
+pub struct ViewerState {
+    pub status: Arc<Mutex<ViewerStatus>>,
+}
+
+// While viewing, local edit commands are refused; see kiosk::check_command
+pub fn is_active(app: &AppHandle) -> bool {
+    app.state::<SettingsState>().lock().map(|settings| settings.viewer_source.is_some()).unwrap_or(false)
+}
+
+pub fn validate_source(source: &str) -> bool {
+    tauri::Url::parse(source).is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host().is_some())
+}
+
+async fn fetch(client: &reqwest::Client, source: &str, timer: Option<u64>) -> Result<CountdownSummary, String> {
+    let mut url = format!("{}/api/countdown", source.trim_end_matches('/'));
+    if let Some(id) = timer {
+        url.push_str(&format!("?timer={}", id));
+    }
+    let response = client.get(url).send().await.map_err(|e| format!("Failed to reach {}: {}", source, e))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("{} answered {}: {}", source, status, body.trim()));
+    }
+    response.json().await.map_err(|e| format!("Failed to read countdown from {}: {}", source, e))
+}
+
+fn publish(app: &AppHandle, update: impl FnOnce(&mut ViewerStatus)) {
+    let status = match app.state::<ViewerState>().status.lock() {
+        Ok(mut status) => {
+            update(&mut status);
+            status.clone()
+        }
+        Err(e) => {
+            eprintln!("Failed to lock viewer status: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = app.emit("viewer-updated", status) {
+        eprintln!("Failed to emit viewer update: {}", e);
+    }
+}
+
+// Follows another instance's countdown through its local API instead of keeping one here
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut client = None;
+        loop {
+            let (source, timer) = app
+                .state::<SettingsState>()
+                .lock()
+                .map(|settings| (settings.viewer_source.clone(), settings.viewer_timer))
+                .unwrap_or((None, None));
+            let Some(source) = source else {
+                let was_viewing = app.state::<ViewerState>().status.lock().map(|status| status.source.is_some()).unwrap_or(false);
+                if was_viewing {
+                    publish(&app, |status| *status = ViewerStatus::default());
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            };
+            if client.is_none() {
+                client = http::client().map_err(|e| eprintln!("{}", e)).ok();
+            }
+            if let Some(client) = &client {
+                let result = fetch(client, &source, timer).await;
+                publish(&app, |status| {
+                    if status.source.as_deref() != Some(source.as_str()) {
+                        *status = ViewerStatus::default();
+                    }
+                    status.source = Some(source);
+                    match result {
+                        Ok(countdown) => {
+                            status.countdown = Some(countdown);
+                            status.error = None;
+                            status.updated_at = Some(Utc::now());
+                        }
+                        Err(e) => status.error = Some(e),
+                    }
+                });
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_viewer_status(state: State<'_, ViewerState>) -> Result<ViewerStatus, String> {
+    state.status.lock().map(|status| status.clone()).map_err(|e| format!("Failed to lock viewer status: {}", e))
+}
+
+// The way out of viewer mode, since settings can't be edited while in it
+#[tauri::command]
+pub async fn stop_viewing(state: State<'_, SettingsState>) -> Result<(), String> {
+    {
+        let mut settings = state.lock()?;
+        settings.viewer_source = None;
+        settings.viewer_timer = None;
+    }
+    state.save()
+}
@@ -0,0 +1,196 @@
+use std::path::{Path, PathBuf};
+#[cfg(not(windows))]
+use std::process::Command;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::persistence;
+use crate::settings::SettingsState;
+use crate::share::{self, Canvas, Countdown, ImageStyle};
+
+const REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+// The wallpaper we replaced, kept on disk so it can be put back even after a crash
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct WallpaperRecord {
+    original: Option<PathBuf>,
+}
+
+#[cfg(windows)]
+fn current_wallpaper() -> Result<PathBuf, String> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{SystemParametersInfoW, SPI_GETDESKWALLPAPER};
+
+    let mut buffer = [0u16; 260];
+    // SAFETY: the buffer length matches the size passed in
+    let ok = unsafe { SystemParametersInfoW(SPI_GETDESKWALLPAPER, buffer.len() as u32, buffer.as_mut_ptr().cast(), 0) } != 0;
+    let length = buffer.iter().position(|c| *c == 0).unwrap_or(buffer.len());
+    if !ok || length == 0 {
+        return Err("Failed to read the desktop wallpaper".to_string());
+    }
+    Ok(PathBuf::from(String::from_utf16_lossy(&buffer[..length])))
+}
+
+#[cfg(windows)]
+fn set_wallpaper(path: &Path) -> Result<(), String> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        SystemParametersInfoW, SPIF_SENDCHANGE, SPIF_UPDATEINIFILE, SPI_SETDESKWALLPAPER,
+    };
+
+    let mut wide: Vec<u16> = path.as_os_str().to_string_lossy().encode_utf16().chain([0]).collect();
+    // SAFETY: a NUL-terminated wide string that outlives the call
+    let ok = unsafe {
+        SystemParametersInfoW(SPI_SETDESKWALLPAPER, 0, wide.as_mut_ptr().cast(), SPIF_UPDATEINIFILE | SPIF_SENDCHANGE)
+    } != 0;
+    if !ok {
+        return Err("Failed to set the desktop wallpaper".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn run(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if !output.status.success() {
+        return Err(format!("{} exited with {}", program, output.status));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn current_wallpaper() -> Result<PathBuf, String> {
+    run("osascript", &["-e", "tell application \"System Events\" to get picture of current desktop"]).map(PathBuf::from)
+}
+
+#[cfg(target_os = "macos")]
+fn set_wallpaper(path: &Path) -> Result<(), String> {
+    let script = format!(
+        "tell application \"System Events\" to tell every desktop to set picture to \"{}\"",
+        path.display().to_string().replace('\\', "\\\\").replace('"', "\\\"")
+    );
+    run("osascript", &["-e", &script]).map(|_| ())
+}
+
+// GNOME and the desktops that share its settings schema
+#[cfg(all(unix, not(target_os = "macos")))]
+fn current_wallpaper() -> Result<PathBuf, String> {
+    let value = run("gsettings", &["get", "org.gnome.desktop.background", "picture-uri"])?;
+    let uri = value.trim_matches('\'');
+    tauri::Url::parse(uri)
+        .ok()
+        .and_then(|url| url.to_file_path().ok())
+        .ok_or_else(|| format!("Unsupported wallpaper location: {}", uri))
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn set_wallpaper(path: &Path) -> Result<(), String> {
+    let uri = tauri::Url::from_file_path(path).map_err(|_| format!("Invalid wallpaper path: {}", path.display()))?;
+    for key in ["picture-uri", "picture-uri-dark"] {
+        run("gsettings", &["set", "org.gnome.desktop.background", key, uri.as_str()])?;
+    }
+    Ok(())
+}
+
+// The original wallpaper with the countdown panel in its lower right corner
+fn render(original: &Path, countdown: &Countdown) -> Result<Vec<u8>, String> {
+    let image = image::open(original).map_err(|e| format!("Failed to open wallpaper {}: {}", original.display(), e))?;
+    let image = image.to_rgba8();
+    let (width, height) = image.dimensions();
+    let mut canvas = Canvas::from_rgba(width, height, image.into_raw());
+    let font = share::load_font()?;
+    let panel_width = width as f32 * 0.32;
+    let panel_height = panel_width * 0.525;
+    let margin = width as f32 * 0.03;
+    let area = (width as f32 - panel_width - margin, height as f32 - panel_height - margin * 2.0, panel_width, panel_height);
+    share::draw_countdown(&mut canvas, &font, countdown, ImageStyle::Dark, area, 210);
+    canvas.to_png()
+}
+
+// Puts the original wallpaper back, for when the app exits
+pub fn restore(app: &AppHandle) -> Result<(), String> {
+    let data_dir = app.path().app_data_dir().map_err(|e| format!("Failed to resolve data directory: {}", e))?;
+    let record_path = data_dir.join("wallpaper.json");
+    let mut record: WallpaperRecord = persistence::load_json(&record_path);
+    let Some(original) = record.original.take() else {
+        return Ok(());
+    };
+    set_wallpaper(&original)?;
+    persistence::save_json(&record_path, &record)
+}
+
+// Once a minute, draws the main countdown onto a copy of the wallpaper and shows that instead.
+// Turning the setting off, or the countdown going away, puts the original back.
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let (Ok(data_dir), Ok(cache_dir)) = (app.path().app_data_dir(), app.path().app_cache_dir()) else {
+            eprintln!("Failed to resolve directories for the wallpaper countdown");
+            return;
+        };
+        let record_path = data_dir.join("wallpaper.json");
+        let mut record: WallpaperRecord = persistence::load_json(&record_path);
+        let mut generation = 0u32;
+        let mut tick = tokio::time::interval(REFRESH_INTERVAL);
+        loop {
+            tick.tick().await;
+            let enabled = app.state::<SettingsState>().lock().map(|settings| settings.wallpaper_countdown).unwrap_or(false);
+            let countdown = Countdown::load(&app, None).ok().filter(|_| enabled);
+
+            let Some(countdown) = countdown else {
+                if let Some(original) = record.original.take() {
+                    let restored = tauri::async_runtime::spawn_blocking(move || set_wallpaper(&original))
+                        .await
+                        .map_err(|e| e.to_string())
+                        .and_then(|result| result);
+                    if let Err(e) = restored {
+                        eprintln!("Failed to restore wallpaper: {}", e);
+                    }
+                    if let Err(e) = persistence::save_json(&record_path, &record) {
+                        eprintln!("Failed to save wallpaper record: {}", e);
+                    }
+                }
+                continue;
+            };
+
+            if record.original.is_none() {
+                let current = tauri::async_runtime::spawn_blocking(current_wallpaper)
+                    .await
+                    .map_err(|e| e.to_string())
+                    .and_then(|result| result);
+                match current {
+                    Ok(original) => {
+                        record.original = Some(original);
+                        if let Err(e) = persistence::save_json(&record_path, &record) {
+                            eprintln!("Failed to save wallpaper record: {}", e);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to read wallpaper: {}", e);
+                        continue;
+                    }
+                }
+            }
+            let Some(original) = record.original.clone() else {
+                continue;
+            };
+            // Alternate file names; some desktops ignore a "change" to the same path
+            generation = (generation + 1) % 2;
+            let target = cache_dir.join(format!("wallpaper-countdown-{}.png", generation));
+            let result = tauri::async_runtime::spawn_blocking(move || {
+                let png = render(&original, &countdown)?;
+                std::fs::create_dir_all(target.parent().unwrap_or(Path::new(".")))
+                    .map_err(|e| format!("Failed to create cache directory: {}", e))?;
+                std::fs::write(&target, png).map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+                set_wallpaper(&target)
+            })
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|result| result);
+            if let Err(e) = result {
+                eprintln!("Failed to update wallpaper countdown: {}", e);
+            }
+        }
+    });
+}